@@ -1,8 +1,8 @@
-/// The example requires to geckodriver have been run
+//! The example requires to geckodriver have been run
 
-#[cfg(feature = "fantoccini_backend")]
+#[cfg(not(feature = "thirtyfour_backend"))]
 fn main() {
-    panic!("This example requires 'fantoccini_backend' feature")
+    panic!("This example requires 'thirtyfour_backend' feature")
 }
 
 #[cfg(feature = "thirtyfour_backend")]
@@ -1,6 +1,6 @@
-/// The example requires a webdriver have been run
+//! The example requires a webdriver have been run
 
-#[cfg(feature = "thirtyfour_backend")]
+#[cfg(not(feature = "fantoccini_backend"))]
 fn main() {
     panic!("This example requires 'fantoccini_backend' feature")
 }
@@ -8,12 +8,15 @@ fn main() {
 #[cfg(feature = "fantoccini_backend")]
 #[tokio::main]
 async fn main() {
-    use fantoccini::Client;
-    use siderunner::{parse, Runner};
+    use fantoccini::ClientBuilder;
+    use siderunner::{parse, webdriver::fantoccini::Client, Runner};
 
-    let client = Client::new("http://localhost:4444")
+    let webdriver = "http://localhost:4444";
+    let client = ClientBuilder::native()
+        .connect(webdriver)
         .await
         .expect("can't connect to webdriver");
+    let client = Client::new(client, webdriver).expect("can't parse a webdriver url");
     let wiki = std::fs::File::open("examples/wiki.side").unwrap();
     let file = parse(wiki).expect("parsing can't be done...");
     let mut runner = Runner::new(client);
@@ -0,0 +1,22 @@
+function dragAndDropToObject(source, target) {
+    var dataTransfer = new DataTransfer();
+
+    function fire(element, type) {
+        var rect = element.getBoundingClientRect();
+        var event = new DragEvent(type, {
+            bubbles: true,
+            cancelable: true,
+            view: window,
+            clientX: rect.left + rect.width / 2,
+            clientY: rect.top + rect.height / 2,
+            dataTransfer: dataTransfer
+        });
+        element.dispatchEvent(event);
+    }
+
+    fire(source, 'dragstart');
+    fire(target, 'dragenter');
+    fire(target, 'dragover');
+    fire(target, 'drop');
+    fire(source, 'dragend');
+}
@@ -0,0 +1,10 @@
+function submitForm(element) {
+    var form = element.form || element;
+    // requestSubmit validates the form and fires a submit event as a click on a submit button does,
+    // browsers which lack it can only submit the form silently.
+    if (typeof form.requestSubmit === 'function') {
+        form.requestSubmit();
+    } else {
+        form.submit();
+    }
+}
@@ -0,0 +1,21 @@
+function watchDrop(target) {
+    var watch = { dropped: false };
+    watch.listener = function (event) {
+        if (target.contains(event.target)) {
+            watch.dropped = true;
+        }
+    };
+    document.addEventListener('drop', watch.listener, true);
+    window.__siderunnerDropWatch = watch;
+}
+
+function isDropped() {
+    var watch = window.__siderunnerDropWatch;
+    if (!watch) {
+        // The page was left, so the drop did have an effect.
+        return true;
+    }
+    document.removeEventListener('drop', watch.listener, true);
+    delete window.__siderunnerDropWatch;
+    return watch.dropped;
+}
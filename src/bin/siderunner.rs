@@ -0,0 +1,321 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! A command line interface for running `.side` files.
+//!
+//! It requires a WebDriver server (e.g. `chromedriver` or `geckodriver`) have been run.
+
+use serde_json::Value as Json;
+use siderunner::{
+    healing::HealedTarget,
+    junit::{Failure, TestCase, TestSuite},
+    parse_all, File, Runner, RunnerError,
+};
+use std::{
+    error::Error,
+    path::{Path, PathBuf},
+    process,
+    time::{Duration, Instant},
+};
+use structopt::StructOpt;
+use thirtyfour::{Capabilities, DesiredCapabilities, WebDriver};
+
+#[derive(Debug, StructOpt)]
+#[structopt(about = "Runs .side files produced by Selenium IDE")]
+struct Opt {
+    /// A URL of a WebDriver server.
+    #[structopt(long, default_value = "http://localhost:4444")]
+    webdriver: String,
+    /// A browser to run tests in: chrome, firefox, edge, safari or opera.
+    #[structopt(long, default_value = "chrome", parse(try_from_str = browser_capabilities))]
+    browser: Json,
+    /// Additional capabilities as a JSON object, e.g. '{"goog:chromeOptions":{"args":["--headless"]}}'.
+    #[structopt(long, parse(try_from_str = serde_json::from_str))]
+    capabilities: Option<Json>,
+    /// Overrides a URL which relative URLs of files are resolved against.
+    #[structopt(long)]
+    base_url: Option<String>,
+    /// Runs only a test with a given name. May be repeated.
+    #[structopt(long = "test", number_of_values = 1)]
+    tests: Vec<String>,
+    /// Runs only a suite with a given name. May be repeated.
+    #[structopt(long = "suite", number_of_values = 1)]
+    suites: Vec<String>,
+    /// Sets a variable before a run, e.g. 'user=admin'.
+    /// A value is parsed as JSON if possible and used as a string otherwise.
+    #[structopt(long = "var", number_of_values = 1, parse(try_from_str = parse_variable))]
+    variables: Vec<(String, Json)>,
+    /// Writes a report in JUnit XML format to a given file.
+    #[structopt(long, parse(from_os_str))]
+    junit: Option<PathBuf>,
+    /// Saves a screenshot and a page source of each failed command to a given directory.
+    #[structopt(long, parse(from_os_str))]
+    artifacts: Option<PathBuf>,
+    /// Falls back to alternative locators recorded by Selenium IDE, reports them
+    /// and writes a corrected copy of each file next to it as `<name>.healed.side`.
+    #[structopt(long)]
+    heal: bool,
+    /// Files to run.
+    #[structopt(required = true, parse(from_os_str))]
+    files: Vec<PathBuf>,
+}
+
+fn browser_capabilities(browser: &str) -> Result<Json, String> {
+    let capabilities = match browser {
+        "chrome" => DesiredCapabilities::chrome().get().clone(),
+        "firefox" => DesiredCapabilities::firefox().get().clone(),
+        "edge" => DesiredCapabilities::edge().get().clone(),
+        "safari" => DesiredCapabilities::safari().get().clone(),
+        "opera" => DesiredCapabilities::opera().get().clone(),
+        _ => return Err(format!("unsupported browser {:?}", browser)),
+    };
+
+    Ok(capabilities)
+}
+
+fn parse_variable(s: &str) -> Result<(String, Json), String> {
+    let (name, value) = s
+        .split_once('=')
+        .ok_or_else(|| format!("expected NAME=VALUE but got {:?}", s))?;
+    let value = serde_json::from_str(value).unwrap_or_else(|_| Json::String(value.to_owned()));
+
+    Ok((name.to_owned(), value))
+}
+
+/// Formats an error followed by its causes.
+fn chain(err: &dyn Error) -> String {
+    let mut message = err.to_string();
+    let mut source = err.source();
+    while let Some(err) = source {
+        message.push_str(&format!(": {}", err));
+        source = err.source();
+    }
+
+    message
+}
+
+fn report(
+    suite: &mut TestSuite,
+    file: &File,
+    name: &str,
+    time: Duration,
+    result: Result<(), RunnerError>,
+) {
+    match &result {
+        Ok(()) => println!("{} ... ok", name),
+        Err(err) => {
+            println!("{} ... FAILED", name);
+            println!("    {}", chain(err));
+            if let Some(path) = &err.screenshot {
+                println!("    screenshot: {}", path.display());
+            }
+            if let Some(path) = &err.page_source {
+                println!("    page source: {}", path.display());
+            }
+        }
+    }
+
+    suite.add(file, name, time, &result);
+}
+
+async fn run_file(
+    opt: &Opt,
+    capabilities: &Json,
+    file: &File,
+    suite: &mut TestSuite,
+) -> Result<Vec<HealedTarget>, String> {
+    let client = WebDriver::new(&opt.webdriver, capabilities)
+        .await
+        .map_err(|err| format!("can't connect to a webdriver {}: {}", opt.webdriver, err))?;
+
+    let mut runner = Runner::new(&client);
+    if let Some(dir) = &opt.artifacts {
+        runner.set_artifacts_dir(dir);
+    }
+    runner.set_healing(opt.heal);
+    for (name, value) in &opt.variables {
+        runner.save_value(name.clone(), value.clone());
+    }
+
+    if opt.tests.is_empty() && opt.suites.is_empty() {
+        for (index, test) in file.tests.iter().enumerate() {
+            let start = Instant::now();
+            let result = runner.run_test_by_index(file, index).await;
+            report(suite, file, &test.name, start.elapsed(), result);
+        }
+    } else {
+        for test in &opt.tests {
+            let start = Instant::now();
+            let result = runner.run_test(file, test).await;
+            report(suite, file, test, start.elapsed(), result);
+        }
+        for name in &opt.suites {
+            let result = runner
+                .run_suite_each(file, name, |test, time, result| {
+                    report(suite, file, test, time, result)
+                })
+                .await;
+            // Only a missing suite fails the whole of it.
+            if result.is_err() {
+                report(
+                    suite,
+                    file,
+                    &format!("suite {}", name),
+                    Duration::ZERO,
+                    result,
+                );
+            }
+        }
+    }
+
+    let healed = runner.get_healed_targets().to_vec();
+    client
+        .quit()
+        .await
+        .map_err(|err| format!("failed to stop a webdriver session: {}", err))?;
+
+    Ok(healed)
+}
+
+/// Names a corrected copy of a file after its stem without a `.side` extension,
+/// so both `login.side` and `login.side.json` are corrected to `login.healed.side`.
+fn healed_path(path: &Path) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let stem = stem.strip_suffix(".side").unwrap_or(&stem);
+    path.with_file_name(format!("{}.healed.side", stem))
+}
+
+fn write_healed(path: &Path, mut file: File, healed: &[HealedTarget]) -> Result<(), String> {
+    println!("healed locators:");
+    for healed in healed {
+        println!("    {}", healed);
+    }
+
+    siderunner::healing::apply(&mut file, healed);
+
+    let path = healed_path(path);
+    let out = std::fs::File::create(&path)
+        .map_err(|err| format!("can't create a file {}: {}", path.display(), err))?;
+    siderunner::write(&file, out)
+        .map_err(|err| format!("can't write a file {}: {}", path.display(), err))?;
+    println!("corrected file: {}", path.display());
+
+    Ok(())
+}
+
+/// Runs a file and fills in its report, which keeps results of tests run before an error.
+async fn run_path(
+    opt: &Opt,
+    capabilities: &Json,
+    path: &Path,
+    suite: &mut TestSuite,
+) -> Result<(), String> {
+    let side = std::fs::File::open(path)
+        .map_err(|err| format!("can't open a file {}: {}", path.display(), err))?;
+    let original = parse_all(side).map_err(|errors| {
+        let errors = errors
+            .iter()
+            .map(|err| format!("\n    {}", chain(err)))
+            .collect::<String>();
+        format!("can't parse a file {}:{}", path.display(), errors)
+    })?;
+    // --base-url is only a setting of the run, so a corrected file keeps the original one.
+    let mut file = original.clone();
+    if let Some(url) = &opt.base_url {
+        file.url = url.clone();
+    }
+
+    suite.name = file.name.clone();
+    let healed = run_file(opt, capabilities, &file, suite).await?;
+    if !healed.is_empty() {
+        write_healed(path, original, &healed)?;
+    }
+
+    Ok(())
+}
+
+/// Runs files one by one and returns their reports and a number of files which failed to be run.
+///
+/// A file which can't be opened, parsed or run is reported as a failed test case
+/// so the rest of files are run anyway.
+async fn run(opt: &Opt) -> (Vec<TestSuite>, usize) {
+    let mut capabilities = DesiredCapabilities::new(opt.browser.clone());
+    if let Some(extra) = &opt.capabilities {
+        capabilities.update(extra.clone());
+    }
+    let capabilities = capabilities.get().clone();
+
+    let mut suites = Vec::new();
+    let mut errors = 0;
+    for path in &opt.files {
+        println!("running {}", path.display());
+        let mut suite = TestSuite::new(path.display().to_string());
+        if let Err(err) = run_path(opt, &capabilities, path, &mut suite).await {
+            eprintln!("error: {}", err);
+            suite.cases.push(TestCase {
+                name: path.display().to_string(),
+                time: Duration::ZERO,
+                failure: Some(Failure {
+                    test: None,
+                    index: 0,
+                    command: String::new(),
+                    target: String::new(),
+                    value: String::new(),
+                    kind: "Error".to_owned(),
+                    message: err,
+                }),
+            });
+            errors += 1;
+        }
+        suites.push(suite);
+    }
+
+    (suites, errors)
+}
+
+fn write_junit(path: &Path, suites: &[TestSuite]) -> Result<(), String> {
+    let out = std::fs::File::create(path)
+        .map_err(|err| format!("can't create a file {}: {}", path.display(), err))?;
+    siderunner::junit::write(suites, out)
+        .map_err(|err| format!("can't write a report {}: {}", path.display(), err))
+}
+
+#[tokio::main]
+async fn main() {
+    let opt = Opt::from_args();
+    let (suites, mut errors) = run(&opt).await;
+    if let Some(path) = &opt.junit {
+        if let Err(err) = write_junit(path, &suites) {
+            eprintln!("error: {}", err);
+            errors += 1;
+        }
+    }
+
+    let tests: usize = suites.iter().map(|suite| suite.cases.len()).sum();
+    let failed: usize = suites.iter().map(TestSuite::failures).sum();
+    println!("\nresult: {} passed; {} failed", tests - failed, failed);
+    if errors > 0 {
+        process::exit(2);
+    }
+    if failed > 0 {
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn healed_path_from_stem() {
+        for (path, healed) in [
+            ("tests/login.side", "tests/login.healed.side"),
+            ("tests/login.side.json", "tests/login.healed.side"),
+            ("tests/login.json", "tests/login.healed.side"),
+            ("login", "login.healed.side"),
+        ] {
+            assert_eq!(healed_path(Path::new(path)), Path::new(healed));
+        }
+    }
+}
@@ -0,0 +1,1432 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Recording of webdriver traffic and its replay.
+//!
+//! [`Recorder`] wraps any [`Webdriver`] and logs each call made to it or to its elements,
+//! with arguments and results, into a [`Cassette`].
+//! A cassette can be saved as JSON and later served back by [`Replayer`],
+//! so a run can be reproduced without a browser.
+//!
+//! Elements are referenced by numbers in the order they were found.
+//!
+//! ```no_run
+//! use siderunner::{
+//!     cassette::{Cassette, Recorder, Replayer},
+//!     webdriver::Webdriver,
+//!     File, Runner,
+//! };
+//!
+//! # async fn run<D: Webdriver>(driver: D, file: File) -> Result<(), Box<dyn std::error::Error>> {
+//! let recorder = Recorder::new(driver);
+//! let recording = recorder.recording();
+//! let result = Runner::with_backend(recorder).run(&file).await;
+//! recording.save("run.cassette.json")?;
+//!
+//! let replayer = Replayer::new(Cassette::load("run.cassette.json")?);
+//! Runner::with_backend(replayer).run(&file).await?;
+//! # result.map_err(Into::into)
+//! # }
+//! ```
+
+use crate::{
+    error::RunnerErrorKind,
+    webdriver::{Element, Locator, Webdriver},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value as Json};
+use std::{
+    collections::VecDeque,
+    fmt, fs, io,
+    path::Path,
+    sync::{Arc, Mutex, MutexGuard},
+    time::Duration,
+};
+
+/// A log of calls made to a webdriver.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Cassette {
+    pub interactions: Vec<Interaction>,
+}
+
+impl Cassette {
+    /// Reads a cassette from a JSON file.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = fs::File::open(path)?;
+        serde_json::from_reader(io::BufReader::new(file)).map_err(io::Error::from)
+    }
+
+    /// Writes a cassette to a JSON file.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let file = fs::File::create(path)?;
+        serde_json::to_writer_pretty(io::BufWriter::new(file), self).map_err(io::Error::from)
+    }
+}
+
+/// A single call with its result.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Interaction {
+    /// A name of a [`Webdriver`] or [`Element`] method.
+    pub method: String,
+    /// An element the method was called on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub element: Option<usize>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<Json>,
+    /// A returned value.
+    ///
+    /// Found elements are stored as their numbers.
+    pub result: Result<Json, RecordedError>,
+}
+
+impl fmt::Display for Interaction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_call(f, &self.method, self.element, &self.args)
+    }
+}
+
+fn fmt_call(
+    f: &mut fmt::Formatter<'_>,
+    method: &str,
+    element: Option<usize>,
+    args: &[Json],
+) -> fmt::Result {
+    if let Some(element) = element {
+        write!(f, "element {} ", element)?;
+    }
+
+    write!(f, "{}(", method)?;
+    for (i, arg) in args.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{}", arg)?;
+    }
+    write!(f, ")")
+}
+
+/// An error returned by a recorded call.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "message", rename_all = "snake_case")]
+pub enum RecordedError {
+    Webdriver(String),
+    Timeout(String),
+    ActionFailed(String),
+}
+
+impl From<&RunnerErrorKind> for RecordedError {
+    fn from(err: &RunnerErrorKind) -> Self {
+        match err {
+            RunnerErrorKind::WebdriverError(err) => Self::Webdriver(err.to_string()),
+            RunnerErrorKind::Timeout(desc) => Self::Timeout(desc.clone()),
+            RunnerErrorKind::ActionFailed(desc) => Self::ActionFailed(desc.clone()),
+            err => Self::Webdriver(format!("{:?}", err)),
+        }
+    }
+}
+
+impl From<RecordedError> for RunnerErrorKind {
+    fn from(err: RecordedError) -> Self {
+        match err {
+            RecordedError::Webdriver(desc) => ReplayError::Recorded(desc).into(),
+            RecordedError::Timeout(desc) => RunnerErrorKind::Timeout(desc),
+            RecordedError::ActionFailed(desc) => RunnerErrorKind::ActionFailed(desc),
+        }
+    }
+}
+
+/// An error returned by [`Replayer`].
+///
+/// It's wrapped into [`RunnerErrorKind::WebdriverError`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplayError {
+    /// A call differs from the one in a cassette.
+    Mismatch { expected: String, found: String },
+    /// A call was made after all interactions were served.
+    Exhausted(String),
+    /// A recorded result can't be converted to a type the call returns.
+    Malformed(String),
+    /// A webdriver error which was recorded.
+    Recorded(String),
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Mismatch { expected, found } => {
+                write!(f, "expected a call {} but got {}", expected, found)
+            }
+            Self::Exhausted(call) => write!(f, "no recorded interaction for a call {}", call),
+            Self::Malformed(call) => write!(f, "malformed recorded result of a call {}", call),
+            Self::Recorded(desc) => write!(f, "{}", desc),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+impl From<ReplayError> for RunnerErrorKind {
+    fn from(err: ReplayError) -> Self {
+        RunnerErrorKind::WebdriverError(Box::new(err))
+    }
+}
+
+/// A call which is being made.
+struct Request {
+    method: &'static str,
+    element: Option<usize>,
+    args: Vec<Json>,
+}
+
+impl Request {
+    fn new(method: &'static str, args: Vec<Json>) -> Self {
+        Self {
+            method,
+            element: None,
+            args,
+        }
+    }
+
+    fn on(element: usize, method: &'static str, args: Vec<Json>) -> Self {
+        Self {
+            method,
+            element: Some(element),
+            args,
+        }
+    }
+
+    fn into_interaction(self, result: Result<Json, RecordedError>) -> Interaction {
+        Interaction {
+            method: self.method.to_owned(),
+            element: self.element,
+            args: self.args,
+            result,
+        }
+    }
+
+    fn matches(&self, interaction: &Interaction) -> bool {
+        interaction.method == self.method
+            && interaction.element == self.element
+            && interaction.args == self.args
+    }
+}
+
+impl fmt::Display for Request {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_call(f, self.method, self.element, &self.args)
+    }
+}
+
+/// A value which can be stored in a cassette.
+trait Record: Sized {
+    fn to_json(&self) -> Json;
+    fn from_json(json: Json) -> Option<Self>;
+}
+
+impl Record for () {
+    fn to_json(&self) -> Json {
+        Json::Null
+    }
+
+    fn from_json(_: Json) -> Option<Self> {
+        Some(())
+    }
+}
+
+impl Record for Json {
+    fn to_json(&self) -> Json {
+        self.clone()
+    }
+
+    fn from_json(json: Json) -> Option<Self> {
+        Some(json)
+    }
+}
+
+impl Record for bool {
+    fn to_json(&self) -> Json {
+        Json::Bool(*self)
+    }
+
+    fn from_json(json: Json) -> Option<Self> {
+        json.as_bool()
+    }
+}
+
+impl Record for usize {
+    fn to_json(&self) -> Json {
+        json!(self)
+    }
+
+    fn from_json(json: Json) -> Option<Self> {
+        serde_json::from_value(json).ok()
+    }
+}
+
+/// Images are stored as arrays of bytes.
+impl Record for Vec<u8> {
+    fn to_json(&self) -> Json {
+        json!(self)
+    }
+
+    fn from_json(json: Json) -> Option<Self> {
+        serde_json::from_value(json).ok()
+    }
+}
+
+impl Record for Vec<usize> {
+    fn to_json(&self) -> Json {
+        json!(self)
+    }
+
+    fn from_json(json: Json) -> Option<Self> {
+        serde_json::from_value(json).ok()
+    }
+}
+
+impl Record for String {
+    fn to_json(&self) -> Json {
+        Json::String(self.clone())
+    }
+
+    fn from_json(json: Json) -> Option<Self> {
+        serde_json::from_value(json).ok()
+    }
+}
+
+impl Record for Option<String> {
+    fn to_json(&self) -> Json {
+        json!(self)
+    }
+
+    fn from_json(json: Json) -> Option<Self> {
+        serde_json::from_value(json).ok()
+    }
+}
+
+impl Record for Vec<String> {
+    fn to_json(&self) -> Json {
+        json!(self)
+    }
+
+    fn from_json(json: Json) -> Option<Self> {
+        serde_json::from_value(json).ok()
+    }
+}
+
+impl Record for url::Url {
+    fn to_json(&self) -> Json {
+        Json::String(self.to_string())
+    }
+
+    fn from_json(json: Json) -> Option<Self> {
+        json.as_str().and_then(|url| url::Url::parse(url).ok())
+    }
+}
+
+fn locator_arg(locator: &Locator) -> Json {
+    match locator {
+        Locator::Css(css) => json!({ "css": css }),
+        Locator::Id(id) => json!({ "id": id }),
+        Locator::Name(name) => json!({ "name": name }),
+        Locator::LinkText(text) => json!({ "linkText": text }),
+        Locator::PartialLinkText(text) => json!({ "partialLinkText": text }),
+        Locator::XPath(path) => json!({ "xpath": path }),
+    }
+}
+
+fn timeout_arg(timeout: Duration) -> Json {
+    json!(timeout.as_millis() as u64)
+}
+
+fn coord_arg((x, y): (i32, i32)) -> Json {
+    json!([x, y])
+}
+
+struct RecordingState {
+    cassette: Cassette,
+    elements: usize,
+}
+
+/// A handle to a cassette which is being recorded by [`Recorder`].
+#[derive(Clone)]
+pub struct Recording(Arc<Mutex<RecordingState>>);
+
+impl Recording {
+    /// Returns interactions recorded so far.
+    pub fn cassette(&self) -> Cassette {
+        self.state().cassette.clone()
+    }
+
+    /// Writes interactions recorded so far to a JSON file.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.state().cassette.save(path)
+    }
+
+    fn state(&self) -> MutexGuard<'_, RecordingState> {
+        self.0.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn push<T>(
+        &self,
+        request: Request,
+        result: &Result<T, RunnerErrorKind>,
+        to_json: impl FnOnce(&T) -> Json,
+    ) {
+        let result = match result {
+            Ok(value) => Ok(to_json(value)),
+            Err(err) => Err(RecordedError::from(err)),
+        };
+        self.state()
+            .cassette
+            .interactions
+            .push(request.into_interaction(result));
+    }
+
+    fn record<T: Record>(
+        &self,
+        request: Request,
+        result: Result<T, RunnerErrorKind>,
+    ) -> Result<T, RunnerErrorKind> {
+        self.push(request, &result, Record::to_json);
+        result
+    }
+
+    fn record_driver<D>(
+        &self,
+        request: Request,
+        result: Result<D, RunnerErrorKind>,
+    ) -> Result<Recorder<D>, RunnerErrorKind> {
+        self.push(request, &result, |_| Json::Null);
+        result.map(|driver| Recorder {
+            driver,
+            recording: self.clone(),
+        })
+    }
+
+    fn record_elements<D: Webdriver>(
+        &self,
+        request: Request,
+        result: Result<Vec<D::Element>, RunnerErrorKind>,
+    ) -> Result<Vec<RecordedElement<D>>, RunnerErrorKind> {
+        let result = result.map(|elements| {
+            let mut state = self.state();
+            elements
+                .into_iter()
+                .map(|element| {
+                    let id = state.elements;
+                    state.elements += 1;
+                    RecordedElement {
+                        element,
+                        id,
+                        recording: self.clone(),
+                    }
+                })
+                .collect::<Vec<_>>()
+        });
+        self.push(request, &result, |elements| {
+            elements.iter().map(|element| element.id).collect()
+        });
+        result
+    }
+
+    fn record_element<D: Webdriver>(
+        &self,
+        request: Request,
+        result: Result<D::Element, RunnerErrorKind>,
+    ) -> Result<RecordedElement<D>, RunnerErrorKind> {
+        let result = result.map(|element| {
+            let mut state = self.state();
+            let id = state.elements;
+            state.elements += 1;
+            RecordedElement {
+                element,
+                id,
+                recording: self.clone(),
+            }
+        });
+        self.push(request, &result, |element| json!(element.id));
+        result
+    }
+}
+
+/// Recorder is a [`Webdriver`] which logs all calls made to an underlying driver.
+pub struct Recorder<D> {
+    driver: D,
+    recording: Recording,
+}
+
+impl<D> Recorder<D> {
+    /// Wraps a driver, starting a new cassette.
+    pub fn new(driver: D) -> Self {
+        let state = RecordingState {
+            cassette: Cassette::default(),
+            elements: 0,
+        };
+
+        Self {
+            driver,
+            recording: Recording(Arc::new(Mutex::new(state))),
+        }
+    }
+
+    /// Returns a handle to the cassette.
+    ///
+    /// It stays valid after the recorder is moved into a [`Runner`].
+    ///
+    /// [`Runner`]: crate::Runner
+    pub fn recording(&self) -> Recording {
+        self.recording.clone()
+    }
+}
+
+/// An element produced by [`Recorder`].
+pub struct RecordedElement<D: Webdriver> {
+    element: D::Element,
+    id: usize,
+    recording: Recording,
+}
+
+#[async_trait::async_trait]
+impl<D: Webdriver> Webdriver for Recorder<D> {
+    type Element = RecordedElement<D>;
+
+    async fn goto(&mut self, url: &str) -> Result<(), RunnerErrorKind> {
+        let result = self.driver.goto(url).await;
+        self.recording
+            .record(Request::new("goto", vec![json!(url)]), result)
+    }
+
+    async fn find(&mut self, locator: Locator) -> Result<Self::Element, RunnerErrorKind> {
+        let request = Request::new("find", vec![locator_arg(&locator)]);
+        let result = self.driver.find(locator).await;
+        self.recording.record_element(request, result)
+    }
+
+    async fn find_all(&mut self, locator: Locator) -> Result<Vec<Self::Element>, RunnerErrorKind> {
+        let request = Request::new("find_all", vec![locator_arg(&locator)]);
+        let result = self.driver.find_all(locator).await;
+        self.recording.record_elements(request, result)
+    }
+
+    async fn current_url(&mut self) -> Result<url::Url, RunnerErrorKind> {
+        let result = self.driver.current_url().await;
+        self.recording
+            .record(Request::new("current_url", Vec::new()), result)
+    }
+
+    async fn wait_for_visible(
+        &mut self,
+        locator: Locator,
+        timeout: Duration,
+    ) -> Result<(), RunnerErrorKind> {
+        let request = Request::new(
+            "wait_for_visible",
+            vec![locator_arg(&locator), timeout_arg(timeout)],
+        );
+        let result = self.driver.wait_for_visible(locator, timeout).await;
+        self.recording.record(request, result)
+    }
+
+    async fn wait_for_not_visible(
+        &mut self,
+        locator: Locator,
+        timeout: Duration,
+    ) -> Result<(), RunnerErrorKind> {
+        let request = Request::new(
+            "wait_for_not_visible",
+            vec![locator_arg(&locator), timeout_arg(timeout)],
+        );
+        let result = self.driver.wait_for_not_visible(locator, timeout).await;
+        self.recording.record(request, result)
+    }
+
+    async fn wait_for_present(
+        &mut self,
+        locator: Locator,
+        timeout: Duration,
+    ) -> Result<(), RunnerErrorKind> {
+        let request = Request::new(
+            "wait_for_present",
+            vec![locator_arg(&locator), timeout_arg(timeout)],
+        );
+        let result = self.driver.wait_for_present(locator, timeout).await;
+        self.recording.record(request, result)
+    }
+
+    async fn wait_for_not_present(
+        &mut self,
+        locator: Locator,
+        timeout: Duration,
+    ) -> Result<(), RunnerErrorKind> {
+        let request = Request::new(
+            "wait_for_not_present",
+            vec![locator_arg(&locator), timeout_arg(timeout)],
+        );
+        let result = self.driver.wait_for_not_present(locator, timeout).await;
+        self.recording.record(request, result)
+    }
+
+    async fn wait_for_editable(
+        &mut self,
+        locator: Locator,
+        timeout: Duration,
+    ) -> Result<(), RunnerErrorKind> {
+        let request = Request::new(
+            "wait_for_editable",
+            vec![locator_arg(&locator), timeout_arg(timeout)],
+        );
+        let result = self.driver.wait_for_editable(locator, timeout).await;
+        self.recording.record(request, result)
+    }
+
+    async fn wait_for_not_editable(
+        &mut self,
+        locator: Locator,
+        timeout: Duration,
+    ) -> Result<(), RunnerErrorKind> {
+        let request = Request::new(
+            "wait_for_not_editable",
+            vec![locator_arg(&locator), timeout_arg(timeout)],
+        );
+        let result = self.driver.wait_for_not_editable(locator, timeout).await;
+        self.recording.record(request, result)
+    }
+
+    async fn set_window_size(&mut self, width: u32, height: u32) -> Result<(), RunnerErrorKind> {
+        let result = self.driver.set_window_size(width, height).await;
+        self.recording.record(
+            Request::new("set_window_size", vec![json!(width), json!(height)]),
+            result,
+        )
+    }
+
+    async fn execute(&mut self, script: &str, args: Vec<Json>) -> Result<Json, RunnerErrorKind> {
+        let request = Request::new("execute", vec![json!(script), json!(args)]);
+        let result = self.driver.execute(script, args).await;
+        self.recording.record(request, result)
+    }
+
+    async fn execute_async(
+        &mut self,
+        script: &str,
+        args: Vec<Json>,
+    ) -> Result<Json, RunnerErrorKind> {
+        let request = Request::new("execute_async", vec![json!(script), json!(args)]);
+        let result = self.driver.execute_async(script, args).await;
+        self.recording.record(request, result)
+    }
+
+    async fn close(&mut self) -> Result<(), RunnerErrorKind> {
+        let result = self.driver.close().await;
+        self.recording
+            .record(Request::new("close", Vec::new()), result)
+    }
+
+    async fn delete_cookies(&mut self) -> Result<(), RunnerErrorKind> {
+        let result = self.driver.delete_cookies().await;
+        self.recording
+            .record(Request::new("delete_cookies", Vec::new()), result)
+    }
+
+    async fn alert_text(&mut self) -> Result<String, RunnerErrorKind> {
+        let result = self.driver.alert_text().await;
+        self.recording
+            .record(Request::new("alert_text", Vec::new()), result)
+    }
+
+    async fn alert_accept(&mut self) -> Result<(), RunnerErrorKind> {
+        let result = self.driver.alert_accept().await;
+        self.recording
+            .record(Request::new("alert_accept", Vec::new()), result)
+    }
+
+    async fn alert_dissmis(&mut self) -> Result<(), RunnerErrorKind> {
+        let result = self.driver.alert_dissmis().await;
+        self.recording
+            .record(Request::new("alert_dissmis", Vec::new()), result)
+    }
+
+    async fn alert_send_text(&mut self, text: &str) -> Result<(), RunnerErrorKind> {
+        let result = self.driver.alert_send_text(text).await;
+        self.recording
+            .record(Request::new("alert_send_text", vec![json!(text)]), result)
+    }
+
+    async fn double_click(&mut self, locator: Locator) -> Result<(), RunnerErrorKind> {
+        let request = Request::new("double_click", vec![locator_arg(&locator)]);
+        let result = self.driver.double_click(locator).await;
+        self.recording.record(request, result)
+    }
+
+    async fn mouse_down(&mut self, locator: Locator) -> Result<(), RunnerErrorKind> {
+        let request = Request::new("mouse_down", vec![locator_arg(&locator)]);
+        let result = self.driver.mouse_down(locator).await;
+        self.recording.record(request, result)
+    }
+
+    async fn mouse_up(&mut self, locator: Locator) -> Result<(), RunnerErrorKind> {
+        let request = Request::new("mouse_up", vec![locator_arg(&locator)]);
+        let result = self.driver.mouse_up(locator).await;
+        self.recording.record(request, result)
+    }
+
+    async fn mouse_over(&mut self, locator: Locator) -> Result<(), RunnerErrorKind> {
+        let request = Request::new("mouse_over", vec![locator_arg(&locator)]);
+        let result = self.driver.mouse_over(locator).await;
+        self.recording.record(request, result)
+    }
+
+    async fn mouse_out(&mut self, locator: Locator) -> Result<(), RunnerErrorKind> {
+        let request = Request::new("mouse_out", vec![locator_arg(&locator)]);
+        let result = self.driver.mouse_out(locator).await;
+        self.recording.record(request, result)
+    }
+
+    async fn mouse_move_at(
+        &mut self,
+        locator: Locator,
+        coord: (i32, i32),
+    ) -> Result<(), RunnerErrorKind> {
+        let request = Request::new(
+            "mouse_move_at",
+            vec![locator_arg(&locator), coord_arg(coord)],
+        );
+        let result = self.driver.mouse_move_at(locator, coord).await;
+        self.recording.record(request, result)
+    }
+
+    async fn mouse_down_at(
+        &mut self,
+        locator: Locator,
+        coord: (i32, i32),
+    ) -> Result<(), RunnerErrorKind> {
+        let request = Request::new(
+            "mouse_down_at",
+            vec![locator_arg(&locator), coord_arg(coord)],
+        );
+        let result = self.driver.mouse_down_at(locator, coord).await;
+        self.recording.record(request, result)
+    }
+
+    async fn mouse_up_at(
+        &mut self,
+        locator: Locator,
+        coord: (i32, i32),
+    ) -> Result<(), RunnerErrorKind> {
+        let request = Request::new("mouse_up_at", vec![locator_arg(&locator), coord_arg(coord)]);
+        let result = self.driver.mouse_up_at(locator, coord).await;
+        self.recording.record(request, result)
+    }
+
+    async fn drag_and_drop(
+        &mut self,
+        source: Locator,
+        destination: Locator,
+    ) -> Result<(), RunnerErrorKind> {
+        let request = Request::new(
+            "drag_and_drop",
+            vec![locator_arg(&source), locator_arg(&destination)],
+        );
+        let result = self.driver.drag_and_drop(source, destination).await;
+        self.recording.record(request, result)
+    }
+
+    async fn title(&mut self) -> Result<String, RunnerErrorKind> {
+        let result = self.driver.title().await;
+        self.recording
+            .record(Request::new("title", Vec::new()), result)
+    }
+
+    async fn click_at(
+        &mut self,
+        locator: Locator,
+        coord: (i32, i32),
+    ) -> Result<(), RunnerErrorKind> {
+        let request = Request::new("click_at", vec![locator_arg(&locator), coord_arg(coord)]);
+        let result = self.driver.click_at(locator, coord).await;
+        self.recording.record(request, result)
+    }
+
+    async fn double_click_at(
+        &mut self,
+        locator: Locator,
+        coord: (i32, i32),
+    ) -> Result<(), RunnerErrorKind> {
+        let request = Request::new(
+            "double_click_at",
+            vec![locator_arg(&locator), coord_arg(coord)],
+        );
+        let result = self.driver.double_click_at(locator, coord).await;
+        self.recording.record(request, result)
+    }
+
+    async fn switch_to_default_content(&mut self) -> Result<(), RunnerErrorKind> {
+        let result = self.driver.switch_to_default_content().await;
+        self.recording.record(
+            Request::new("switch_to_default_content", Vec::new()),
+            result,
+        )
+    }
+
+    async fn switch_to_parent_frame(&mut self) -> Result<(), RunnerErrorKind> {
+        let result = self.driver.switch_to_parent_frame().await;
+        self.recording
+            .record(Request::new("switch_to_parent_frame", Vec::new()), result)
+    }
+
+    async fn switch_to_frame(&mut self, index: u16) -> Result<(), RunnerErrorKind> {
+        let result = self.driver.switch_to_frame(index).await;
+        self.recording
+            .record(Request::new("switch_to_frame", vec![json!(index)]), result)
+    }
+
+    async fn switch_to_frame_element(&mut self, locator: Locator) -> Result<(), RunnerErrorKind> {
+        let request = Request::new("switch_to_frame_element", vec![locator_arg(&locator)]);
+        let result = self.driver.switch_to_frame_element(locator).await;
+        self.recording.record(request, result)
+    }
+
+    async fn window_handles(&mut self) -> Result<Vec<String>, RunnerErrorKind> {
+        let result = self.driver.window_handles().await;
+        self.recording
+            .record(Request::new("window_handles", Vec::new()), result)
+    }
+
+    async fn current_window_handle(&mut self) -> Result<String, RunnerErrorKind> {
+        let result = self.driver.current_window_handle().await;
+        self.recording
+            .record(Request::new("current_window_handle", Vec::new()), result)
+    }
+
+    async fn switch_to_window(&mut self, handle: &str) -> Result<(), RunnerErrorKind> {
+        let result = self.driver.switch_to_window(handle).await;
+        self.recording.record(
+            Request::new("switch_to_window", vec![json!(handle)]),
+            result,
+        )
+    }
+
+    async fn screenshot(&mut self) -> Result<Vec<u8>, RunnerErrorKind> {
+        let result = self.driver.screenshot().await;
+        self.recording
+            .record(Request::new("screenshot", Vec::new()), result)
+    }
+
+    async fn page_source(&mut self) -> Result<String, RunnerErrorKind> {
+        let result = self.driver.page_source().await;
+        self.recording
+            .record(Request::new("page_source", Vec::new()), result)
+    }
+}
+
+#[async_trait::async_trait]
+impl<D: Webdriver> Element for RecordedElement<D> {
+    type Driver = Recorder<D>;
+
+    async fn attr(&mut self, attribute: &str) -> Result<Option<String>, RunnerErrorKind> {
+        let result = self.element.attr(attribute).await;
+        self.recording
+            .record(Request::on(self.id, "attr", vec![json!(attribute)]), result)
+    }
+
+    async fn prop(&mut self, prop: &str) -> Result<Option<String>, RunnerErrorKind> {
+        let result = self.element.prop(prop).await;
+        self.recording
+            .record(Request::on(self.id, "prop", vec![json!(prop)]), result)
+    }
+
+    async fn text(&mut self) -> Result<String, RunnerErrorKind> {
+        let result = self.element.text().await;
+        self.recording
+            .record(Request::on(self.id, "text", Vec::new()), result)
+    }
+
+    async fn html(&mut self, inner: bool) -> Result<String, RunnerErrorKind> {
+        let result = self.element.html(inner).await;
+        self.recording
+            .record(Request::on(self.id, "html", vec![json!(inner)]), result)
+    }
+
+    async fn find(&mut self, search: Locator) -> Result<Self, RunnerErrorKind> {
+        let request = Request::on(self.id, "find", vec![locator_arg(&search)]);
+        let result = self.element.find(search).await;
+        self.recording.record_element(request, result)
+    }
+
+    async fn click(mut self) -> Result<Self::Driver, RunnerErrorKind> {
+        let request = Request::on(self.id, "click", Vec::new());
+        let result = self.element.click().await;
+        self.recording.record_driver(request, result)
+    }
+
+    async fn select_by_index(mut self, index: usize) -> Result<Self::Driver, RunnerErrorKind> {
+        let request = Request::on(self.id, "select_by_index", vec![json!(index)]);
+        let result = self.element.select_by_index(index).await;
+        self.recording.record_driver(request, result)
+    }
+
+    async fn select_by_value(mut self, value: &str) -> Result<Self::Driver, RunnerErrorKind> {
+        let request = Request::on(self.id, "select_by_value", vec![json!(value)]);
+        let result = self.element.select_by_value(value).await;
+        self.recording.record_driver(request, result)
+    }
+
+    async fn select_by_label(mut self, value: &str) -> Result<Self::Driver, RunnerErrorKind> {
+        let request = Request::on(self.id, "select_by_label", vec![json!(value)]);
+        let result = self.element.select_by_label(value).await;
+        self.recording.record_driver(request, result)
+    }
+
+    async fn send_keys(mut self, value: &str) -> Result<(), RunnerErrorKind> {
+        let result = self.element.send_keys(value).await;
+        self.recording.record(
+            Request::on(self.id, "send_keys", vec![json!(value)]),
+            result,
+        )
+    }
+
+    async fn submit(mut self) -> Result<(), RunnerErrorKind> {
+        let result = self.element.submit().await;
+        self.recording
+            .record(Request::on(self.id, "submit", Vec::new()), result)
+    }
+
+    async fn is_selected(&mut self) -> Result<bool, RunnerErrorKind> {
+        let result = self.element.is_selected().await;
+        self.recording
+            .record(Request::on(self.id, "is_selected", Vec::new()), result)
+    }
+
+    async fn is_present(&mut self) -> Result<bool, RunnerErrorKind> {
+        let result = self.element.is_present().await;
+        self.recording
+            .record(Request::on(self.id, "is_present", Vec::new()), result)
+    }
+
+    async fn is_enabled(&mut self) -> Result<bool, RunnerErrorKind> {
+        let result = self.element.is_enabled().await;
+        self.recording
+            .record(Request::on(self.id, "is_enabled", Vec::new()), result)
+    }
+}
+
+/// Replayer is a [`Webdriver`] which serves interactions of a [`Cassette`] back.
+///
+/// Calls must be made in the same order and with the same arguments as they were recorded,
+/// otherwise [`ReplayError::Mismatch`] is returned.
+///
+/// Clones share the same cassette.
+#[derive(Clone)]
+pub struct Replayer(Arc<Mutex<VecDeque<Interaction>>>);
+
+impl Replayer {
+    /// Creates a replayer which serves interactions from the start of a cassette.
+    pub fn new(cassette: Cassette) -> Self {
+        Self(Arc::new(Mutex::new(cassette.interactions.into())))
+    }
+
+    /// Returns a number of interactions which weren't served yet.
+    pub fn remaining(&self) -> usize {
+        self.interactions().len()
+    }
+
+    fn interactions(&self) -> MutexGuard<'_, VecDeque<Interaction>> {
+        self.0.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn next(&self, request: &Request) -> Result<Json, RunnerErrorKind> {
+        let found = request.to_string();
+        let interaction = self
+            .interactions()
+            .pop_front()
+            .ok_or_else(|| ReplayError::Exhausted(found.clone()))?;
+        if !request.matches(&interaction) {
+            return Err(ReplayError::Mismatch {
+                expected: interaction.to_string(),
+                found,
+            }
+            .into());
+        }
+
+        interaction.result.map_err(Into::into)
+    }
+
+    fn replay<T: Record>(&self, request: Request) -> Result<T, RunnerErrorKind> {
+        let json = self.next(&request)?;
+        T::from_json(json).ok_or_else(|| ReplayError::Malformed(request.to_string()).into())
+    }
+
+    fn replay_element(&self, request: Request) -> Result<ReplayedElement, RunnerErrorKind> {
+        let id = self.replay::<usize>(request)?;
+        Ok(ReplayedElement {
+            id,
+            replayer: self.clone(),
+        })
+    }
+
+    fn replay_elements(&self, request: Request) -> Result<Vec<ReplayedElement>, RunnerErrorKind> {
+        let ids = self.replay::<Vec<usize>>(request)?;
+        let elements = ids
+            .into_iter()
+            .map(|id| ReplayedElement {
+                id,
+                replayer: self.clone(),
+            })
+            .collect();
+        Ok(elements)
+    }
+}
+
+/// An element produced by [`Replayer`].
+pub struct ReplayedElement {
+    id: usize,
+    replayer: Replayer,
+}
+
+#[async_trait::async_trait]
+impl Webdriver for Replayer {
+    type Element = ReplayedElement;
+
+    async fn goto(&mut self, url: &str) -> Result<(), RunnerErrorKind> {
+        self.replay(Request::new("goto", vec![json!(url)]))
+    }
+
+    async fn find(&mut self, locator: Locator) -> Result<Self::Element, RunnerErrorKind> {
+        self.replay_element(Request::new("find", vec![locator_arg(&locator)]))
+    }
+
+    async fn find_all(&mut self, locator: Locator) -> Result<Vec<Self::Element>, RunnerErrorKind> {
+        self.replay_elements(Request::new("find_all", vec![locator_arg(&locator)]))
+    }
+
+    async fn current_url(&mut self) -> Result<url::Url, RunnerErrorKind> {
+        self.replay(Request::new("current_url", Vec::new()))
+    }
+
+    async fn wait_for_visible(
+        &mut self,
+        locator: Locator,
+        timeout: Duration,
+    ) -> Result<(), RunnerErrorKind> {
+        self.replay(Request::new(
+            "wait_for_visible",
+            vec![locator_arg(&locator), timeout_arg(timeout)],
+        ))
+    }
+
+    async fn wait_for_not_visible(
+        &mut self,
+        locator: Locator,
+        timeout: Duration,
+    ) -> Result<(), RunnerErrorKind> {
+        self.replay(Request::new(
+            "wait_for_not_visible",
+            vec![locator_arg(&locator), timeout_arg(timeout)],
+        ))
+    }
+
+    async fn wait_for_present(
+        &mut self,
+        locator: Locator,
+        timeout: Duration,
+    ) -> Result<(), RunnerErrorKind> {
+        self.replay(Request::new(
+            "wait_for_present",
+            vec![locator_arg(&locator), timeout_arg(timeout)],
+        ))
+    }
+
+    async fn wait_for_not_present(
+        &mut self,
+        locator: Locator,
+        timeout: Duration,
+    ) -> Result<(), RunnerErrorKind> {
+        self.replay(Request::new(
+            "wait_for_not_present",
+            vec![locator_arg(&locator), timeout_arg(timeout)],
+        ))
+    }
+
+    async fn wait_for_editable(
+        &mut self,
+        locator: Locator,
+        timeout: Duration,
+    ) -> Result<(), RunnerErrorKind> {
+        self.replay(Request::new(
+            "wait_for_editable",
+            vec![locator_arg(&locator), timeout_arg(timeout)],
+        ))
+    }
+
+    async fn wait_for_not_editable(
+        &mut self,
+        locator: Locator,
+        timeout: Duration,
+    ) -> Result<(), RunnerErrorKind> {
+        self.replay(Request::new(
+            "wait_for_not_editable",
+            vec![locator_arg(&locator), timeout_arg(timeout)],
+        ))
+    }
+
+    async fn set_window_size(&mut self, width: u32, height: u32) -> Result<(), RunnerErrorKind> {
+        self.replay(Request::new(
+            "set_window_size",
+            vec![json!(width), json!(height)],
+        ))
+    }
+
+    async fn execute(&mut self, script: &str, args: Vec<Json>) -> Result<Json, RunnerErrorKind> {
+        self.replay(Request::new("execute", vec![json!(script), json!(args)]))
+    }
+
+    async fn execute_async(
+        &mut self,
+        script: &str,
+        args: Vec<Json>,
+    ) -> Result<Json, RunnerErrorKind> {
+        self.replay(Request::new(
+            "execute_async",
+            vec![json!(script), json!(args)],
+        ))
+    }
+
+    async fn close(&mut self) -> Result<(), RunnerErrorKind> {
+        self.replay(Request::new("close", Vec::new()))
+    }
+
+    async fn delete_cookies(&mut self) -> Result<(), RunnerErrorKind> {
+        self.replay(Request::new("delete_cookies", Vec::new()))
+    }
+
+    async fn alert_text(&mut self) -> Result<String, RunnerErrorKind> {
+        self.replay(Request::new("alert_text", Vec::new()))
+    }
+
+    async fn alert_accept(&mut self) -> Result<(), RunnerErrorKind> {
+        self.replay(Request::new("alert_accept", Vec::new()))
+    }
+
+    async fn alert_dissmis(&mut self) -> Result<(), RunnerErrorKind> {
+        self.replay(Request::new("alert_dissmis", Vec::new()))
+    }
+
+    async fn alert_send_text(&mut self, text: &str) -> Result<(), RunnerErrorKind> {
+        self.replay(Request::new("alert_send_text", vec![json!(text)]))
+    }
+
+    async fn double_click(&mut self, locator: Locator) -> Result<(), RunnerErrorKind> {
+        self.replay(Request::new("double_click", vec![locator_arg(&locator)]))
+    }
+
+    async fn mouse_down(&mut self, locator: Locator) -> Result<(), RunnerErrorKind> {
+        self.replay(Request::new("mouse_down", vec![locator_arg(&locator)]))
+    }
+
+    async fn mouse_up(&mut self, locator: Locator) -> Result<(), RunnerErrorKind> {
+        self.replay(Request::new("mouse_up", vec![locator_arg(&locator)]))
+    }
+
+    async fn mouse_over(&mut self, locator: Locator) -> Result<(), RunnerErrorKind> {
+        self.replay(Request::new("mouse_over", vec![locator_arg(&locator)]))
+    }
+
+    async fn mouse_out(&mut self, locator: Locator) -> Result<(), RunnerErrorKind> {
+        self.replay(Request::new("mouse_out", vec![locator_arg(&locator)]))
+    }
+
+    async fn mouse_move_at(
+        &mut self,
+        locator: Locator,
+        coord: (i32, i32),
+    ) -> Result<(), RunnerErrorKind> {
+        self.replay(Request::new(
+            "mouse_move_at",
+            vec![locator_arg(&locator), coord_arg(coord)],
+        ))
+    }
+
+    async fn mouse_down_at(
+        &mut self,
+        locator: Locator,
+        coord: (i32, i32),
+    ) -> Result<(), RunnerErrorKind> {
+        self.replay(Request::new(
+            "mouse_down_at",
+            vec![locator_arg(&locator), coord_arg(coord)],
+        ))
+    }
+
+    async fn mouse_up_at(
+        &mut self,
+        locator: Locator,
+        coord: (i32, i32),
+    ) -> Result<(), RunnerErrorKind> {
+        self.replay(Request::new(
+            "mouse_up_at",
+            vec![locator_arg(&locator), coord_arg(coord)],
+        ))
+    }
+
+    async fn drag_and_drop(
+        &mut self,
+        source: Locator,
+        destination: Locator,
+    ) -> Result<(), RunnerErrorKind> {
+        self.replay(Request::new(
+            "drag_and_drop",
+            vec![locator_arg(&source), locator_arg(&destination)],
+        ))
+    }
+
+    async fn title(&mut self) -> Result<String, RunnerErrorKind> {
+        self.replay(Request::new("title", Vec::new()))
+    }
+
+    async fn click_at(
+        &mut self,
+        locator: Locator,
+        coord: (i32, i32),
+    ) -> Result<(), RunnerErrorKind> {
+        self.replay(Request::new(
+            "click_at",
+            vec![locator_arg(&locator), coord_arg(coord)],
+        ))
+    }
+
+    async fn double_click_at(
+        &mut self,
+        locator: Locator,
+        coord: (i32, i32),
+    ) -> Result<(), RunnerErrorKind> {
+        self.replay(Request::new(
+            "double_click_at",
+            vec![locator_arg(&locator), coord_arg(coord)],
+        ))
+    }
+
+    async fn switch_to_default_content(&mut self) -> Result<(), RunnerErrorKind> {
+        self.replay(Request::new("switch_to_default_content", Vec::new()))
+    }
+
+    async fn switch_to_parent_frame(&mut self) -> Result<(), RunnerErrorKind> {
+        self.replay(Request::new("switch_to_parent_frame", Vec::new()))
+    }
+
+    async fn switch_to_frame(&mut self, index: u16) -> Result<(), RunnerErrorKind> {
+        self.replay(Request::new("switch_to_frame", vec![json!(index)]))
+    }
+
+    async fn switch_to_frame_element(&mut self, locator: Locator) -> Result<(), RunnerErrorKind> {
+        self.replay(Request::new(
+            "switch_to_frame_element",
+            vec![locator_arg(&locator)],
+        ))
+    }
+
+    async fn window_handles(&mut self) -> Result<Vec<String>, RunnerErrorKind> {
+        self.replay(Request::new("window_handles", Vec::new()))
+    }
+
+    async fn current_window_handle(&mut self) -> Result<String, RunnerErrorKind> {
+        self.replay(Request::new("current_window_handle", Vec::new()))
+    }
+
+    async fn switch_to_window(&mut self, handle: &str) -> Result<(), RunnerErrorKind> {
+        self.replay(Request::new("switch_to_window", vec![json!(handle)]))
+    }
+
+    async fn screenshot(&mut self) -> Result<Vec<u8>, RunnerErrorKind> {
+        self.replay(Request::new("screenshot", Vec::new()))
+    }
+
+    async fn page_source(&mut self) -> Result<String, RunnerErrorKind> {
+        self.replay(Request::new("page_source", Vec::new()))
+    }
+}
+
+#[async_trait::async_trait]
+impl Element for ReplayedElement {
+    type Driver = Replayer;
+
+    async fn attr(&mut self, attribute: &str) -> Result<Option<String>, RunnerErrorKind> {
+        self.replayer
+            .replay(Request::on(self.id, "attr", vec![json!(attribute)]))
+    }
+
+    async fn prop(&mut self, prop: &str) -> Result<Option<String>, RunnerErrorKind> {
+        self.replayer
+            .replay(Request::on(self.id, "prop", vec![json!(prop)]))
+    }
+
+    async fn text(&mut self) -> Result<String, RunnerErrorKind> {
+        self.replayer
+            .replay(Request::on(self.id, "text", Vec::new()))
+    }
+
+    async fn html(&mut self, inner: bool) -> Result<String, RunnerErrorKind> {
+        self.replayer
+            .replay(Request::on(self.id, "html", vec![json!(inner)]))
+    }
+
+    async fn find(&mut self, search: Locator) -> Result<Self, RunnerErrorKind> {
+        self.replayer
+            .replay_element(Request::on(self.id, "find", vec![locator_arg(&search)]))
+    }
+
+    async fn click(mut self) -> Result<Self::Driver, RunnerErrorKind> {
+        self.replayer
+            .replay::<()>(Request::on(self.id, "click", Vec::new()))?;
+        Ok(self.replayer)
+    }
+
+    async fn select_by_index(mut self, index: usize) -> Result<Self::Driver, RunnerErrorKind> {
+        self.replayer
+            .replay::<()>(Request::on(self.id, "select_by_index", vec![json!(index)]))?;
+        Ok(self.replayer)
+    }
+
+    async fn select_by_value(mut self, value: &str) -> Result<Self::Driver, RunnerErrorKind> {
+        self.replayer
+            .replay::<()>(Request::on(self.id, "select_by_value", vec![json!(value)]))?;
+        Ok(self.replayer)
+    }
+
+    async fn select_by_label(mut self, value: &str) -> Result<Self::Driver, RunnerErrorKind> {
+        self.replayer
+            .replay::<()>(Request::on(self.id, "select_by_label", vec![json!(value)]))?;
+        Ok(self.replayer)
+    }
+
+    async fn send_keys(mut self, value: &str) -> Result<(), RunnerErrorKind> {
+        self.replayer
+            .replay(Request::on(self.id, "send_keys", vec![json!(value)]))
+    }
+
+    async fn submit(mut self) -> Result<(), RunnerErrorKind> {
+        self.replayer
+            .replay(Request::on(self.id, "submit", Vec::new()))
+    }
+
+    async fn is_selected(&mut self) -> Result<bool, RunnerErrorKind> {
+        self.replayer
+            .replay(Request::on(self.id, "is_selected", Vec::new()))
+    }
+
+    async fn is_present(&mut self) -> Result<bool, RunnerErrorKind> {
+        self.replayer
+            .replay(Request::on(self.id, "is_present", Vec::new()))
+    }
+
+    async fn is_enabled(&mut self) -> Result<bool, RunnerErrorKind> {
+        self.replayer
+            .replay(Request::on(self.id, "is_enabled", Vec::new()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        testing::{tests::side_file, MockDriver, MockElement},
+        Runner,
+    };
+
+    fn driver() -> MockDriver {
+        let driver = MockDriver::new();
+        driver.add_page("http://localhost/index.html", "Index");
+        driver.add_element(
+            Locator::Css("h1".to_owned()),
+            MockElement::new().text("Hello"),
+        );
+        driver.add_element(Locator::Id("name".to_owned()), MockElement::new());
+        driver.add_script_result("window.answer", json!(42));
+        driver
+    }
+
+    #[tokio::test]
+    async fn record_and_replay() {
+        let file = side_file(&[
+            ("open", "/index.html", ""),
+            ("assertTitle", "Index", ""),
+            ("storeText", "css=h1", "header"),
+            ("type", "id=name", "siderunner"),
+            ("executeScript", "return window.answer", "answer"),
+        ]);
+
+        let recorder = Recorder::new(driver());
+        let recording = recorder.recording();
+        Runner::with_backend(recorder).run(&file).await.unwrap();
+
+        let cassette = recording.cassette();
+        assert!(cassette.interactions.contains(&Interaction {
+            method: "goto".to_owned(),
+            element: None,
+            args: vec![json!("http://localhost/index.html")],
+            result: Ok(Json::Null),
+        }));
+
+        let json = serde_json::to_string(&cassette).unwrap();
+        let cassette: Cassette = serde_json::from_str(&json).unwrap();
+
+        let replayer = Replayer::new(cassette);
+        let mut runner = Runner::with_backend(replayer.clone());
+        runner.run(&file).await.unwrap();
+
+        assert_eq!(replayer.remaining(), 0);
+        assert_eq!(runner.get_value("header"), Some(&json!("Hello")));
+        assert_eq!(runner.get_value("answer"), Some(&json!(42)));
+    }
+
+    #[tokio::test]
+    async fn replay_recorded_error() {
+        let file = side_file(&[("click", "id=missing", "")]);
+
+        let recorder = Recorder::new(driver());
+        let recording = recorder.recording();
+        let recorded = Runner::with_backend(recorder).run(&file).await.unwrap_err();
+
+        let mut runner = Runner::with_backend(Replayer::new(recording.cassette()));
+        let replayed = runner.run(&file).await.unwrap_err();
+
+        assert_eq!(replayed.index, recorded.index);
+        match replayed.kind {
+            RunnerErrorKind::WebdriverError(err) => assert_eq!(
+                err.downcast_ref::<ReplayError>(),
+                Some(&ReplayError::Recorded(
+                    "no such element Id(\"missing\")".to_owned()
+                ))
+            ),
+            kind => panic!("unexpected error {:?}", kind),
+        }
+    }
+
+    #[tokio::test]
+    async fn replay_mismatch() {
+        let recorder = Recorder::new(driver());
+        let recording = recorder.recording();
+        Runner::with_backend(recorder)
+            .run(&side_file(&[("open", "/index.html", "")]))
+            .await
+            .unwrap();
+
+        let mut runner = Runner::with_backend(Replayer::new(recording.cassette()));
+        let err = runner
+            .run(&side_file(&[("open", "/about.html", "")]))
+            .await
+            .unwrap_err();
+
+        match err.kind {
+            RunnerErrorKind::WebdriverError(err) => assert_eq!(
+                err.downcast_ref::<ReplayError>(),
+                Some(&ReplayError::Mismatch {
+                    expected: "goto(\"http://localhost/index.html\")".to_owned(),
+                    found: "goto(\"http://localhost/about.html\")".to_owned(),
+                })
+            ),
+            kind => panic!("unexpected error {:?}", kind),
+        }
+    }
+}
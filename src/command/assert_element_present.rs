@@ -58,14 +58,9 @@ impl Command for AssertElementNotPresent {
     where
         D: Webdriver,
     {
-        let is_present = runner
-            .get_webdriver()
-            .find(self.target.clone())
-            .await?
-            .is_present()
-            .await?;
+        let elements = runner.get_webdriver().find_all(self.target.clone()).await?;
 
-        if is_present {
+        if !elements.is_empty() {
             return Err(RunnerErrorKind::AssertFailed {
                 lhs: "true".to_string(),
                 rhs: "false".to_string(),
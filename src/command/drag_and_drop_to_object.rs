@@ -0,0 +1,37 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use super::Command;
+use crate::{
+    error::RunnerErrorKind,
+    webdriver::{Locator, Webdriver},
+};
+
+pub struct DragAndDropToObject {
+    source: Locator,
+    destination: Locator,
+}
+
+impl DragAndDropToObject {
+    pub fn new(source: Locator, destination: Locator) -> Self {
+        Self {
+            source,
+            destination,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Command for DragAndDropToObject {
+    async fn run<D>(&self, runner: &mut crate::runner::Runner<D>) -> Result<(), RunnerErrorKind>
+    where
+        D: Webdriver,
+    {
+        runner
+            .get_webdriver()
+            .drag_and_drop(self.source.clone(), self.destination.clone())
+            .await?;
+        Ok(())
+    }
+}
@@ -21,8 +21,7 @@ impl Command for Echo {
     where
         D: Webdriver,
     {
-        let message = runner.emit(&self.message);
-        runner.echo(&message);
+        runner.echo(&self.message);
 
         Ok(())
     }
@@ -22,6 +22,7 @@ mod choose_on_next_;
 mod click;
 mod close;
 mod double_click;
+mod drag_and_drop_to_object;
 mod echo;
 mod edit_content;
 mod execute;
@@ -32,6 +33,8 @@ mod pause;
 mod remove_selection;
 mod run_script;
 mod select;
+mod select_frame;
+mod select_window;
 mod send_keys;
 mod set_window_size;
 mod store;
@@ -40,22 +43,26 @@ mod store_json;
 mod store_text;
 mod store_title;
 mod store_value;
+mod store_window_handle;
 mod store_xpath_count;
+mod submit;
 mod type_;
 mod wait_for_element_editable;
 mod wait_for_element_present;
 mod wait_for_element_visible;
+mod webdriver_on_visible_;
 
 pub use {
     answer_on_next_prompt::*, assert::*, assert_alert::*, assert_checked::*,
     assert_confirmation::*, assert_editable::*, assert_element_present::*, assert_prompt::*,
     assert_selected_label::*, assert_selected_value::*, assert_text::*, assert_title::*,
-    assert_value::*, check::*, choose_on_next_::*, click::*, close::*, double_click::*, echo::*,
-    edit_content::*, execute::*, execute_async::*, mouse::*, open::*, pause::*,
-    remove_selection::*, run_script::*, select::*, send_keys::*, set_window_size::*, store::*,
-    store_attribute::*, store_json::*, store_text::*, store_title::*, store_value::*,
-    store_xpath_count::*, type_::*, wait_for_element_editable::*, wait_for_element_present::*,
-    wait_for_element_visible::*,
+    assert_value::*, check::*, choose_on_next_::*, click::*, close::*, double_click::*,
+    drag_and_drop_to_object::*, echo::*, edit_content::*, execute::*, execute_async::*, mouse::*,
+    open::*, pause::*, remove_selection::*, run_script::*, select::*, select_frame::*,
+    select_window::*, send_keys::*, set_window_size::*, store::*, store_attribute::*,
+    store_json::*, store_text::*, store_title::*, store_value::*, store_window_handle::*,
+    store_xpath_count::*, submit::*, type_::*, wait_for_element_editable::*,
+    wait_for_element_present::*, wait_for_element_visible::*, webdriver_on_visible_::*,
 };
 
 #[async_trait::async_trait]
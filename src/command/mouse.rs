@@ -52,3 +52,126 @@ impl Command for MouseDown {
         Ok(())
     }
 }
+
+pub struct MouseOver {
+    target: Locator,
+}
+
+impl MouseOver {
+    pub fn new(target: Locator) -> Self {
+        Self { target }
+    }
+}
+
+#[async_trait::async_trait]
+impl Command for MouseOver {
+    async fn run<D>(&self, runner: &mut crate::runner::Runner<D>) -> Result<(), RunnerErrorKind>
+    where
+        D: Webdriver,
+    {
+        runner
+            .get_webdriver()
+            .mouse_over(self.target.clone())
+            .await?;
+        Ok(())
+    }
+}
+
+pub struct MouseOut {
+    target: Locator,
+}
+
+impl MouseOut {
+    pub fn new(target: Locator) -> Self {
+        Self { target }
+    }
+}
+
+#[async_trait::async_trait]
+impl Command for MouseOut {
+    async fn run<D>(&self, runner: &mut crate::runner::Runner<D>) -> Result<(), RunnerErrorKind>
+    where
+        D: Webdriver,
+    {
+        runner
+            .get_webdriver()
+            .mouse_out(self.target.clone())
+            .await?;
+        Ok(())
+    }
+}
+
+pub struct MouseMoveAt {
+    target: Locator,
+    coord: (i32, i32),
+}
+
+impl MouseMoveAt {
+    pub fn new(target: Locator, coord: (i32, i32)) -> Self {
+        Self { target, coord }
+    }
+}
+
+#[async_trait::async_trait]
+impl Command for MouseMoveAt {
+    async fn run<D>(&self, runner: &mut crate::runner::Runner<D>) -> Result<(), RunnerErrorKind>
+    where
+        D: Webdriver,
+    {
+        runner
+            .get_webdriver()
+            .mouse_move_at(self.target.clone(), self.coord)
+            .await?;
+        Ok(())
+    }
+}
+
+pub struct MouseDownAt {
+    target: Locator,
+    coord: (i32, i32),
+}
+
+impl MouseDownAt {
+    pub fn new(target: Locator, coord: (i32, i32)) -> Self {
+        Self { target, coord }
+    }
+}
+
+#[async_trait::async_trait]
+impl Command for MouseDownAt {
+    async fn run<D>(&self, runner: &mut crate::runner::Runner<D>) -> Result<(), RunnerErrorKind>
+    where
+        D: Webdriver,
+    {
+        runner
+            .get_webdriver()
+            .mouse_down_at(self.target.clone(), self.coord)
+            .await?;
+        Ok(())
+    }
+}
+
+pub struct MouseUpAt {
+    target: Locator,
+    coord: (i32, i32),
+}
+
+impl MouseUpAt {
+    pub fn new(target: Locator, coord: (i32, i32)) -> Self {
+        Self { target, coord }
+    }
+}
+
+#[async_trait::async_trait]
+impl Command for MouseUpAt {
+    async fn run<D>(&self, runner: &mut crate::runner::Runner<D>) -> Result<(), RunnerErrorKind>
+    where
+        D: Webdriver,
+    {
+        runner
+            .get_webdriver()
+            .mouse_up_at(self.target.clone(), self.coord)
+            .await?;
+        Ok(())
+    }
+}
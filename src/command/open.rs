@@ -24,8 +24,7 @@ impl Command for Open {
     where
         D: Webdriver,
     {
-        let url = runner.emit(&self.url);
-        let url = build_url(&self.file_url, &url)?;
+        let url = build_url(&self.file_url, &self.url)?;
         let url = url.as_str();
 
         runner.get_webdriver().goto(url).await?;
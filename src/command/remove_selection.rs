@@ -27,9 +27,8 @@ impl Command for RemoveSelection {
     {
         let mut select = runner.get_webdriver().find(self.target.clone()).await?;
 
-        let label = runner.emit(&self.label);
         // somehow .//option[normalize-space(.)='{}'] doesn work...
-        let locator = format!(".//*[normalize-space(.)='{}']", label);
+        let locator = format!(".//*[normalize-space(.)='{}']", self.label);
 
         let mut opt = select.find(Locator::XPath(locator)).await?;
         if opt.is_selected().await? {
@@ -32,8 +32,6 @@ impl Command for Select {
         let mut select = runner.get_webdriver().find(self.target.clone()).await?;
         match &self.select_target {
             SelectLocator::Index(index) => {
-                // todo: DO emit of locators before calling Command
-                let index = runner.emit(index);
                 match index.parse() {
                     Ok(index) => {
                         select.select_by_index(index).await?;
@@ -48,11 +46,9 @@ impl Command for Select {
                 }
             }
             SelectLocator::Value(value) => {
-                let value = runner.emit(value);
-                select.select_by_value(&value).await?;
+                select.select_by_value(value).await?;
             }
             SelectLocator::Id(id) => {
-                let id = runner.emit(id);
                 let locator = format!(r#"option[id='{}']"#, id);
                 let mut opt = select.find(Locator::Css(locator)).await?;
                 let selected = opt.prop("selected").await?;
@@ -61,7 +57,6 @@ impl Command for Select {
                 }
             }
             SelectLocator::Label(label) => {
-                let label = runner.emit(label);
                 // somehow .//option[normalize-space(.)='{}'] doesn work...
                 let locator = format!(".//*[normalize-space(.)='{}']", label);
 
@@ -0,0 +1,37 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use super::Command;
+use crate::{error::RunnerErrorKind, parser::FrameLocator, webdriver::Webdriver};
+
+pub struct SelectFrame {
+    locator: FrameLocator,
+}
+
+impl SelectFrame {
+    pub fn new(locator: FrameLocator) -> Self {
+        Self { locator }
+    }
+}
+
+#[async_trait::async_trait]
+impl Command for SelectFrame {
+    async fn run<D>(&self, runner: &mut crate::runner::Runner<D>) -> Result<(), RunnerErrorKind>
+    where
+        D: Webdriver,
+    {
+        match &self.locator {
+            FrameLocator::Top => runner.get_webdriver().switch_to_default_content().await,
+            FrameLocator::Parent => runner.get_webdriver().switch_to_parent_frame().await,
+            FrameLocator::Index(index) => runner.get_webdriver().switch_to_frame(*index).await,
+            FrameLocator::Element(target) => {
+                let locator = runner.locator(target)?;
+                runner
+                    .get_webdriver()
+                    .switch_to_frame_element(locator)
+                    .await
+            }
+        }
+    }
+}
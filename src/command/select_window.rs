@@ -0,0 +1,73 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use super::Command;
+use crate::{error::RunnerErrorKind, parser::WindowLocator, webdriver::Webdriver};
+
+pub struct SelectWindow {
+    locator: WindowLocator,
+}
+
+impl SelectWindow {
+    pub fn new(locator: WindowLocator) -> Self {
+        Self { locator }
+    }
+}
+
+#[async_trait::async_trait]
+impl Command for SelectWindow {
+    async fn run<D>(&self, runner: &mut crate::runner::Runner<D>) -> Result<(), RunnerErrorKind>
+    where
+        D: Webdriver,
+    {
+        match &self.locator {
+            WindowLocator::Handle(handle) => {
+                let handle = runner.emit(handle);
+                runner.get_webdriver().switch_to_window(&handle).await
+            }
+            WindowLocator::Name(name) => {
+                let name = runner.emit(name);
+                select_by_name(runner.get_webdriver(), &name).await
+            }
+            // Selenium IDE names a window a test was recorded in so.
+            WindowLocator::Serialized(name) if name == "win_ser_local" => {
+                let handle = runner
+                    .get_start_window()
+                    .map(str::to_owned)
+                    .ok_or_else(|| {
+                        RunnerErrorKind::ActionFailed(
+                            "the window the test was started in is unknown".to_owned(),
+                        )
+                    })?;
+                runner.get_webdriver().switch_to_window(&handle).await
+            }
+            // Other windows are known only to Selenium IDE,
+            // a handle of a window opened by a command is saved by its `windowHandleName` instead.
+            WindowLocator::Serialized(name) => Err(RunnerErrorKind::ActionFailed(format!(
+                "window {} isn't supported, select a window by handle=${{windowHandleName}}",
+                name
+            ))),
+        }
+    }
+}
+
+/// WebDriver switches windows only by handles so each window is checked in turn.
+async fn select_by_name<D: Webdriver>(
+    webdriver: &mut D,
+    name: &str,
+) -> Result<(), RunnerErrorKind> {
+    let current = webdriver.current_window_handle().await?;
+    for handle in webdriver.window_handles().await? {
+        webdriver.switch_to_window(&handle).await?;
+        if webdriver.execute("return window.name;", Vec::new()).await? == *name {
+            return Ok(());
+        }
+    }
+
+    webdriver.switch_to_window(&current).await?;
+    Err(RunnerErrorKind::ActionFailed(format!(
+        "no window with name {:?}",
+        name
+    )))
+}
@@ -25,7 +25,6 @@ impl Command for SendKeys {
     where
         D: Webdriver,
     {
-        // todo: add support for a KEY_STROKES like KEY_ENTER
         let element = runner.get_webdriver().find(self.target.clone()).await?;
         element.send_keys(&self.text).await?;
         Ok(())
@@ -22,8 +22,7 @@ impl Command for StoreJson {
     where
         D: Webdriver,
     {
-        let value = runner.emit(&self.value);
-        let value = serde_json::from_str(&value).map_err(|_| {
+        let value = serde_json::from_str(&self.value).map_err(|_| {
             RunnerErrorKind::MismatchedType("Unexpected type of json object".to_string())
         })?;
         runner.save_value(self.variable.clone(), value);
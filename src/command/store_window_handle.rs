@@ -0,0 +1,29 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use super::Command;
+use crate::{error::RunnerErrorKind, webdriver::Webdriver};
+use serde_json::Value;
+
+pub struct StoreWindowHandle {
+    var: String,
+}
+
+impl StoreWindowHandle {
+    pub fn new(var: String) -> Self {
+        Self { var }
+    }
+}
+
+#[async_trait::async_trait]
+impl Command for StoreWindowHandle {
+    async fn run<D>(&self, runner: &mut crate::runner::Runner<D>) -> Result<(), RunnerErrorKind>
+    where
+        D: Webdriver,
+    {
+        let handle = runner.get_webdriver().current_window_handle().await?;
+        runner.save_value(self.var.clone(), Value::String(handle));
+        Ok(())
+    }
+}
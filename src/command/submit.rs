@@ -0,0 +1,36 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use super::Command;
+use crate::{
+    error::RunnerErrorKind,
+    webdriver::{Element, Locator, Webdriver},
+};
+
+pub struct Submit {
+    target: Locator,
+}
+
+impl Submit {
+    pub fn new(target: Locator) -> Self {
+        Self { target }
+    }
+}
+
+#[async_trait::async_trait]
+impl Command for Submit {
+    async fn run<D>(&self, runner: &mut crate::runner::Runner<D>) -> Result<(), RunnerErrorKind>
+    where
+        D: Webdriver,
+    {
+        runner
+            .get_webdriver()
+            .find(self.target.clone())
+            .await?
+            .submit()
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use super::Command;
+use crate::{error::RunnerErrorKind, webdriver::Webdriver};
+
+pub struct WebdriverAnswerOnVisiblePrompt {
+    answer: String,
+}
+
+impl WebdriverAnswerOnVisiblePrompt {
+    pub fn new(answer: String) -> Self {
+        Self { answer }
+    }
+}
+
+#[async_trait::async_trait]
+impl Command for WebdriverAnswerOnVisiblePrompt {
+    async fn run<D>(&self, runner: &mut crate::runner::Runner<D>) -> Result<(), RunnerErrorKind>
+    where
+        D: Webdriver,
+    {
+        runner.get_webdriver().alert_send_text(&self.answer).await?;
+        runner.get_webdriver().alert_accept().await
+    }
+}
+
+pub struct WebdriverChooseOkOnVisibleConfirmation;
+
+#[async_trait::async_trait]
+impl Command for WebdriverChooseOkOnVisibleConfirmation {
+    async fn run<D>(&self, runner: &mut crate::runner::Runner<D>) -> Result<(), RunnerErrorKind>
+    where
+        D: Webdriver,
+    {
+        runner.get_webdriver().alert_accept().await
+    }
+}
+
+pub struct WebdriverChooseCancelOnVisibleConfirmation;
+
+#[async_trait::async_trait]
+impl Command for WebdriverChooseCancelOnVisibleConfirmation {
+    async fn run<D>(&self, runner: &mut crate::runner::Runner<D>) -> Result<(), RunnerErrorKind>
+    where
+        D: Webdriver,
+    {
+        runner.get_webdriver().alert_dissmis().await
+    }
+}
+
+pub struct WebdriverChooseCancelOnVisiblePrompt;
+
+#[async_trait::async_trait]
+impl Command for WebdriverChooseCancelOnVisiblePrompt {
+    async fn run<D>(&self, runner: &mut crate::runner::Runner<D>) -> Result<(), RunnerErrorKind>
+    where
+        D: Webdriver,
+    {
+        runner.get_webdriver().alert_dissmis().await
+    }
+}
@@ -2,7 +2,8 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use std::fmt;
+use crate::{writer::format_cmd, Command, Test};
+use std::{error::Error, fmt, path::Path};
 
 /// RunnerError represents a Error which may occure while running
 /// running [`Command`].
@@ -12,6 +13,14 @@ pub struct RunnerError {
     pub kind: RunnerErrorKind,
     pub index: usize,
     pub test: Option<String>,
+    /// A command which failed, if the error relates to a particular one.
+    pub command: Option<Box<FailedCommand>>,
+    /// A path to a screenshot taken on the failure.
+    ///
+    /// See [`Runner::set_artifacts_dir`](crate::Runner::set_artifacts_dir).
+    pub screenshot: Option<Box<Path>>,
+    /// A path to a source of the page saved on the failure.
+    pub page_source: Option<Box<Path>>,
 }
 
 impl RunnerError {
@@ -21,8 +30,85 @@ impl RunnerError {
             kind,
             index,
             test: None,
+            command: None,
+            screenshot: None,
+            page_source: None,
         }
     }
+
+    /// Attaches a test and a command at the error's index.
+    pub(crate) fn with_context(mut self, test: &Test) -> Self {
+        self.test = Some(test.name.clone());
+        if self.command.is_none() {
+            self.command = test
+                .commands
+                .get(self.index)
+                .map(|command| Box::new(FailedCommand::new(command)));
+        }
+
+        self
+    }
+}
+
+impl fmt::Display for RunnerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.test, &self.command) {
+            (Some(test), Some(command)) => write!(
+                f,
+                "test '{}' step {} ({}): {}",
+                test, self.index, command, self.kind
+            ),
+            (Some(test), None) => write!(f, "test '{}': {}", test, self.kind),
+            (None, _) => write!(f, "{}", self.kind),
+        }
+    }
+}
+
+impl Error for RunnerError {
+    // A message already includes a kind so the chain continues with its cause.
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.kind.source()
+    }
+}
+
+/// A command which caused a [`RunnerError`].
+///
+/// A target and a value are kept as they're written in a file, without substituted variables.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FailedCommand {
+    /// A name of the command as it's written in Selenium IDE, e.g. `click`.
+    pub name: String,
+    /// An id of the command which can be used to find it in Selenium IDE.
+    pub id: String,
+    pub comment: String,
+    pub target: String,
+    pub value: String,
+}
+
+impl FailedCommand {
+    fn new(command: &Command) -> Self {
+        let (name, target, value) = format_cmd(&command.cmd);
+        Self {
+            name,
+            id: command.id.clone(),
+            comment: command.comment.clone(),
+            target,
+            value,
+        }
+    }
+}
+
+impl fmt::Display for FailedCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        for arg in [&self.target, &self.value] {
+            if !arg.is_empty() {
+                write!(f, " {}", arg)?;
+            }
+        }
+
+        Ok(())
+    }
 }
 
 impl fmt::Debug for RunnerError {
@@ -44,10 +130,10 @@ impl fmt::Debug for RunnerError {
 ///
 /// [`Test`]: struct.Test.html
 pub enum RunnerErrorKind {
-    #[cfg(feature = "fantoccini_backend")]
-    WebdriverError(fantoccini::error::CmdError),
-    #[cfg(feature = "thirtyfour_backend")]
-    WebdriverError(thirtyfour::error::WebDriverError),
+    /// An error of a webdriver backend.
+    ///
+    /// An original error is its [`source`](std::error::Error::source) and can be retrieved by downcasting.
+    WebdriverError(Box<dyn std::error::Error + Send + Sync>),
     BranchValidationError(String),
     MismatchedType(String),
     Url(url::ParseError),
@@ -57,6 +143,9 @@ pub enum RunnerErrorKind {
         rhs: String,
     },
     TestNotFound(String),
+    SuiteNotFound(String),
+    VerificationFailed(Vec<RunnerError>),
+    ActionFailed(String),
 }
 
 impl std::fmt::Debug for RunnerErrorKind {
@@ -69,6 +158,94 @@ impl std::fmt::Debug for RunnerErrorKind {
             Self::Timeout(desc) => write!(f, "timeout {}", desc),
             Self::AssertFailed { lhs, rhs } => write!(f, "assert failed {} == {}", lhs, rhs),
             Self::TestNotFound(name) => write!(f, "A test {} wasn't found", name),
+            Self::SuiteNotFound(name) => write!(f, "A suite {} wasn't found", name),
+            Self::ActionFailed(desc) => write!(f, "action failed {}", desc),
+            Self::VerificationFailed(failures) => {
+                write!(f, "{} verifications failed {:?}", failures.len(), failures)
+            }
+        }
+    }
+}
+
+impl fmt::Display for RunnerErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BranchValidationError(err) => write!(f, "branching error: {}", err),
+            Self::WebdriverError(..) => write!(f, "webdriver error"),
+            Self::Url(..) => write!(f, "url construction error"),
+            Self::MismatchedType(desc) => write!(f, "mismatched type: {}", desc),
+            Self::Timeout(desc) => write!(f, "timeout: {}", desc),
+            Self::AssertFailed { lhs, rhs } => write!(f, "assert failed: {} != {}", lhs, rhs),
+            Self::TestNotFound(name) => write!(f, "test '{}' wasn't found", name),
+            Self::SuiteNotFound(name) => write!(f, "suite '{}' wasn't found", name),
+            Self::ActionFailed(desc) => write!(f, "action failed: {}", desc),
+            Self::VerificationFailed(failures) => {
+                write!(f, "{} verifications failed", failures.len())?;
+                for (i, failure) in failures.iter().enumerate() {
+                    let sep = if i == 0 { ": " } else { "; " };
+                    write!(f, "{}{}", sep, Chain(failure))?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl RunnerErrorKind {
+    /// Checks if the error means a webdriver session is gone,
+    /// so no command can be run after it.
+    pub(crate) fn ends_session(&self) -> bool {
+        let err = match self {
+            Self::WebdriverError(err) => err,
+            _ => return false,
+        };
+
+        #[cfg(feature = "thirtyfour_backend")]
+        {
+            use thirtyfour::error::WebDriverError;
+            if let Some(err) = err.downcast_ref::<WebDriverError>() {
+                return matches!(
+                    err,
+                    WebDriverError::InvalidSessionId(..)
+                        | WebDriverError::SessionNotCreated(..)
+                        | WebDriverError::RequestFailed(..)
+                        | WebDriverError::HttpError(..)
+                        | WebDriverError::IoError(..)
+                        | WebDriverError::FatalError(..)
+                );
+            }
+        }
+
+        #[cfg(feature = "fantoccini_backend")]
+        {
+            use fantoccini::error::CmdError;
+            use webdriver::error::ErrorStatus;
+            if let Some(err) = err.downcast_ref::<CmdError>() {
+                return match err {
+                    CmdError::Standard(err) => matches!(
+                        err.error,
+                        ErrorStatus::InvalidSessionId | ErrorStatus::SessionNotCreated
+                    ),
+                    CmdError::Failed(..) | CmdError::Lost(..) => true,
+                    _ => false,
+                };
+            }
+            if err.is::<hyper::Error>() {
+                return true;
+            }
+        }
+
+        false
+    }
+}
+
+impl Error for RunnerErrorKind {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::WebdriverError(err) => Some(err.as_ref()),
+            Self::Url(err) => Some(err),
+            _ => None,
         }
     }
 }
@@ -76,14 +253,14 @@ impl std::fmt::Debug for RunnerErrorKind {
 #[cfg(feature = "fantoccini_backend")]
 impl From<fantoccini::error::CmdError> for RunnerErrorKind {
     fn from(err: fantoccini::error::CmdError) -> Self {
-        RunnerErrorKind::WebdriverError(err)
+        RunnerErrorKind::WebdriverError(Box::new(err))
     }
 }
 
 #[cfg(feature = "thirtyfour_backend")]
 impl From<thirtyfour::error::WebDriverError> for RunnerErrorKind {
     fn from(err: thirtyfour::error::WebDriverError) -> Self {
-        RunnerErrorKind::WebdriverError(err)
+        RunnerErrorKind::WebdriverError(Box::new(err))
     }
 }
 
@@ -95,7 +272,96 @@ impl From<url::ParseError> for RunnerErrorKind {
 
 /// ParseError represents errors which may occure while
 /// parsing a `Side` file.
-pub enum ParseError {
+///
+/// Errors of a particular command point to its test, index and id
+/// so it can be found in Selenium IDE.
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    /// A name of a test the error belongs to.
+    pub test: Option<String>,
+    /// An index of a command in the test.
+    pub index: Option<usize>,
+    /// An id of the command.
+    pub id: Option<String>,
+    /// A field of the command which can't be parsed.
+    pub field: Option<CommandField>,
+}
+
+impl ParseError {
+    /// New creates an error without a context.
+    pub fn new(kind: ParseErrorKind) -> Self {
+        Self {
+            kind,
+            test: None,
+            index: None,
+            id: None,
+            field: None,
+        }
+    }
+
+    pub(crate) fn in_field(kind: ParseErrorKind, field: CommandField) -> Self {
+        let mut err = Self::new(kind);
+        err.field = Some(field);
+        err
+    }
+}
+
+impl From<ParseErrorKind> for ParseError {
+    fn from(kind: ParseErrorKind) -> Self {
+        Self::new(kind)
+    }
+}
+
+impl ParseError {
+    fn fmt_context(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(test) = &self.test {
+            write!(f, "test '{}'", test)?;
+        }
+        if let Some(index) = self.index {
+            write!(f, " step {}", index)?;
+        }
+        if let Some(id) = &self.id {
+            write!(f, " (id={})", id)?;
+        }
+        if let Some(field) = self.field {
+            if self.test.is_some() {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", field)?;
+        }
+        if self.test.is_some() || self.field.is_some() {
+            write!(f, ": ")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_context(f)?;
+        write!(f, "{:?}", self.kind)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_context(f)?;
+        write!(f, "{}", self.kind)
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            ParseErrorKind::FormatError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// ParseErrorKind represents a reason of a [`ParseError`].
+pub enum ParseErrorKind {
     FormatError(serde_json::Error),
     LocatorFormatError(String),
     TypeError(String),
@@ -103,7 +369,7 @@ pub enum ParseError {
     NotUniqTestName(usize, usize),
 }
 
-impl std::fmt::Debug for ParseError {
+impl std::fmt::Debug for ParseErrorKind {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::FormatError(err) => write!(f, "parsing json error {:?}", err),
@@ -118,3 +384,142 @@ impl std::fmt::Debug for ParseError {
         }
     }
 }
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FormatError(..) => write!(f, "parsing json error"),
+            Self::LocatorFormatError(err) => write!(f, "locator has wrong format: {}", err),
+            Self::TypeError(err) => write!(f, "unexpected type: {}", err),
+            Self::ValidationError(err) => write!(f, "validation error: {}", err),
+            Self::NotUniqTestName(t1, t2) => write!(
+                f,
+                "file contains tests with the same name (test1={}, test2={})",
+                t1, t2
+            ),
+        }
+    }
+}
+
+/// Displays an error followed by its causes, e.g. `webdriver error: no such element`.
+pub(crate) struct Chain<'a>(pub &'a dyn Error);
+
+impl fmt::Display for Chain<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)?;
+        let mut source = self.0.source();
+        while let Some(err) = source {
+            write!(f, ": {}", err)?;
+            source = err.source();
+        }
+
+        Ok(())
+    }
+}
+
+/// A field of a command in a `Side` file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandField {
+    /// A name of the command.
+    Command,
+    Target,
+    Value,
+}
+
+impl fmt::Display for CommandField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Command => "command",
+            Self::Target => "target",
+            Self::Value => "value",
+        };
+        f.write_str(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    fn login_test() -> Test {
+        let file = r#"{
+            "id": "", "version": "2.0", "name": "project", "url": "http://localhost",
+            "tests": [
+                { "id": "1", "name": "login", "commands": [
+                    { "id": "c1", "comment": "", "command": "open", "target": "/login", "targets": [], "value": "" },
+                    { "id": "c2", "comment": "submit a form", "command": "click", "target": "css=#submit", "targets": [], "value": "" }
+                ]}
+            ]
+        }"#;
+
+        parse(file.as_bytes()).unwrap().tests.remove(0)
+    }
+
+    #[test]
+    fn display_with_command() {
+        let err = RunnerError::new(
+            RunnerErrorKind::ActionFailed("element not found".to_owned()),
+            1,
+        )
+        .with_context(&login_test());
+
+        assert_eq!(
+            err.to_string(),
+            "test 'login' step 1 (click css=#submit): action failed: element not found"
+        );
+        assert_eq!(
+            err.command.as_deref(),
+            Some(&FailedCommand {
+                name: "click".to_owned(),
+                id: "c2".to_owned(),
+                comment: "submit a form".to_owned(),
+                target: "css=#submit".to_owned(),
+                value: "".to_owned(),
+            })
+        );
+    }
+
+    #[test]
+    fn display_without_command() {
+        let err = RunnerError::new(RunnerErrorKind::Timeout("5s".to_owned()), 2)
+            .with_context(&login_test());
+        assert_eq!(err.command, None);
+        assert_eq!(err.to_string(), "test 'login': timeout: 5s");
+
+        let err = RunnerError::new(RunnerErrorKind::TestNotFound("login".to_owned()), 0);
+        assert_eq!(err.to_string(), "test 'login' wasn't found");
+    }
+
+    #[cfg(feature = "thirtyfour_backend")]
+    #[test]
+    fn ends_session() {
+        use thirtyfour::error::{WebDriverError, WebDriverErrorInfo};
+
+        let session_err = WebDriverError::InvalidSessionId(WebDriverErrorInfo::new("gone"));
+        assert!(RunnerErrorKind::from(session_err).ends_session());
+
+        let element_err = WebDriverError::NoSuchElement(WebDriverErrorInfo::new("missing"));
+        assert!(!RunnerErrorKind::from(element_err).ends_session());
+        assert!(!RunnerErrorKind::Timeout("5s".to_owned()).ends_session());
+    }
+
+    #[test]
+    fn error_source() {
+        let err = RunnerError::new(RunnerErrorKind::Url(url::ParseError::EmptyHost), 0);
+        assert_eq!(err.to_string(), "url construction error");
+        assert_eq!(err.source().unwrap().to_string(), "empty host");
+        assert_eq!(
+            Chain(&err).to_string(),
+            "url construction error: empty host"
+        );
+
+        let err: Box<dyn Error> = Box::new(parse("{".as_bytes()).unwrap_err());
+        assert_eq!(err.to_string(), "parsing json error");
+        assert!(err
+            .source()
+            .unwrap()
+            .to_string()
+            .contains("EOF while parsing"));
+    }
+}
@@ -0,0 +1,154 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Healing of locators which became outdated.
+//!
+//! When healing is turned on by [`Runner::set_healing`],
+//! the runner keeps track of commands whose target wasn't found
+//! but one of the alternative targets recorded by Selenium IDE worked.
+//! A file can be corrected afterwards by promoting these alternatives to targets.
+//!
+//! ```no_run
+//! # async fn run<D: siderunner::webdriver::Webdriver>(mut runner: siderunner::Runner<D>, mut file: siderunner::File) {
+//! runner.set_healing(true);
+//! runner.run(&file).await.unwrap();
+//!
+//! for healed in runner.get_healed_targets() {
+//!     println!("{}", healed);
+//! }
+//!
+//! siderunner::healing::apply(&mut file, runner.get_healed_targets());
+//! let out = std::fs::File::create("healed.side").unwrap();
+//! siderunner::write(&file, out).unwrap();
+//! # }
+//! ```
+//!
+//! [`Runner::set_healing`]: crate::Runner::set_healing
+
+use crate::{File, Target};
+use std::fmt;
+
+/// A command which was run with an alternative target.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HealedTarget {
+    /// A name of a test the command belongs to.
+    pub test: String,
+    /// An index of the command in the test.
+    pub index: usize,
+    /// An id of the command.
+    pub id: String,
+    /// A target of the command which didn't match any element.
+    pub target: Target,
+    /// An alternative target which was used instead.
+    pub alternative: Target,
+}
+
+impl fmt::Display for HealedTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "test '{}' step {} (id={}): {} -> {}",
+            self.test, self.index, self.id, self.target.location, self.alternative.location
+        )
+    }
+}
+
+/// Promotes alternative targets to targets of commands.
+///
+/// A command is updated only if it still has the same id and target,
+/// so a report which doesn't belong to the file is ignored.
+/// It returns a number of updated commands.
+pub fn apply(file: &mut File, healed: &[HealedTarget]) -> usize {
+    let mut updated = 0;
+    for healed in healed {
+        let command = file
+            .tests
+            .iter_mut()
+            .find(|test| test.name == healed.test)
+            .and_then(|test| test.commands.get_mut(healed.index));
+        let command = match command {
+            Some(command) if command.id == healed.id => command,
+            _ => continue,
+        };
+        if command.cmd.target() != Some(&healed.target) {
+            continue;
+        }
+
+        command.cmd = command
+            .cmd
+            .with_target(Target::new(healed.alternative.location.clone()));
+        updated += 1;
+    }
+
+    updated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse, Location};
+
+    fn side_file() -> File {
+        let file = r#"{
+            "id": "", "version": "2.0", "name": "project", "url": "http://localhost",
+            "tests": [
+                { "id": "1", "name": "login", "commands": [
+                    { "id": "c1", "comment": "", "command": "open", "target": "/", "targets": [], "value": "" },
+                    { "id": "c2", "comment": "", "command": "click", "target": "css=#old", "targets": [
+                        ["css=#old", "css:finder"],
+                        ["id=submit", "id"]
+                    ], "value": "" }
+                ]}
+            ]
+        }"#;
+
+        parse(file.as_bytes()).unwrap()
+    }
+
+    fn healed(id: &str) -> HealedTarget {
+        HealedTarget {
+            test: "login".to_owned(),
+            index: 1,
+            id: id.to_owned(),
+            target: Target::new(Location::Css("#old".to_owned())),
+            alternative: Target {
+                location: Location::Id("submit".to_owned()).into(),
+                tag: Some("id".to_owned()),
+            },
+        }
+    }
+
+    #[test]
+    fn apply_healed_targets() {
+        let mut file = side_file();
+
+        assert_eq!(apply(&mut file, &[healed("c2")]), 1);
+
+        let command = &file.tests[0].commands[1];
+        assert_eq!(
+            command.cmd.target(),
+            Some(&Target::new(Location::Id("submit".to_owned())))
+        );
+        assert_eq!(command.targets.len(), 2);
+
+        // The command is already healed.
+        assert_eq!(apply(&mut file, &[healed("c2")]), 0);
+    }
+
+    #[test]
+    fn skip_unknown_commands() {
+        let mut file = side_file();
+
+        assert_eq!(apply(&mut file, &[healed("c1")]), 0);
+        assert_eq!(file, side_file());
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            healed("c2").to_string(),
+            "test 'login' step 1 (id=c2): css=#old -> id=submit"
+        );
+    }
+}
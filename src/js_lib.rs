@@ -16,6 +16,9 @@ macro_rules! include_func {
 const REPLACE_ALERT_METHOD: &str = include_func!("replaceAlertMethod.js");
 const ANSWER_ON_NEXT_PROMPT: &str = include_func!("answerOnNextPrompt.js");
 const SET_NEXT_CONFIRMATION_STATE: &str = include_func!("setNextConfirmationState.js");
+pub(crate) const DRAG_AND_DROP_TO_OBJECT: &str = include_func!("dragAndDropToObject.js");
+pub(crate) const WATCH_DROP: &str = include_func!("watchDrop.js");
+pub(crate) const SUBMIT_FORM: &str = include_func!("submitForm.js");
 
 pub async fn answer_on_next_prompt<D>(
     runner: &mut Runner<D>,
@@ -0,0 +1,350 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! A reporter of runs in [JUnit XML] format which is understood by most CI systems.
+//!
+//! ```no_run
+//! # async fn run<D: siderunner::webdriver::Webdriver>(mut runner: siderunner::Runner<D>, file: siderunner::File) {
+//! let suite = siderunner::junit::run(&mut runner, &file).await;
+//!
+//! let out = std::fs::File::create("report.xml").unwrap();
+//! siderunner::junit::write(&[suite], out).unwrap();
+//! # }
+//! ```
+//!
+//! [JUnit XML]: https://llg.cubic.org/docs/junit/
+
+use crate::{
+    error::{Chain, RunnerError, RunnerErrorKind},
+    webdriver::Webdriver,
+    writer::format_cmd,
+    File, Runner,
+};
+use std::{
+    io::{self, Write},
+    time::{Duration, Instant},
+};
+
+/// A report of a run of a [`File`].
+///
+/// It's written as a `<testsuite>` element.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestSuite {
+    /// A name of the suite, a name of a file by default.
+    pub name: String,
+    pub cases: Vec<TestCase>,
+}
+
+impl TestSuite {
+    /// Creates an empty report.
+    pub fn new<S: Into<String>>(name: S) -> Self {
+        Self {
+            name: name.into(),
+            cases: Vec::new(),
+        }
+    }
+
+    /// Adds a result of a test run.
+    ///
+    /// A file is used to look up a failed command.
+    pub fn add<S: Into<String>>(
+        &mut self,
+        file: &File,
+        name: S,
+        time: Duration,
+        result: &Result<(), RunnerError>,
+    ) {
+        self.cases.push(TestCase {
+            name: name.into(),
+            time,
+            failure: result.as_ref().err().map(|err| Failure::new(file, err)),
+        });
+    }
+
+    /// Returns a number of failed tests.
+    pub fn failures(&self) -> usize {
+        self.cases
+            .iter()
+            .filter(|case| case.failure.is_some())
+            .count()
+    }
+
+    /// Returns a time spent on all tests.
+    pub fn time(&self) -> Duration {
+        self.cases.iter().map(|case| case.time).sum()
+    }
+
+    fn write<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        writeln!(
+            w,
+            r#"  <testsuite name="{}" tests="{}" failures="{}" errors="0" time="{:.3}">"#,
+            escape(&self.name),
+            self.cases.len(),
+            self.failures(),
+            self.time().as_secs_f64(),
+        )?;
+        for case in &self.cases {
+            case.write(&self.name, w)?;
+        }
+        writeln!(w, "  </testsuite>")
+    }
+}
+
+/// A result of a single test.
+///
+/// It's written as a `<testcase>` element.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestCase {
+    pub name: String,
+    pub time: Duration,
+    /// A failure or `None` if the test passed.
+    pub failure: Option<Failure>,
+}
+
+impl TestCase {
+    fn write<W: Write>(&self, classname: &str, w: &mut W) -> io::Result<()> {
+        write!(
+            w,
+            r#"    <testcase name="{}" classname="{}" time="{:.3}""#,
+            escape(&self.name),
+            escape(classname),
+            self.time.as_secs_f64(),
+        )?;
+        match &self.failure {
+            Some(failure) => {
+                writeln!(w, ">")?;
+                failure.write(w)?;
+                writeln!(w, "    </testcase>")
+            }
+            None => writeln!(w, "/>"),
+        }
+    }
+}
+
+/// A description of a failed command.
+///
+/// It's written as a `<failure>` element.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Failure {
+    /// A test the failed command belongs to.
+    ///
+    /// It may differ from a test case if the command was called by `run`.
+    pub test: Option<String>,
+    /// An index of the command in the test.
+    pub index: usize,
+    /// A name of the command as it's written in Selenium IDE, e.g. `assertText`.
+    pub command: String,
+    pub target: String,
+    pub value: String,
+    /// A kind of an error, e.g. `AssertFailed`.
+    pub kind: String,
+    pub message: String,
+}
+
+impl Failure {
+    /// Creates a failure from an error of a run of a file.
+    pub fn new(file: &File, err: &RunnerError) -> Self {
+        let command = err
+            .test
+            .as_ref()
+            .and_then(|name| file.tests.iter().find(|test| &test.name == name))
+            .and_then(|test| test.commands.get(err.index));
+        let (command, target, value) = command
+            .map(|command| format_cmd(&command.cmd))
+            .unwrap_or_default();
+
+        Self {
+            test: err.test.clone(),
+            index: err.index,
+            command,
+            target,
+            value,
+            kind: kind_name(&err.kind).to_owned(),
+            message: Chain(&err.kind).to_string(),
+        }
+    }
+
+    fn write<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        writeln!(
+            w,
+            r#"      <failure message="{}" type="{}">"#,
+            escape(&self.message),
+            escape(&self.kind),
+        )?;
+        if let Some(test) = &self.test {
+            writeln!(w, "test: {}", escape(test))?;
+        }
+        writeln!(w, "command index: {}", self.index)?;
+        writeln!(w, "command: {}", escape(&self.command))?;
+        writeln!(w, "target: {}", escape(&self.target))?;
+        writeln!(w, "value: {}", escape(&self.value))?;
+        writeln!(w, "      </failure>")
+    }
+}
+
+/// Runs all tests in a file one by one and reports their results.
+///
+/// Unlike [`Runner::run`] it doesn't stop on a failed test.
+pub async fn run<D: Webdriver>(runner: &mut Runner<D>, file: &File) -> TestSuite {
+    let mut suite = TestSuite::new(file.name.clone());
+    for (index, test) in file.tests.iter().enumerate() {
+        let start = Instant::now();
+        let result = runner.run_test_by_index(file, index).await;
+        suite.add(file, test.name.clone(), start.elapsed(), &result);
+    }
+
+    suite
+}
+
+/// Writes reports as a `<testsuites>` document.
+pub fn write<W: Write>(suites: &[TestSuite], mut w: W) -> io::Result<()> {
+    let tests: usize = suites.iter().map(|suite| suite.cases.len()).sum();
+    let failures: usize = suites.iter().map(TestSuite::failures).sum();
+    let time: Duration = suites.iter().map(TestSuite::time).sum();
+
+    writeln!(w, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        w,
+        r#"<testsuites tests="{}" failures="{}" errors="0" time="{:.3}">"#,
+        tests,
+        failures,
+        time.as_secs_f64(),
+    )?;
+    for suite in suites {
+        suite.write(&mut w)?;
+    }
+    writeln!(w, "</testsuites>")
+}
+
+fn kind_name(kind: &RunnerErrorKind) -> &'static str {
+    match kind {
+        RunnerErrorKind::WebdriverError(..) => "WebdriverError",
+        RunnerErrorKind::BranchValidationError(..) => "BranchValidationError",
+        RunnerErrorKind::MismatchedType(..) => "MismatchedType",
+        RunnerErrorKind::Url(..) => "Url",
+        RunnerErrorKind::Timeout(..) => "Timeout",
+        RunnerErrorKind::AssertFailed { .. } => "AssertFailed",
+        RunnerErrorKind::TestNotFound(..) => "TestNotFound",
+        RunnerErrorKind::SuiteNotFound(..) => "SuiteNotFound",
+        RunnerErrorKind::VerificationFailed(..) => "VerificationFailed",
+        RunnerErrorKind::ActionFailed(..) => "ActionFailed",
+    }
+}
+
+fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' => escaped.push_str("&#10;"),
+            // Other control characters are not allowed in XML 1.0.
+            c if c.is_control() && c != '\t' && c != '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    fn side_file() -> File {
+        let file = r#"{
+            "id": "", "version": "2.0", "name": "project", "url": "http://localhost",
+            "tests": [
+                { "id": "1", "name": "passed", "commands": [] },
+                { "id": "2", "name": "failed", "commands": [
+                    { "id": "", "comment": "", "command": "echo", "target": "hello", "targets": [], "value": "" },
+                    { "id": "", "comment": "", "command": "assert", "target": "var", "targets": [], "value": "<1>" }
+                ]}
+            ]
+        }"#;
+
+        parse(file.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn write_report() {
+        let file = side_file();
+        let err = {
+            let mut err = RunnerError::new(
+                RunnerErrorKind::AssertFailed {
+                    lhs: "\"2\"".to_owned(),
+                    rhs: "<1>".to_owned(),
+                },
+                1,
+            );
+            err.test = Some("failed".to_owned());
+            err
+        };
+
+        let mut suite = TestSuite::new("project");
+        suite.add(&file, "passed", Duration::from_millis(1500), &Ok(()));
+        suite.add(&file, "failed", Duration::from_millis(250), &Err(err));
+
+        assert_eq!(suite.failures(), 1);
+        assert_eq!(
+            suite.cases[1].failure.as_ref().map(|f| f.command.as_str()),
+            Some("assert")
+        );
+
+        let mut out = Vec::new();
+        write(&[suite], &mut out).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites tests="2" failures="1" errors="0" time="1.750">
+  <testsuite name="project" tests="2" failures="1" errors="0" time="1.750">
+    <testcase name="passed" classname="project" time="1.500"/>
+    <testcase name="failed" classname="project" time="0.250">
+      <failure message="assert failed: &quot;2&quot; != &lt;1&gt;" type="AssertFailed">
+test: failed
+command index: 1
+command: assert
+target: var
+value: &lt;1&gt;
+      </failure>
+    </testcase>
+  </testsuite>
+</testsuites>
+"#
+        );
+    }
+
+    #[tokio::test]
+    async fn run_file() {
+        let file = side_file();
+        let mut runner = Runner::with_backend(crate::testing::MockDriver::new());
+        runner.set_echo(|_| {});
+
+        let suite = run(&mut runner, &file).await;
+
+        assert_eq!(suite.name, "project");
+        assert_eq!(suite.cases.len(), 2);
+        assert_eq!(suite.cases[0].failure, None);
+        let failure = suite.cases[1].failure.as_ref().unwrap();
+        assert_eq!(failure.test.as_deref(), Some("failed"));
+        assert_eq!(failure.index, 1);
+        assert_eq!(failure.command, "assert");
+    }
+
+    #[test]
+    fn failure_without_test() {
+        let file = side_file();
+        let err = RunnerError::new(RunnerErrorKind::TestNotFound("missing".to_owned()), 0);
+        let failure = Failure::new(&file, &err);
+
+        assert_eq!(failure.test, None);
+        assert_eq!(failure.command, "");
+        assert_eq!(failure.kind, "TestNotFound");
+    }
+}
@@ -0,0 +1,89 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Special keys which Selenium IDE accepts in `sendKeys` and `type` commands, e.g. `${KEY_ENTER}`.
+//!
+//! They're translated to WebDriver key codes, so any backend sends them as ordinary text.
+//!
+//! https://www.w3.org/TR/webdriver/#keyboard-actions
+
+/// Returns a WebDriver code of a key by its name in Selenium IDE without `${}`, e.g. `KEY_ENTER`.
+pub(crate) fn key_code(name: &str) -> Option<char> {
+    let code = match name {
+        "KEY_NULL" => '\u{E000}',
+        "KEY_CANCEL" => '\u{E001}',
+        "KEY_HELP" => '\u{E002}',
+        "KEY_BACKSPACE" | "KEY_BKSP" => '\u{E003}',
+        "KEY_TAB" => '\u{E004}',
+        "KEY_CLEAR" => '\u{E005}',
+        "KEY_RETURN" => '\u{E006}',
+        "KEY_ENTER" => '\u{E007}',
+        "KEY_SHIFT" => '\u{E008}',
+        "KEY_CONTROL" | "KEY_CTRL" => '\u{E009}',
+        "KEY_ALT" => '\u{E00A}',
+        "KEY_PAUSE" => '\u{E00B}',
+        "KEY_ESCAPE" | "KEY_ESC" => '\u{E00C}',
+        "KEY_SPACE" => '\u{E00D}',
+        "KEY_PAGE_UP" | "KEY_PGUP" => '\u{E00E}',
+        "KEY_PAGE_DOWN" | "KEY_PGDN" => '\u{E00F}',
+        "KEY_END" => '\u{E010}',
+        "KEY_HOME" => '\u{E011}',
+        "KEY_LEFT" => '\u{E012}',
+        "KEY_UP" => '\u{E013}',
+        "KEY_RIGHT" => '\u{E014}',
+        "KEY_DOWN" => '\u{E015}',
+        "KEY_INSERT" | "KEY_INS" => '\u{E016}',
+        "KEY_DELETE" | "KEY_DEL" => '\u{E017}',
+        "KEY_SEMICOLON" => '\u{E018}',
+        "KEY_EQUALS" => '\u{E019}',
+        "KEY_NUMPAD0" | "KEY_N0" => '\u{E01A}',
+        "KEY_NUMPAD1" | "KEY_N1" => '\u{E01B}',
+        "KEY_NUMPAD2" | "KEY_N2" => '\u{E01C}',
+        "KEY_NUMPAD3" | "KEY_N3" => '\u{E01D}',
+        "KEY_NUMPAD4" | "KEY_N4" => '\u{E01E}',
+        "KEY_NUMPAD5" | "KEY_N5" => '\u{E01F}',
+        "KEY_NUMPAD6" | "KEY_N6" => '\u{E020}',
+        "KEY_NUMPAD7" | "KEY_N7" => '\u{E021}',
+        "KEY_NUMPAD8" | "KEY_N8" => '\u{E022}',
+        "KEY_NUMPAD9" | "KEY_N9" => '\u{E023}',
+        "KEY_MULTIPLY" => '\u{E024}',
+        "KEY_ADD" => '\u{E025}',
+        "KEY_SEPARATOR" => '\u{E026}',
+        "KEY_SUBTRACT" => '\u{E027}',
+        "KEY_DECIMAL" => '\u{E028}',
+        "KEY_DIVIDE" => '\u{E029}',
+        "KEY_F1" => '\u{E031}',
+        "KEY_F2" => '\u{E032}',
+        "KEY_F3" => '\u{E033}',
+        "KEY_F4" => '\u{E034}',
+        "KEY_F5" => '\u{E035}',
+        "KEY_F6" => '\u{E036}',
+        "KEY_F7" => '\u{E037}',
+        "KEY_F8" => '\u{E038}',
+        "KEY_F9" => '\u{E039}',
+        "KEY_F10" => '\u{E03A}',
+        "KEY_F11" => '\u{E03B}',
+        "KEY_F12" => '\u{E03C}',
+        "KEY_META" | "KEY_COMMAND" => '\u{E03D}',
+        _ => return None,
+    };
+
+    Some(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_codes() {
+        assert_eq!(key_code("KEY_ENTER"), Some('\u{E007}'));
+        assert_eq!(key_code("KEY_TAB"), Some('\u{E004}'));
+        assert_eq!(key_code("KEY_LEFT"), Some('\u{E012}'));
+        assert_eq!(key_code("KEY_CTRL"), key_code("KEY_CONTROL"));
+        assert_eq!(key_code("KEY_F12"), Some('\u{E03C}'));
+        assert_eq!(key_code("KEY_UNKNOWN"), None);
+        assert_eq!(key_code("ENTER"), None);
+    }
+}
@@ -32,41 +32,38 @@
 //!
 //! [`Selenium IDE`]: https://www.selenium.dev/selenium-ide/
 
+#[cfg(any(test, feature = "cassette"))]
+pub mod cassette;
 mod command;
 mod error;
+pub mod healing;
 mod js_lib;
+pub mod junit;
+mod keys;
+mod listener;
 mod parser;
 mod playground;
 mod playground_test;
 mod runner;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 mod validation;
-mod webdriver;
+pub mod webdriver;
+mod writer;
 
-pub use error::{ParseError, RunnerError};
-pub use parser::{parse, Command, File, Test};
+pub use error::{
+    CommandField, FailedCommand, ParseError, ParseErrorKind, RunnerError, RunnerErrorKind,
+};
+pub use listener::{CommandEvent, RunListener};
+pub use parser::{parse, parse_all, Command, File, Location, NewWindow, Suite, Target, Test};
+pub use writer::write;
 
-/// Runner responsible for running a [`Test`](./struct.Test.html)
-/// and collecting data.
-#[cfg(feature = "fantoccini_backend")]
-pub type Runner = runner::Runner<webdriver::fantoccini::Client>;
-
-/// Runner responsible for running a [`Test`](./struct.Test.html)
-/// and collecting data.
-#[cfg(feature = "thirtyfour_backend")]
-pub type Runner<'a> = runner::Runner<webdriver::thirtyfour::Client<'a>>;
+pub use runner::Runner;
 
+/// A [`Runner`] which uses [`thirtyfour`](https://github.com/stevepryde/thirtyfour) backend.
 #[cfg(feature = "thirtyfour_backend")]
-impl<'a> Runner<'a> {
-    /// Create a new runner
-    pub fn new(client: &'a thirtyfour::WebDriver) -> Runner<'a> {
-        Self::_new(webdriver::thirtyfour::Client(client))
-    }
-}
+pub type ThirtyfourRunner<'a> = Runner<webdriver::thirtyfour::Client<'a>>;
 
+/// A [`Runner`] which uses [`fantoccini`](https://github.com/jonhoo/fantoccini) backend.
 #[cfg(feature = "fantoccini_backend")]
-impl Runner {
-    /// Create a new runner
-    pub fn new(client: fantoccini::Client) -> Runner {
-        Self::_new(webdriver::fantoccini::Client(client))
-    }
-}
+pub type FantocciniRunner = Runner<webdriver::fantoccini::Client>;
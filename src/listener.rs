@@ -0,0 +1,83 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::{error::RunnerErrorKind, Command, RunnerError, Target, Test};
+use serde_json::Value;
+use std::time::Duration;
+
+/// RunListener observes a run of tests.
+///
+/// It's notified about tests and commands being run, stored variables and errors,
+/// so it can be used for logging, live dashboards or custom reporters.
+/// All methods do nothing by default.
+///
+/// A listener is added to a runner by [`Runner::add_listener`].
+///
+/// [`Runner::add_listener`]: crate::Runner::add_listener
+pub trait RunListener {
+    /// Called before a test is run.
+    ///
+    /// It's called for tests which are run by `run` command as well.
+    fn test_started(&mut self, _test: &Test) {}
+
+    /// Called after a test is run.
+    fn test_finished(
+        &mut self,
+        _test: &Test,
+        _duration: Duration,
+        _result: Result<(), &RunnerError>,
+    ) {
+    }
+
+    /// Called before a command is run.
+    ///
+    /// Conditions of control flow commands (`if`, `while`, `forEach` etc.) are not reported.
+    fn command_started(&mut self, _event: &CommandEvent<'_>) {}
+
+    /// Called after a command is run.
+    fn command_finished(
+        &mut self,
+        _event: &CommandEvent<'_>,
+        _duration: Duration,
+        _result: Result<(), &RunnerErrorKind>,
+    ) {
+    }
+
+    /// Called when an element can't be found by a command's target
+    /// but one of the alternative [`Command::targets`] matches it.
+    ///
+    /// The command is rerun with the alternative afterwards.
+    fn target_fallback(&mut self, _event: &CommandEvent<'_>, _target: &Target) {}
+
+    /// Called when a variable is stored, either by a command or by [`Runner::save_value`].
+    ///
+    /// [`Runner::save_value`]: crate::Runner::save_value
+    fn variable_stored(&mut self, _name: &str, _value: &Value) {}
+
+    /// Called before each pass over a body of `while`, `forEach` and `times` loops
+    /// and before each repetition of a `do` block.
+    ///
+    /// Iterations are counted from 1.
+    fn loop_iteration(&mut self, _event: &CommandEvent<'_>, _iteration: usize) {}
+
+    /// Called when a test fails or a `verify*` command fails.
+    fn error(&mut self, _error: &RunnerError) {}
+}
+
+/// A command which is being run.
+#[derive(Debug, Clone)]
+pub struct CommandEvent<'a> {
+    /// A test the command belongs to.
+    pub test: &'a Test,
+    /// The command. Its id can be used to match it with Selenium IDE.
+    pub command: &'a Command,
+    /// An index of the command in the test.
+    pub index: usize,
+    /// A name of the command as it's written in Selenium IDE, e.g. `assertText`.
+    pub name: String,
+    /// A target with variables substituted.
+    pub target: String,
+    /// A value with variables substituted.
+    pub value: String,
+}
@@ -2,29 +2,71 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use crate::error::ParseError;
+use crate::error::{CommandField, ParseError, ParseErrorKind};
 use std::collections::HashMap;
 use std::result::Result;
 use std::time::Duration;
 
+/// A time Selenium IDE waits for a new window by default.
+pub(crate) const DEFAULT_WINDOW_TIMEOUT: Duration = Duration::from_millis(2000);
+
 /// Parse [.side format] into rust representation
 ///
 /// [.side format]: https://github.com/SeleniumHQ/selenium-ide/issues/77
 pub fn parse<R: std::io::Read>(side_file: R) -> Result<File, ParseError> {
-    let side: format::SideFile =
-        serde_json::from_reader(side_file).map_err(ParseError::FormatError)?;
+    parse_all(side_file).map_err(|mut errors| errors.remove(0))
+}
+
+/// Parse [.side format] like [`parse`] but doesn't stop on the first error.
+///
+/// It returns all errors found in a file in the order of tests and their commands,
+/// errors of suites go last, so a file can be fixed at once.
+///
+/// [.side format]: https://github.com/SeleniumHQ/selenium-ide/issues/77
+pub fn parse_all<R: std::io::Read>(side_file: R) -> Result<File, Vec<ParseError>> {
+    let side: format::SideFile = serde_json::from_reader(side_file)
+        .map_err(|err| vec![ParseErrorKind::FormatError(err).into()])?;
 
-    file_has_uniq_tests(&side)?;
+    let mut suite_errors = Vec::new();
+    suites_has_known_tests(&side, &mut suite_errors);
 
+    let mut errors = Vec::new();
+    let mut seen = HashMap::new();
     let mut tests = Vec::new();
-    for test in side.tests {
+    for (i, test) in side.tests.into_iter().enumerate() {
+        if let Some(index) = seen.insert(test.name.clone(), i) {
+            let mut err = ParseError::new(ParseErrorKind::NotUniqTestName(index, i));
+            err.test = Some(test.name.clone());
+            errors.push(err);
+        }
+
         let mut commands = Vec::with_capacity(test.commands.len());
-        for command in test.commands {
-            let cmd = parse_cmd(&command)?;
+        for (index, command) in test.commands.into_iter().enumerate() {
+            let cmd = match parse_cmd(&command) {
+                Ok(cmd) => cmd,
+                Err(mut err) => {
+                    err.test = Some(test.name.clone());
+                    err.index = Some(index);
+                    err.id = Some(command.id);
+                    errors.push(err);
+                    continue;
+                }
+            };
+            let opens_window = if command.opens_window {
+                Some(NewWindow {
+                    handle_name: command.window_handle_name,
+                    timeout: command.window_timeout.map(Duration::from_millis),
+                })
+            } else {
+                None
+            };
+
             commands.push(Command {
                 comment: command.comment,
                 id: command.id,
                 cmd,
+                targets: parse_targets(&command.targets),
+                opens_window,
             });
         }
 
@@ -35,25 +77,50 @@ pub fn parse<R: std::io::Read>(side_file: R) -> Result<File, ParseError> {
         });
     }
 
+    let suites = side
+        .suites
+        .into_iter()
+        .map(|suite| Suite {
+            id: suite.id,
+            name: suite.name,
+            persist_session: suite.persist_session,
+            parallel: suite.parallel,
+            timeout: Duration::from_secs(suite.timeout),
+            tests: suite.tests,
+        })
+        .collect();
+
+    errors.extend(suite_errors);
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
     Ok(File {
         id: side.id,
         name: side.name,
         url: side.url,
         version: side.version,
         tests,
+        suites,
+        urls: side.urls,
+        plugins: side.plugins,
     })
 }
 
-fn file_has_uniq_tests(file: &format::SideFile) -> Result<(), ParseError> {
-    let mut seen = HashMap::new();
-    for (i, test) in file.tests.iter().enumerate() {
-        let old = seen.insert(test.name.clone(), i);
-        if let Some(index) = old {
-            return Err(ParseError::NotUniqTestName(index, i));
+fn suites_has_known_tests(file: &format::SideFile, errors: &mut Vec<ParseError>) {
+    for suite in &file.suites {
+        for id in &suite.tests {
+            if !file.tests.iter().any(|test| &test.id == id) {
+                errors.push(
+                    ParseErrorKind::ValidationError(format!(
+                        "Suite {:?} refers to unknown test {:?}",
+                        suite.name, id
+                    ))
+                    .into(),
+                );
+            }
         }
     }
-
-    Ok(())
 }
 
 fn parse_cmd(command: &format::Command) -> Result<Cmd, ParseError> {
@@ -127,6 +194,40 @@ fn parse_cmd(command: &format::Command) -> Result<Cmd, ParseError> {
         "assertNotEditable" => Cmd::parse_assert_element_not_editable,
         "clickAt" => Cmd::parse_click_at,
         "doubleClickAt" => Cmd::parse_double_click_at,
+        "mouseOver" => Cmd::parse_mouse_over,
+        "mouseOut" => Cmd::parse_mouse_out,
+        "mouseMoveAt" => Cmd::parse_mouse_move_at,
+        "mouseDownAt" => Cmd::parse_mouse_down_at,
+        "mouseUpAt" => Cmd::parse_mouse_up_at,
+        "dragAndDropToObject" => Cmd::parse_drag_and_drop_to_object,
+        "submit" => Cmd::parse_submit,
+        "webdriverAnswerOnVisiblePrompt" => Cmd::parse_webdriver_answer_on_visible_prompt,
+        "webdriverChooseOkOnVisibleConfirmation" => {
+            Cmd::parse_webdriver_choose_ok_on_visible_confirmation
+        }
+        "webdriverChooseCancelOnVisibleConfirmation" => {
+            Cmd::parse_webdriver_choose_cancel_on_visible_confirmation
+        }
+        "webdriverChooseCancelOnVisiblePrompt" => {
+            Cmd::parse_webdriver_choose_cancel_on_visible_prompt
+        }
+        "selectFrame" => Cmd::parse_select_frame,
+        "selectWindow" => Cmd::parse_select_window,
+        "storeWindowHandle" => Cmd::parse_store_window_handle,
+        "verify" => Cmd::parse_verify,
+        "verifyChecked" => Cmd::parse_verify_checked,
+        "verifyNotChecked" => Cmd::parse_verify_not_checked,
+        "verifyEditable" => Cmd::parse_verify_editable,
+        "verifyNotEditable" => Cmd::parse_verify_not_editable,
+        "verifyElementPresent" => Cmd::parse_verify_element_present,
+        "verifyElementNotPresent" => Cmd::parse_verify_element_not_present,
+        "verifySelectedLabel" => Cmd::parse_verify_selected_label,
+        "verifySelectedValue" => Cmd::parse_verify_selected_value,
+        "verifyNotSelectedValue" => Cmd::parse_verify_not_selected_value,
+        "verifyText" => Cmd::parse_verify_text,
+        "verifyNotText" => Cmd::parse_verify_not_text,
+        "verifyTitle" => Cmd::parse_verify_title,
+        "verifyValue" => Cmd::parse_verify_value,
         cmd if cmd.is_empty() || cmd.starts_with("//") => {
             // We create an empty command to not lose an order of commands.
             // It's usefull for error messages to not break the indexes of commands from a file.
@@ -139,10 +240,10 @@ fn parse_cmd(command: &format::Command) -> Result<Cmd, ParseError> {
             Cmd::parse_custom_cmd
         }
         cmd => {
-            return Err(ParseError::ValidationError(format!(
-                "Command {:?} is not implemented",
-                cmd
-            )))
+            return Err(ParseError::in_field(
+                ParseErrorKind::ValidationError(format!("Command {:?} is not implemented", cmd)),
+                CommandField::Command,
+            ))
         }
     };
 
@@ -152,7 +253,7 @@ fn parse_cmd(command: &format::Command) -> Result<Cmd, ParseError> {
 /// File represent a [`Side` file] information
 ///
 /// [`Side` file]: https://github.com/SeleniumHQ/selenium-ide/issues/77
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct File {
     /// Id of a file.
     /// It is generated by Selenium IDE automatically.
@@ -167,22 +268,63 @@ pub struct File {
     ///
     /// [`Test`]: struct.Test.html
     pub tests: Vec<Test>,
+    /// A list of [`Suite`]s
+    ///
+    /// [`Suite`]: struct.Suite.html
+    pub suites: Vec<Suite>,
+    /// Addresses of sites which were used in a project.
+    pub urls: Vec<String>,
+    /// Plugins of Selenium IDE which a project relies on.
+    ///
+    /// They're not used by a runner but kept to be written back.
+    pub plugins: Vec<serde_json::Value>,
 }
 
 impl File {
     pub fn new(id: String, name: String, url: String, version: String, tests: Vec<Test>) -> Self {
+        let urls = if url.is_empty() {
+            Vec::new()
+        } else {
+            vec![url.clone()]
+        };
+
         Self {
             id,
             version,
             name,
             url,
             tests,
+            suites: Vec::new(),
+            urls,
+            plugins: Vec::new(),
         }
     }
 }
 
+/// The structure represent a selenium suite
+#[derive(Clone, Debug, PartialEq)]
+pub struct Suite {
+    /// Id of a suite.
+    /// Generated automatically by Selenium IDE.
+    pub id: String,
+    /// Name of the suite
+    pub name: String,
+    /// Keep a webdriver session between tests.
+    ///
+    /// If it's not set the browser state and variables are reset before each test.
+    pub persist_session: bool,
+    /// Whether Selenium IDE would run tests in parallel.
+    ///
+    /// Runner doesn't support parallel execution so tests are run sequentially anyway.
+    pub parallel: bool,
+    /// A time limit for each test in the suite
+    pub timeout: Duration,
+    /// Ids of tests in the order they must be run
+    pub tests: Vec<String>,
+}
+
 /// The structure represent a selenium test
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Test {
     /// Id of a test.
     /// Generated automatically by Selenium IDE.
@@ -202,6 +344,12 @@ pub struct Command {
     pub comment: String,
     /// Particualar command for run.
     pub cmd: Cmd,
+    /// Alternative targets recorded by Selenium IDE.
+    ///
+    /// They're tried in order when an element can't be found by the command's target.
+    pub targets: Vec<Target>,
+    /// A window which is expected to be opened by the command.
+    pub opens_window: Option<NewWindow>,
 }
 
 impl Command {
@@ -210,19 +358,32 @@ impl Command {
             id: id.as_ref().to_owned(),
             comment: comment.as_ref().to_owned(),
             cmd,
+            targets: Vec::new(),
+            opens_window: None,
         }
     }
 }
 
+/// NewWindow describes a window which a command opens e.g. by clicking on a link.
+///
+/// A handle of the window is saved in a variable so it can be selected later on.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NewWindow {
+    /// A name of variable for the window handle
+    pub handle_name: String,
+    /// A time to wait for the window to be opened,
+    /// Selenium IDE's default of 2 seconds is used if it's not set
+    pub timeout: Option<Duration>,
+}
+
 /// Command corresponds a selenium command
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Cmd {
     Open(String),
     Echo(String),
     Click(Target),
-    Pause(Duration),
-    SetWindowSize(u32, u32),
-    // todo: targets?
+    Pause(Param<Duration>),
+    SetWindowSize(Param<(u32, u32)>),
     Select {
         target: Target,
         locator: SelectLocator,
@@ -231,32 +392,31 @@ pub enum Cmd {
     RemoveSelection(Target, String),
     WaitForElementVisible {
         target: Target,
-        timeout: Duration,
+        timeout: Param<Duration>,
     },
     WaitForElementNotVisible {
         target: Target,
-        timeout: Duration,
+        timeout: Param<Duration>,
     },
     WaitForElementEditable {
         target: Target,
-        timeout: Duration,
+        timeout: Param<Duration>,
     },
     WaitForElementNotEditable {
         target: Target,
-        timeout: Duration,
+        timeout: Param<Duration>,
     },
     WaitForElementPresent {
         target: Target,
-        timeout: Duration,
+        timeout: Param<Duration>,
     },
     WaitForElementNotPresent {
         target: Target,
-        timeout: Duration,
+        timeout: Param<Duration>,
     },
     StoreText {
         var: String,
         target: Target,
-        targets: Vec<Target>,
     },
     Store {
         var: String,
@@ -289,7 +449,6 @@ pub enum Cmd {
     Custom {
         cmd: String,
         target: String,
-        targets: Vec<Target>,
         value: String,
     },
     Assert {
@@ -324,7 +483,7 @@ pub enum Cmd {
     AssertValue(Target, String),
     AssertConfirmation(String),
     AssertSelectedLabel(Target, String),
-    Times(String),
+    Times(Param<u64>),
     RunTest(String),
     StoreJson(String, String),
     StoreValue(Target, String),
@@ -333,8 +492,39 @@ pub enum Cmd {
     AssertElementNotPresent(Target),
     AssertEditable(Target),
     AssertNotEditable(Target),
-    ClickAt(Target, (i32, i32)),
-    DoubleClickAt(Target, (i32, i32)),
+    ClickAt(Target, Param<(i32, i32)>),
+    DoubleClickAt(Target, Param<(i32, i32)>),
+    MouseOver(Target),
+    MouseOut(Target),
+    MouseMoveAt(Target, Param<(i32, i32)>),
+    MouseDownAt(Target, Param<(i32, i32)>),
+    MouseUpAt(Target, Param<(i32, i32)>),
+    DragAndDropToObject(Target, Target),
+    Submit(Target),
+    WebdriverAnswerOnVisiblePrompt(String),
+    WebdriverChooseOkOnVisibleConfirmation,
+    WebdriverChooseCancelOnVisibleConfirmation,
+    WebdriverChooseCancelOnVisiblePrompt,
+    SelectFrame(FrameLocator),
+    SelectWindow(WindowLocator),
+    StoreWindowHandle(String),
+    Verify {
+        var: String,
+        value: String,
+    },
+    VerifyChecked(Target),
+    VerifyNotChecked(Target),
+    VerifyEditable(Target),
+    VerifyNotEditable(Target),
+    VerifyElementPresent(Target),
+    VerifyElementNotPresent(Target),
+    VerifySelectedLabel(Target, String),
+    VerifySelectedValue(Target, String),
+    VerifyNotSelectedValue(Target, String),
+    VerifyText(Target, String),
+    VerifyNotText(Target, String),
+    VerifyTitle(String),
+    VerifyValue(Target, String),
 }
 
 impl Cmd {
@@ -343,16 +533,11 @@ impl Cmd {
     }
 
     fn parse_store_text(c: &format::Command) -> Result<Self, ParseError> {
-        let targets = parse_targets(&c.targets)?;
         let var = c.value.clone();
-        let location = parse_location(&c.target)?;
+        let location = parse_target_location(&c.target).map_err(in_target)?;
         let target = Target::new(location);
 
-        Ok(Self::StoreText {
-            var,
-            target,
-            targets,
-        })
+        Ok(Self::StoreText { var, target })
     }
 
     fn parse_execute_script(c: &format::Command) -> Result<Self, ParseError> {
@@ -378,70 +563,70 @@ impl Cmd {
     }
 
     fn parse_wait_for_visible(c: &format::Command) -> Result<Self, ParseError> {
-        let location = parse_location(&c.target)?;
+        let location = parse_target_location(&c.target).map_err(in_target)?;
         let target = Target::new(location);
-        let timeout = cast_timeout(&c.value)?;
+        let timeout = Param::parse(&c.value).map_err(in_value)?;
 
         Ok(Self::WaitForElementVisible { target, timeout })
     }
 
     fn parse_wait_for_not_visible(c: &format::Command) -> Result<Self, ParseError> {
-        let location = parse_location(&c.target)?;
+        let location = parse_target_location(&c.target).map_err(in_target)?;
         let target = Target::new(location);
-        let timeout = cast_timeout(&c.value)?;
+        let timeout = Param::parse(&c.value).map_err(in_value)?;
 
         Ok(Self::WaitForElementNotVisible { target, timeout })
     }
 
     fn parse_wait_for_editable(c: &format::Command) -> Result<Self, ParseError> {
-        let location = parse_location(&c.target)?;
+        let location = parse_target_location(&c.target).map_err(in_target)?;
         let target = Target::new(location);
-        let timeout = cast_timeout(&c.value)?;
+        let timeout = Param::parse(&c.value).map_err(in_value)?;
 
         Ok(Self::WaitForElementEditable { target, timeout })
     }
 
     fn parse_wait_for_not_editable(c: &format::Command) -> Result<Self, ParseError> {
-        let location = parse_location(&c.target)?;
+        let location = parse_target_location(&c.target).map_err(in_target)?;
         let target = Target::new(location);
-        let timeout = cast_timeout(&c.value)?;
+        let timeout = Param::parse(&c.value).map_err(in_value)?;
 
         Ok(Self::WaitForElementNotEditable { target, timeout })
     }
 
     fn parse_wait_for_not_present(c: &format::Command) -> Result<Self, ParseError> {
-        let location = parse_location(&c.target)?;
+        let location = parse_target_location(&c.target).map_err(in_target)?;
         let target = Target::new(location);
-        let timeout = cast_timeout(&c.value)?;
+        let timeout = Param::parse(&c.value).map_err(in_value)?;
 
         Ok(Self::WaitForElementNotPresent { target, timeout })
     }
 
     fn parse_wait_for_present(c: &format::Command) -> Result<Self, ParseError> {
-        let location = parse_location(&c.target)?;
+        let location = parse_target_location(&c.target).map_err(in_target)?;
         let target = Target::new(location);
-        let timeout = cast_timeout(&c.value)?;
+        let timeout = Param::parse(&c.value).map_err(in_value)?;
 
         Ok(Self::WaitForElementPresent { target, timeout })
     }
 
     fn parse_select(c: &format::Command) -> Result<Self, ParseError> {
-        let locator = parse_select_locator(&c.value)?;
-        let location = parse_location(&c.target)?;
+        let locator = parse_select_locator(&c.value).map_err(in_value)?;
+        let location = parse_target_location(&c.target).map_err(in_target)?;
         let target = Target::new(location);
 
         Ok(Self::Select { target, locator })
     }
 
     fn parse_add_selection(c: &format::Command) -> Result<Self, ParseError> {
-        let location = parse_location(&c.target)?;
+        let location = parse_target_location(&c.target).map_err(in_target)?;
         let target = Target::new(location);
 
         Ok(Self::AddSelection(target, c.value.clone()))
     }
 
     fn parse_remove_selection(c: &format::Command) -> Result<Self, ParseError> {
-        let location = parse_location(&c.target)?;
+        let location = parse_target_location(&c.target).map_err(in_target)?;
         let target = Target::new(location);
 
         Ok(Self::RemoveSelection(target, c.value.clone()))
@@ -472,12 +657,12 @@ impl Cmd {
     }
 
     fn parse_pause(c: &format::Command) -> Result<Self, ParseError> {
-        let timeout = cast_timeout(&c.target)?;
+        let timeout = Param::parse(&c.target).map_err(in_target)?;
         Ok(Self::Pause(timeout))
     }
 
     fn parse_click(c: &format::Command) -> Result<Self, ParseError> {
-        let location = parse_location(&c.target)?;
+        let location = parse_target_location(&c.target).map_err(in_target)?;
         let target = Target::new(location);
         Ok(Self::Click(target))
     }
@@ -490,22 +675,8 @@ impl Cmd {
     }
 
     fn parse_set_window_size(c: &format::Command) -> Result<Self, ParseError> {
-        let settings = c.target.split('x').map(|n| n.parse()).collect::<Vec<_>>();
-        if settings.len() != 2 {
-            return Err(ParseError::TypeError(
-                "window size expected to get in a form like this 1916x1034 (Width x Height)"
-                    .to_owned(),
-            ));
-        }
-
-        let w = settings[0]
-            .clone()
-            .map_err(|_| ParseError::TypeError("expected to get int".to_owned()))?;
-        let h = settings[1]
-            .clone()
-            .map_err(|_| ParseError::TypeError("expected to get int".to_owned()))?;
-
-        Ok(Self::SetWindowSize(w, h))
+        let size = Param::parse(&c.target).map_err(in_target)?;
+        Ok(Self::SetWindowSize(size))
     }
 
     fn parse_do(_: &format::Command) -> Result<Self, ParseError> {
@@ -527,12 +698,10 @@ impl Cmd {
     }
 
     fn parse_custom_cmd(cmd: &format::Command) -> Result<Self, ParseError> {
-        let targets = parse_targets(&cmd.targets)?;
         Ok(Self::Custom {
             cmd: cmd.cmd.clone(),
             target: cmd.target.clone(),
             value: cmd.value.clone(),
-            targets,
         })
     }
 
@@ -541,7 +710,6 @@ impl Cmd {
             cmd: String::default(),
             target: String::default(),
             value: String::default(),
-            targets: Vec::default(),
         }
     }
 
@@ -551,12 +719,12 @@ impl Cmd {
         } else {
             Some(c.value.clone())
         };
-        let location = parse_location(&c.target)?;
+        let location = parse_location(&c.target).map_err(in_target)?;
         match location {
             Location::XPath(xpath) => Ok(Self::StoreXpathCount { var, xpath }),
-            _ => Err(ParseError::LocatorFormatError(
+            _ => Err(in_target(ParseErrorKind::LocatorFormatError(
                 "expected to get an xpath locator".to_owned(),
-            )),
+            ))),
         }
     }
 
@@ -589,85 +757,85 @@ impl Cmd {
     }
 
     fn parse_assert_checked(c: &format::Command) -> Result<Self, ParseError> {
-        let location = parse_location(&c.target)?;
+        let location = parse_target_location(&c.target).map_err(in_target)?;
         let target = Target::new(location);
         Ok(Self::AssertChecked(target))
     }
 
     fn parse_assert_not_checked(c: &format::Command) -> Result<Self, ParseError> {
-        let location = parse_location(&c.target)?;
+        let location = parse_target_location(&c.target).map_err(in_target)?;
         let target = Target::new(location);
         Ok(Self::AssertNotChecked(target))
     }
 
     fn parse_assert_selected_value(c: &format::Command) -> Result<Self, ParseError> {
-        let location = parse_location(&c.target)?;
+        let location = parse_target_location(&c.target).map_err(in_target)?;
         let target = Target::new(location);
         Ok(Self::AssertSelectedValue(target, c.value.clone()))
     }
 
     fn parse_assert_not_selected_value(c: &format::Command) -> Result<Self, ParseError> {
-        let location = parse_location(&c.target)?;
+        let location = parse_target_location(&c.target).map_err(in_target)?;
         let target = Target::new(location);
         Ok(Self::AssertNotSelectedValue(target, c.value.clone()))
     }
 
     fn parse_assert_text(c: &format::Command) -> Result<Self, ParseError> {
-        let location = parse_location(&c.target)?;
+        let location = parse_target_location(&c.target).map_err(in_target)?;
         let target = Target::new(location);
         Ok(Self::AssertText(target, c.value.clone()))
     }
 
     fn parse_assert_not_text(c: &format::Command) -> Result<Self, ParseError> {
-        let location = parse_location(&c.target)?;
+        let location = parse_target_location(&c.target).map_err(in_target)?;
         let target = Target::new(location);
         Ok(Self::AssertNotText(target, c.value.clone()))
     }
 
     fn parse_double_click(c: &format::Command) -> Result<Self, ParseError> {
-        let location = parse_location(&c.target)?;
+        let location = parse_target_location(&c.target).map_err(in_target)?;
         let target = Target::new(location);
         Ok(Self::DoubleClick(target))
     }
 
     fn parse_edit_content(c: &format::Command) -> Result<Self, ParseError> {
-        let location = parse_location(&c.target)?;
+        let location = parse_target_location(&c.target).map_err(in_target)?;
         let target = Target::new(location);
         Ok(Self::EditContent(target, c.value.clone()))
     }
 
     fn parse_send_keys(c: &format::Command) -> Result<Self, ParseError> {
-        let location = parse_location(&c.target)?;
+        let location = parse_target_location(&c.target).map_err(in_target)?;
         let target = Target::new(location);
         Ok(Self::SendKeys(target, c.value.clone()))
     }
 
     fn parse_type(c: &format::Command) -> Result<Self, ParseError> {
-        let location = parse_location(&c.target)?;
+        let location = parse_target_location(&c.target).map_err(in_target)?;
         let target = Target::new(location);
         Ok(Self::Type(target, c.value.clone()))
     }
 
     fn parse_check(c: &format::Command) -> Result<Self, ParseError> {
-        let location = parse_location(&c.target)?;
+        let location = parse_target_location(&c.target).map_err(in_target)?;
         let target = Target::new(location);
         Ok(Self::Check(target))
     }
 
     fn parse_uncheck(c: &format::Command) -> Result<Self, ParseError> {
-        let location = parse_location(&c.target)?;
+        let location = parse_target_location(&c.target).map_err(in_target)?;
         let target = Target::new(location);
         Ok(Self::UnCheck(target))
     }
 
     fn parse_mouse_down(c: &format::Command) -> Result<Self, ParseError> {
-        let location = parse_location(&c.target)?;
+        let location = parse_target_location(&c.target).map_err(in_target)?;
         let target = Target::new(location);
         Ok(Self::MouseDown(target))
     }
 
     fn parse_mouse_up(c: &format::Command) -> Result<Self, ParseError> {
-        let location = parse_location(&c.target)?;
+        let location = parse_target_location(&c.target).map_err(in_target)?;
         let target = Target::new(location);
         Ok(Self::MouseUp(target))
     }
@@ -693,7 +861,7 @@ impl Cmd {
     }
 
     fn parse_assert_value(c: &format::Command) -> Result<Self, ParseError> {
-        let location = parse_location(&c.target)?;
+        let location = parse_target_location(&c.target).map_err(in_target)?;
         let target = Target::new(location);
         Ok(Self::AssertValue(target, c.value.clone()))
     }
@@ -704,13 +872,14 @@ impl Cmd {
     }
 
     fn parse_assert_selected_label(c: &format::Command) -> Result<Self, ParseError> {
-        let location = parse_location(&c.target)?;
+        let location = parse_target_location(&c.target).map_err(in_target)?;
         let target = Target::new(location);
         Ok(Self::AssertSelectedLabel(target, c.value.clone()))
     }
 
     fn parse_times(c: &format::Command) -> Result<Self, ParseError> {
-        Ok(Self::Times(c.target.clone()))
+        let times = Param::parse(&c.target).map_err(in_target)?;
+        Ok(Self::Times(times))
     }
 
     fn parse_run(c: &format::Command) -> Result<Self, ParseError> {
@@ -718,7 +887,7 @@ impl Cmd {
     }
 
     fn parse_store_value(c: &format::Command) -> Result<Self, ParseError> {
-        let location = parse_location(&c.target)?;
+        let location = parse_target_location(&c.target).map_err(in_target)?;
         let target = Target::new(location);
         Ok(Self::StoreValue(target, c.value.clone()))
     }
@@ -729,10 +898,12 @@ impl Cmd {
 
     fn parse_store_attribute(c: &format::Command) -> Result<Self, ParseError> {
         let (target, attribute) = c.target.split_once('@').ok_or_else(|| {
-            ParseError::LocatorFormatError("a attribute splitter '@' wasn't found".to_string())
+            in_target(ParseErrorKind::LocatorFormatError(
+                "a attribute splitter '@' wasn't found".to_string(),
+            ))
         })?;
 
-        let location = parse_location(target)?;
+        let location = parse_target_location(target).map_err(in_target)?;
         let target = Target::new(location);
         Ok(Self::StoreAttribute(
             target,
@@ -742,45 +913,370 @@ impl Cmd {
     }
 
     fn parse_assert_element_present(c: &format::Command) -> Result<Self, ParseError> {
-        let location = parse_location(&c.target)?;
+        let location = parse_target_location(&c.target).map_err(in_target)?;
         let target = Target::new(location);
         Ok(Self::AssertElementPresent(target))
     }
 
     fn parse_assert_element_not_present(c: &format::Command) -> Result<Self, ParseError> {
-        let location = parse_location(&c.target)?;
+        let location = parse_target_location(&c.target).map_err(in_target)?;
         let target = Target::new(location);
         Ok(Self::AssertElementNotPresent(target))
     }
 
     fn parse_assert_element_editable(c: &format::Command) -> Result<Self, ParseError> {
-        let location = parse_location(&c.target)?;
+        let location = parse_target_location(&c.target).map_err(in_target)?;
         let target = Target::new(location);
         Ok(Self::AssertEditable(target))
     }
 
     fn parse_assert_element_not_editable(c: &format::Command) -> Result<Self, ParseError> {
-        let location = parse_location(&c.target)?;
+        let location = parse_target_location(&c.target).map_err(in_target)?;
         let target = Target::new(location);
         Ok(Self::AssertNotEditable(target))
     }
 
     fn parse_click_at(c: &format::Command) -> Result<Self, ParseError> {
-        let location = parse_location(&c.target)?;
+        let location = parse_target_location(&c.target).map_err(in_target)?;
         let target = Target::new(location);
-        let coords = parse_coordinates(&c.value)?;
+        let coords = Param::parse(&c.value).map_err(in_value)?;
         Ok(Self::ClickAt(target, coords))
     }
 
     fn parse_double_click_at(c: &format::Command) -> Result<Self, ParseError> {
-        let location = parse_location(&c.target)?;
+        let location = parse_target_location(&c.target).map_err(in_target)?;
         let target = Target::new(location);
-        let coords = parse_coordinates(&c.value)?;
+        let coords = Param::parse(&c.value).map_err(in_value)?;
         Ok(Self::DoubleClickAt(target, coords))
     }
+
+    fn parse_mouse_over(c: &format::Command) -> Result<Self, ParseError> {
+        let location = parse_target_location(&c.target).map_err(in_target)?;
+        let target = Target::new(location);
+        Ok(Self::MouseOver(target))
+    }
+
+    fn parse_mouse_out(c: &format::Command) -> Result<Self, ParseError> {
+        let location = parse_target_location(&c.target).map_err(in_target)?;
+        let target = Target::new(location);
+        Ok(Self::MouseOut(target))
+    }
+
+    fn parse_mouse_move_at(c: &format::Command) -> Result<Self, ParseError> {
+        let location = parse_target_location(&c.target).map_err(in_target)?;
+        let target = Target::new(location);
+        let coords = Param::parse(&c.value).map_err(in_value)?;
+        Ok(Self::MouseMoveAt(target, coords))
+    }
+
+    fn parse_mouse_down_at(c: &format::Command) -> Result<Self, ParseError> {
+        let location = parse_target_location(&c.target).map_err(in_target)?;
+        let target = Target::new(location);
+        let coords = Param::parse(&c.value).map_err(in_value)?;
+        Ok(Self::MouseDownAt(target, coords))
+    }
+
+    fn parse_mouse_up_at(c: &format::Command) -> Result<Self, ParseError> {
+        let location = parse_target_location(&c.target).map_err(in_target)?;
+        let target = Target::new(location);
+        let coords = Param::parse(&c.value).map_err(in_value)?;
+        Ok(Self::MouseUpAt(target, coords))
+    }
+
+    fn parse_drag_and_drop_to_object(c: &format::Command) -> Result<Self, ParseError> {
+        let source = Target::new(parse_target_location(&c.target).map_err(in_target)?);
+        let destination = Target::new(parse_target_location(&c.value).map_err(in_value)?);
+        Ok(Self::DragAndDropToObject(source, destination))
+    }
+
+    fn parse_submit(c: &format::Command) -> Result<Self, ParseError> {
+        let location = parse_target_location(&c.target).map_err(in_target)?;
+        let target = Target::new(location);
+        Ok(Self::Submit(target))
+    }
+
+    fn parse_webdriver_answer_on_visible_prompt(c: &format::Command) -> Result<Self, ParseError> {
+        let answer = c.target.clone();
+        Ok(Self::WebdriverAnswerOnVisiblePrompt(answer))
+    }
+
+    fn parse_webdriver_choose_ok_on_visible_confirmation(
+        _: &format::Command,
+    ) -> Result<Self, ParseError> {
+        Ok(Self::WebdriverChooseOkOnVisibleConfirmation)
+    }
+
+    fn parse_webdriver_choose_cancel_on_visible_confirmation(
+        _: &format::Command,
+    ) -> Result<Self, ParseError> {
+        Ok(Self::WebdriverChooseCancelOnVisibleConfirmation)
+    }
+
+    fn parse_webdriver_choose_cancel_on_visible_prompt(
+        _: &format::Command,
+    ) -> Result<Self, ParseError> {
+        Ok(Self::WebdriverChooseCancelOnVisiblePrompt)
+    }
+
+    fn parse_select_frame(c: &format::Command) -> Result<Self, ParseError> {
+        let locator = parse_frame_locator(&c.target).map_err(in_target)?;
+        Ok(Self::SelectFrame(locator))
+    }
+
+    fn parse_select_window(c: &format::Command) -> Result<Self, ParseError> {
+        let locator = parse_window_locator(&c.target).map_err(in_target)?;
+        Ok(Self::SelectWindow(locator))
+    }
+
+    fn parse_store_window_handle(c: &format::Command) -> Result<Self, ParseError> {
+        Ok(Self::StoreWindowHandle(c.target.clone()))
+    }
+
+    fn parse_verify(c: &format::Command) -> Result<Self, ParseError> {
+        let var = c.target.clone();
+        let value = c.value.clone();
+        Ok(Self::Verify { value, var })
+    }
+
+    fn parse_verify_checked(c: &format::Command) -> Result<Self, ParseError> {
+        let location = parse_target_location(&c.target).map_err(in_target)?;
+        let target = Target::new(location);
+        Ok(Self::VerifyChecked(target))
+    }
+
+    fn parse_verify_not_checked(c: &format::Command) -> Result<Self, ParseError> {
+        let location = parse_target_location(&c.target).map_err(in_target)?;
+        let target = Target::new(location);
+        Ok(Self::VerifyNotChecked(target))
+    }
+
+    fn parse_verify_editable(c: &format::Command) -> Result<Self, ParseError> {
+        let location = parse_target_location(&c.target).map_err(in_target)?;
+        let target = Target::new(location);
+        Ok(Self::VerifyEditable(target))
+    }
+
+    fn parse_verify_not_editable(c: &format::Command) -> Result<Self, ParseError> {
+        let location = parse_target_location(&c.target).map_err(in_target)?;
+        let target = Target::new(location);
+        Ok(Self::VerifyNotEditable(target))
+    }
+
+    fn parse_verify_element_present(c: &format::Command) -> Result<Self, ParseError> {
+        let location = parse_target_location(&c.target).map_err(in_target)?;
+        let target = Target::new(location);
+        Ok(Self::VerifyElementPresent(target))
+    }
+
+    fn parse_verify_element_not_present(c: &format::Command) -> Result<Self, ParseError> {
+        let location = parse_target_location(&c.target).map_err(in_target)?;
+        let target = Target::new(location);
+        Ok(Self::VerifyElementNotPresent(target))
+    }
+
+    fn parse_verify_selected_label(c: &format::Command) -> Result<Self, ParseError> {
+        let location = parse_target_location(&c.target).map_err(in_target)?;
+        let target = Target::new(location);
+        Ok(Self::VerifySelectedLabel(target, c.value.clone()))
+    }
+
+    fn parse_verify_selected_value(c: &format::Command) -> Result<Self, ParseError> {
+        let location = parse_target_location(&c.target).map_err(in_target)?;
+        let target = Target::new(location);
+        Ok(Self::VerifySelectedValue(target, c.value.clone()))
+    }
+
+    fn parse_verify_not_selected_value(c: &format::Command) -> Result<Self, ParseError> {
+        let location = parse_target_location(&c.target).map_err(in_target)?;
+        let target = Target::new(location);
+        Ok(Self::VerifyNotSelectedValue(target, c.value.clone()))
+    }
+
+    fn parse_verify_text(c: &format::Command) -> Result<Self, ParseError> {
+        let location = parse_target_location(&c.target).map_err(in_target)?;
+        let target = Target::new(location);
+        Ok(Self::VerifyText(target, c.value.clone()))
+    }
+
+    fn parse_verify_not_text(c: &format::Command) -> Result<Self, ParseError> {
+        let location = parse_target_location(&c.target).map_err(in_target)?;
+        let target = Target::new(location);
+        Ok(Self::VerifyNotText(target, c.value.clone()))
+    }
+
+    fn parse_verify_title(c: &format::Command) -> Result<Self, ParseError> {
+        Ok(Self::VerifyTitle(c.target.clone()))
+    }
+
+    fn parse_verify_value(c: &format::Command) -> Result<Self, ParseError> {
+        let location = parse_target_location(&c.target).map_err(in_target)?;
+        let target = Target::new(location);
+        Ok(Self::VerifyValue(target, c.value.clone()))
+    }
+
+    /// Target returns a locator of an element the command is addressed to.
+    ///
+    /// The second element of `dragAndDropToObject` isn't considered a target,
+    /// as Selenium IDE records alternatives only for the first one.
+    pub(crate) fn target(&self) -> Option<&Target> {
+        match self {
+            Self::Click(target)
+            | Self::Select { target, .. }
+            | Self::AddSelection(target, ..)
+            | Self::RemoveSelection(target, ..)
+            | Self::WaitForElementVisible { target, .. }
+            | Self::WaitForElementNotVisible { target, .. }
+            | Self::WaitForElementEditable { target, .. }
+            | Self::WaitForElementNotEditable { target, .. }
+            | Self::WaitForElementPresent { target, .. }
+            | Self::WaitForElementNotPresent { target, .. }
+            | Self::StoreText { target, .. }
+            | Self::AssertChecked(target)
+            | Self::AssertNotChecked(target)
+            | Self::AssertSelectedValue(target, ..)
+            | Self::AssertNotSelectedValue(target, ..)
+            | Self::AssertText(target, ..)
+            | Self::AssertNotText(target, ..)
+            | Self::DoubleClick(target)
+            | Self::EditContent(target, ..)
+            | Self::SendKeys(target, ..)
+            | Self::Type(target, ..)
+            | Self::Check(target)
+            | Self::UnCheck(target)
+            | Self::MouseUp(target)
+            | Self::MouseDown(target)
+            | Self::AssertValue(target, ..)
+            | Self::AssertSelectedLabel(target, ..)
+            | Self::StoreValue(target, ..)
+            | Self::StoreAttribute(target, ..)
+            | Self::AssertElementPresent(target)
+            | Self::AssertElementNotPresent(target)
+            | Self::AssertEditable(target)
+            | Self::AssertNotEditable(target)
+            | Self::ClickAt(target, ..)
+            | Self::DoubleClickAt(target, ..)
+            | Self::MouseOver(target)
+            | Self::MouseOut(target)
+            | Self::MouseMoveAt(target, ..)
+            | Self::MouseDownAt(target, ..)
+            | Self::MouseUpAt(target, ..)
+            | Self::DragAndDropToObject(target, ..)
+            | Self::Submit(target)
+            | Self::SelectFrame(FrameLocator::Element(target))
+            | Self::VerifyChecked(target)
+            | Self::VerifyNotChecked(target)
+            | Self::VerifyEditable(target)
+            | Self::VerifyNotEditable(target)
+            | Self::VerifyElementPresent(target)
+            | Self::VerifyElementNotPresent(target)
+            | Self::VerifySelectedLabel(target, ..)
+            | Self::VerifySelectedValue(target, ..)
+            | Self::VerifyNotSelectedValue(target, ..)
+            | Self::VerifyText(target, ..)
+            | Self::VerifyNotText(target, ..)
+            | Self::VerifyValue(target, ..) => Some(target),
+            _ => None,
+        }
+    }
+
+    /// Replaces a target of the command returning a command with the new one.
+    ///
+    /// A command which has no target is returned unchanged.
+    pub(crate) fn with_target(&self, new: Target) -> Self {
+        let mut cmd = self.clone();
+        match &mut cmd {
+            Self::Click(target)
+            | Self::Select { target, .. }
+            | Self::AddSelection(target, ..)
+            | Self::RemoveSelection(target, ..)
+            | Self::WaitForElementVisible { target, .. }
+            | Self::WaitForElementNotVisible { target, .. }
+            | Self::WaitForElementEditable { target, .. }
+            | Self::WaitForElementNotEditable { target, .. }
+            | Self::WaitForElementPresent { target, .. }
+            | Self::WaitForElementNotPresent { target, .. }
+            | Self::StoreText { target, .. }
+            | Self::AssertChecked(target)
+            | Self::AssertNotChecked(target)
+            | Self::AssertSelectedValue(target, ..)
+            | Self::AssertNotSelectedValue(target, ..)
+            | Self::AssertText(target, ..)
+            | Self::AssertNotText(target, ..)
+            | Self::DoubleClick(target)
+            | Self::EditContent(target, ..)
+            | Self::SendKeys(target, ..)
+            | Self::Type(target, ..)
+            | Self::Check(target)
+            | Self::UnCheck(target)
+            | Self::MouseUp(target)
+            | Self::MouseDown(target)
+            | Self::AssertValue(target, ..)
+            | Self::AssertSelectedLabel(target, ..)
+            | Self::StoreValue(target, ..)
+            | Self::StoreAttribute(target, ..)
+            | Self::AssertElementPresent(target)
+            | Self::AssertElementNotPresent(target)
+            | Self::AssertEditable(target)
+            | Self::AssertNotEditable(target)
+            | Self::ClickAt(target, ..)
+            | Self::DoubleClickAt(target, ..)
+            | Self::MouseOver(target)
+            | Self::MouseOut(target)
+            | Self::MouseMoveAt(target, ..)
+            | Self::MouseDownAt(target, ..)
+            | Self::MouseUpAt(target, ..)
+            | Self::DragAndDropToObject(target, ..)
+            | Self::Submit(target)
+            | Self::SelectFrame(FrameLocator::Element(target))
+            | Self::VerifyChecked(target)
+            | Self::VerifyNotChecked(target)
+            | Self::VerifyEditable(target)
+            | Self::VerifyNotEditable(target)
+            | Self::VerifyElementPresent(target)
+            | Self::VerifyElementNotPresent(target)
+            | Self::VerifySelectedLabel(target, ..)
+            | Self::VerifySelectedValue(target, ..)
+            | Self::VerifyNotSelectedValue(target, ..)
+            | Self::VerifyText(target, ..)
+            | Self::VerifyNotText(target, ..)
+            | Self::VerifyValue(target, ..) => *target = new,
+            _ => {}
+        }
+
+        cmd
+    }
+
+    /// Verification commands don't stop a test on a failed check.
+    pub(crate) fn is_verification(&self) -> bool {
+        matches!(
+            self,
+            Self::Verify { .. }
+                | Self::VerifyChecked(..)
+                | Self::VerifyNotChecked(..)
+                | Self::VerifyEditable(..)
+                | Self::VerifyNotEditable(..)
+                | Self::VerifyElementPresent(..)
+                | Self::VerifyElementNotPresent(..)
+                | Self::VerifySelectedLabel(..)
+                | Self::VerifySelectedValue(..)
+                | Self::VerifyNotSelectedValue(..)
+                | Self::VerifyText(..)
+                | Self::VerifyNotText(..)
+                | Self::VerifyTitle(..)
+                | Self::VerifyValue(..)
+        )
+    }
+}
+
+fn in_target(kind: ParseErrorKind) -> ParseError {
+    ParseError::in_field(kind, CommandField::Target)
+}
+
+fn in_value(kind: ParseErrorKind) -> ParseError {
+    ParseError::in_field(kind, CommandField::Value)
 }
 
-fn parse_coordinates(s: &str) -> Result<(i32, i32), ParseError> {
+fn parse_coordinates(s: &str) -> Result<(i32, i32), ParseErrorKind> {
     let mut bytes = s.trim().as_bytes();
     if let Some(b) = bytes.strip_prefix(b"(") {
         bytes = b;
@@ -792,17 +1288,17 @@ fn parse_coordinates(s: &str) -> Result<(i32, i32), ParseError> {
     let pos = bytes
         .iter()
         .position(|&c| c == b',')
-        .ok_or_else(|| ParseError::TypeError("Unexpected format of coordinates".to_string()))?;
+        .ok_or_else(|| ParseErrorKind::TypeError("Unexpected format of coordinates".to_string()))?;
 
     let x = std::str::from_utf8(&bytes[..pos])
         .map(|s| s.parse::<i32>())
-        .map_err(|_| ParseError::TypeError("Unexpected format of coordinates".to_string()))?
-        .map_err(|_| ParseError::TypeError("Unexpected format of coordinates".to_string()))?;
+        .map_err(|_| ParseErrorKind::TypeError("Unexpected format of coordinates".to_string()))?
+        .map_err(|_| ParseErrorKind::TypeError("Unexpected format of coordinates".to_string()))?;
 
     let y = std::str::from_utf8(&bytes[pos + 1..])
         .map(|s| s.parse::<i32>())
-        .map_err(|_| ParseError::TypeError("Unexpected format of coordinates".to_string()))?
-        .map_err(|_| ParseError::TypeError("Unexpected format of coordinates".to_string()))?;
+        .map_err(|_| ParseErrorKind::TypeError("Unexpected format of coordinates".to_string()))?
+        .map_err(|_| ParseErrorKind::TypeError("Unexpected format of coordinates".to_string()))?;
 
     Ok((x, y))
 }
@@ -811,21 +1307,127 @@ fn parse_coordinates(s: &str) -> Result<(i32, i32), ParseError> {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Target {
     /// Location is a way to find an element
-    pub location: Location,
+    ///
+    /// It's incomplete if it contains variables, e.g. `${locator}` or `css=${selector}`.
+    pub location: Param<Location>,
     /// Tag is an additional information of location type e.g.
     /// location = xpath, tag = Some(relative) | Some(positional) | None
     pub tag: Option<String>,
 }
 
 impl Target {
-    pub fn new(location: Location) -> Self {
+    pub fn new<L: Into<Param<Location>>>(location: L) -> Self {
         Target {
-            location,
+            location: location.into(),
             tag: None,
         }
     }
 }
 
+/// Param is an argument of a command which may contain variables, e.g. `${timeout}`.
+///
+/// Such an argument can't be checked while a file is parsed,
+/// so it's kept as it is and parsed when the command is run.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Param<T> {
+    /// An argument without variables.
+    Completed(T),
+    /// An argument with variables which aren't substituted yet.
+    Incomplete(String),
+}
+
+impl<T> From<T> for Param<T> {
+    fn from(value: T) -> Self {
+        Param::Completed(value)
+    }
+}
+
+impl<T: Argument> Param<T> {
+    fn parse(s: &str) -> Result<Self, ParseErrorKind> {
+        if s.contains("${") {
+            Ok(Param::Incomplete(s.to_owned()))
+        } else {
+            T::parse(s).map(Param::Completed)
+        }
+    }
+
+    /// Format returns the argument as it's written in a side file.
+    pub(crate) fn format(&self) -> String {
+        match self {
+            Param::Completed(value) => value.format(),
+            Param::Incomplete(s) => s.clone(),
+        }
+    }
+}
+
+impl<T: Argument> std::fmt::Display for Param<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.format())
+    }
+}
+
+/// Argument is a type of a command's argument which is stored as a string in a side file.
+pub trait Argument: Sized {
+    fn parse(s: &str) -> Result<Self, ParseErrorKind>;
+    fn format(&self) -> String;
+}
+
+/// A timeout in milliseconds.
+impl Argument for Duration {
+    fn parse(s: &str) -> Result<Self, ParseErrorKind> {
+        cast_timeout(s)
+    }
+
+    fn format(&self) -> String {
+        self.as_millis().to_string()
+    }
+}
+
+/// A window size in a form of `1916x1034` (Width x Height).
+impl Argument for (u32, u32) {
+    fn parse(s: &str) -> Result<Self, ParseErrorKind> {
+        parse_window_size(s)
+    }
+
+    fn format(&self) -> String {
+        format!("{}x{}", self.0, self.1)
+    }
+}
+
+/// Coordinates in a form of `10,-5`.
+impl Argument for (i32, i32) {
+    fn parse(s: &str) -> Result<Self, ParseErrorKind> {
+        parse_coordinates(s)
+    }
+
+    fn format(&self) -> String {
+        format!("{},{}", self.0, self.1)
+    }
+}
+
+/// A number of iterations.
+impl Argument for u64 {
+    fn parse(s: &str) -> Result<Self, ParseErrorKind> {
+        s.parse()
+            .map_err(|_| ParseErrorKind::TypeError("expected to get int".to_owned()))
+    }
+
+    fn format(&self) -> String {
+        self.to_string()
+    }
+}
+
+/// A locator in a form of `strategy=value`, e.g. `css=#id`.
+impl Argument for Location {
+    fn parse(s: &str) -> Result<Self, ParseErrorKind> {
+        parse_location(s)
+    }
+
+    fn format(&self) -> String {
+        self.to_string()
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum SelectLocator {
     Index(String),
@@ -834,38 +1436,143 @@ pub enum SelectLocator {
     Value(String),
 }
 
+impl SelectLocator {
+    /// Map returns the locator with a value changed by a given function.
+    pub(crate) fn map<F: FnOnce(&str) -> String>(&self, f: F) -> Self {
+        match self {
+            SelectLocator::Index(index) => SelectLocator::Index(f(index)),
+            SelectLocator::Label(label) => SelectLocator::Label(f(label)),
+            SelectLocator::Id(id) => SelectLocator::Id(f(id)),
+            SelectLocator::Value(value) => SelectLocator::Value(f(value)),
+        }
+    }
+}
+
+impl std::fmt::Display for SelectLocator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SelectLocator::Index(index) => write!(f, "index={}", index),
+            SelectLocator::Label(label) => write!(f, "label={}", label),
+            SelectLocator::Id(id) => write!(f, "id={}", id),
+            SelectLocator::Value(value) => write!(f, "value={}", value),
+        }
+    }
+}
+
+/// FrameLocator is a way to select a browsing context
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FrameLocator {
+    /// The top level browsing context
+    Top,
+    /// The parent of a current frame
+    Parent,
+    /// A frame by its index in the current browsing context
+    Index(u16),
+    /// A frame represented by an element e.g. `iframe`
+    Element(Target),
+}
+
+impl std::fmt::Display for FrameLocator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FrameLocator::Top => write!(f, "relative=top"),
+            FrameLocator::Parent => write!(f, "relative=parent"),
+            FrameLocator::Index(index) => write!(f, "index={}", index),
+            FrameLocator::Element(target) => write!(f, "{}", target.location),
+        }
+    }
+}
+
+/// WindowLocator is a way to select a window
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum WindowLocator {
+    /// A window by its handle
+    Handle(String),
+    /// A window by its name e.g. a `target` of a link which opened it
+    Name(String),
+    /// A window by a name Selenium IDE gave it on recording e.g. `win_ser_local` or `win_ser_1`
+    Serialized(String),
+}
+
+impl std::fmt::Display for WindowLocator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WindowLocator::Handle(handle) => write!(f, "handle={}", handle),
+            WindowLocator::Name(name) => write!(f, "name={}", name),
+            WindowLocator::Serialized(name) => write!(f, "{}", name),
+        }
+    }
+}
+
 /// Location is a locator of a HTML element
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Location {
     XPath(String),
     Css(String),
     Id(String),
+    Name(String),
+    LinkText(String),
+    PartialLinkText(String),
+}
+
+impl std::fmt::Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Location::XPath(path) => write!(f, "xpath={}", path),
+            Location::Css(path) => write!(f, "css={}", path),
+            Location::Id(path) => write!(f, "id={}", path),
+            Location::Name(name) => write!(f, "name={}", name),
+            Location::LinkText(text) => write!(f, "linkText={}", text),
+            Location::PartialLinkText(text) => write!(f, "partialLinkText={}", text),
+        }
+    }
 }
 
 impl Location {
-    fn new(tp: &str, path: &str) -> Result<Self, ParseError> {
+    /// Type returns a name of locator's strategy as it's used in a side file.
+    pub(crate) fn type_name(&self) -> &'static str {
+        match self {
+            Location::XPath(..) => "xpath",
+            Location::Css(..) => "css",
+            Location::Id(..) => "id",
+            Location::Name(..) => "name",
+            Location::LinkText(..) => "linkText",
+            Location::PartialLinkText(..) => "partialLinkText",
+        }
+    }
+
+    fn new(tp: &str, path: &str) -> Result<Self, ParseErrorKind> {
         let path = path.to_owned();
         match tp {
             "xpath" => Ok(Location::XPath(path)),
             "css" => Ok(Location::Css(path)),
             "id" => Ok(Location::Id(path)),
-            _ => Err(ParseError::LocatorFormatError(format!(
-                "unexpected locator type {}, supported xpath|css|id",
+            "name" => Ok(Location::Name(path)),
+            "linkText" => Ok(Location::LinkText(path)),
+            "partialLinkText" => Ok(Location::PartialLinkText(path)),
+            _ => Err(ParseErrorKind::LocatorFormatError(format!(
+                "unexpected locator type {}, supported xpath|css|id|name|linkText|partialLinkText",
                 tp
             ))),
         }
     }
 }
 
-fn parse_location(text: &str) -> Result<Location, ParseError> {
+/// Parses a location of a command's target
+/// which is checked when the command is run if it contains variables.
+fn parse_target_location(text: &str) -> Result<Param<Location>, ParseErrorKind> {
+    Param::parse(text)
+}
+
+fn parse_location(text: &str) -> Result<Location, ParseErrorKind> {
     let mut target_location = text.splitn(2, '=');
     let location_type = target_location.next().ok_or_else(|| {
-        ParseError::LocatorFormatError(
+        ParseErrorKind::LocatorFormatError(
             "target should contain a type of selector and a selector splited by '='".to_owned(),
         )
     })?;
     let location = target_location.next().ok_or_else(|| {
-        ParseError::LocatorFormatError(
+        ParseErrorKind::LocatorFormatError(
             "target should contain a type of selector and a selector splited by '='".to_owned(),
         )
     })?;
@@ -873,59 +1580,103 @@ fn parse_location(text: &str) -> Result<Location, ParseError> {
     Location::new(location_type, location)
 }
 
-fn parse_select_locator(text: &str) -> Result<SelectLocator, ParseError> {
+fn parse_select_locator(text: &str) -> Result<SelectLocator, ParseErrorKind> {
     const ERROR_TEXT: &str = "unexpected type of selector";
 
     let mut locator = text.splitn(2, '=');
     let locator_type = locator
         .next()
-        .ok_or_else(|| ParseError::LocatorFormatError(ERROR_TEXT.to_owned()))?;
+        .ok_or_else(|| ParseErrorKind::LocatorFormatError(ERROR_TEXT.to_owned()))?;
     let locator = locator
         .next()
-        .ok_or_else(|| ParseError::LocatorFormatError(ERROR_TEXT.to_owned()))?;
+        .ok_or_else(|| ParseErrorKind::LocatorFormatError(ERROR_TEXT.to_owned()))?;
 
     match locator_type {
         "index" => Ok(SelectLocator::Index(locator.to_owned())),
         "label" => Ok(SelectLocator::Label(locator.to_owned())),
         "id" => Ok(SelectLocator::Id(locator.to_owned())),
         "value" => Ok(SelectLocator::Value(locator.to_owned())),
-        _ => Err(ParseError::LocatorFormatError(ERROR_TEXT.to_owned())),
+        _ => Err(ParseErrorKind::LocatorFormatError(ERROR_TEXT.to_owned())),
     }
 }
 
-fn parse_targets(targets: &[(String, String)]) -> Result<Vec<Target>, ParseError> {
-    let mut out = Vec::new();
-    for target in targets {
-        let location = parse_location(&target.0)?;
-        let tag = parse_target_tag(&target.1)?.to_owned();
+fn parse_frame_locator(text: &str) -> Result<FrameLocator, ParseErrorKind> {
+    match text.split_once('=') {
+        Some(("relative", "top")) => Ok(FrameLocator::Top),
+        Some(("relative", "parent")) => Ok(FrameLocator::Parent),
+        Some(("relative", relative)) => Err(ParseErrorKind::LocatorFormatError(format!(
+            "unexpected relative frame {}, supported top|parent",
+            relative
+        ))),
+        Some(("index", index)) => index.parse().map(FrameLocator::Index).map_err(|_| {
+            ParseErrorKind::TypeError("expected to get a frame index as an int".to_owned())
+        }),
+        _ => {
+            let location = parse_target_location(text)?;
+            Ok(FrameLocator::Element(Target::new(location)))
+        }
+    }
+}
 
-        let target = Target {
-            location,
-            tag: Some(tag),
-        };
-        out.push(target);
+fn parse_window_locator(text: &str) -> Result<WindowLocator, ParseErrorKind> {
+    match text.split_once('=') {
+        Some(("handle", handle)) => Ok(WindowLocator::Handle(handle.to_owned())),
+        Some(("name", name)) => Ok(WindowLocator::Name(name.to_owned())),
+        None if text.starts_with("win_ser_") => Ok(WindowLocator::Serialized(text.to_owned())),
+        _ => Err(ParseErrorKind::LocatorFormatError(format!(
+            "unexpected window locator {}, supported handle|name|win_ser_*",
+            text
+        ))),
     }
+}
+
+/// Parses alternative targets of a command.
+///
+/// Alternatives are only a fallback of the primary target,
+/// so ones which can't be parsed are skipped instead of failing the file.
+fn parse_targets(targets: &[(String, String)]) -> Vec<Target> {
+    targets
+        .iter()
+        .filter_map(|(location, tag)| {
+            let location = parse_location(location).ok()?;
+            let tag = parse_target_tag(tag).to_owned();
+            Some(Target {
+                location: location.into(),
+                tag: Some(tag),
+            })
+        })
+        .collect()
+}
 
-    Ok(out)
+fn parse_target_tag(tag: &str) -> &'_ str {
+    tag.split_once(':').map_or(tag, |(_, tag)| tag)
 }
 
-fn parse_target_tag(tag: &str) -> Result<&'_ str, ParseError> {
-    Ok(tag.splitn(2, ':').nth(1).unwrap_or(tag))
+fn parse_window_size(s: &str) -> Result<(u32, u32), ParseErrorKind> {
+    let settings = s.split('x').map(|n| n.parse()).collect::<Vec<_>>();
+    if settings.len() != 2 {
+        return Err(ParseErrorKind::TypeError(
+            "window size expected to get in a form like this 1916x1034 (Width x Height)".to_owned(),
+        ));
+    }
+
+    let w = settings[0]
+        .clone()
+        .map_err(|_| ParseErrorKind::TypeError("expected to get int".to_owned()))?;
+    let h = settings[1]
+        .clone()
+        .map_err(|_| ParseErrorKind::TypeError("expected to get int".to_owned()))?;
+
+    Ok((w, h))
 }
 
-fn cast_timeout(s: &str) -> Result<Duration, ParseError> {
-    // TODO: posibly there may be a variable not only a number
-    // so we would need to create a enum like
-    // enum Value<T> {
-    //    Completed(T),
-    //    Incomplete(String),
-    // }
+fn cast_timeout(s: &str) -> Result<Duration, ParseErrorKind> {
     s.parse()
-        .map_err(|_| ParseError::TypeError("expected to get int".to_owned()))
+        .map_err(|_| ParseErrorKind::TypeError("expected to get int".to_owned()))
         .map(Duration::from_millis)
 }
 
-mod format {
+pub(crate) mod format {
     use serde::{Deserialize, Serialize};
 
     #[derive(Debug, Serialize, Deserialize)]
@@ -935,6 +1686,23 @@ mod format {
         pub name: String,
         pub url: String,
         pub tests: Vec<Test>,
+        #[serde(default)]
+        pub suites: Vec<Suite>,
+        #[serde(default)]
+        pub urls: Vec<String>,
+        #[serde(default)]
+        pub plugins: Vec<serde_json::Value>,
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    pub struct Suite {
+        pub id: String,
+        pub name: String,
+        #[serde(rename = "persistSession")]
+        pub persist_session: bool,
+        pub parallel: bool,
+        pub timeout: u64,
+        pub tests: Vec<String>,
     }
 
     #[derive(Debug, Serialize, Deserialize)]
@@ -953,6 +1721,24 @@ mod format {
         pub target: String,
         pub targets: Vec<(String, String)>,
         pub value: String,
+        #[serde(
+            default,
+            rename = "opensWindow",
+            skip_serializing_if = "std::ops::Not::not"
+        )]
+        pub opens_window: bool,
+        #[serde(
+            default,
+            rename = "windowHandleName",
+            skip_serializing_if = "String::is_empty"
+        )]
+        pub window_handle_name: String,
+        #[serde(
+            default,
+            rename = "windowTimeout",
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub window_timeout: Option<u64>,
     }
 }
 
@@ -975,7 +1761,7 @@ mod tests {
         ));
         assert!(matches!(
             file.tests[0].commands[1].cmd,
-            Cmd::Pause(ref timeout) if *timeout == Duration::from_secs(5)
+            Cmd::Pause(Param::Completed(ref timeout)) if *timeout == Duration::from_secs(5)
         ));
         assert!(matches!(
             file.tests[0].commands[2].cmd,
@@ -1045,37 +1831,36 @@ mod tests {
         assert!(matches!(commands[1].cmd, Cmd::Custom { .. }));
         assert!(matches!(commands[2].cmd, Cmd::Open(..)));
 
-        if let Cmd::Custom { targets, .. } = &commands[1].cmd {
-            assert_eq!(targets.len(), 4);
-            assert_eq!(
-                targets[0],
-                Target {
-                    location: Location::Id("content".to_string()),
-                    tag: Some("id".to_string())
-                }
-            );
-            assert_eq!(
-                targets[1],
-                Target {
-                    location: Location::Css("#content".to_string()),
-                    tag: Some("finder".to_string())
-                }
-            );
-            assert_eq!(
-                targets[2],
-                Target {
-                    location: Location::XPath("//div[@id='content']".to_string()),
-                    tag: Some("attributes".to_string())
-                }
-            );
-            assert_eq!(
-                targets[3],
-                Target {
-                    location: Location::XPath("//div[4]/div[2]".to_string()),
-                    tag: Some("position".to_string())
-                }
-            );
-        }
+        let targets = &commands[1].targets;
+        assert_eq!(targets.len(), 4);
+        assert_eq!(
+            targets[0],
+            Target {
+                location: Location::Id("content".to_string()).into(),
+                tag: Some("id".to_string())
+            }
+        );
+        assert_eq!(
+            targets[1],
+            Target {
+                location: Location::Css("#content".to_string()).into(),
+                tag: Some("finder".to_string())
+            }
+        );
+        assert_eq!(
+            targets[2],
+            Target {
+                location: Location::XPath("//div[@id='content']".to_string()).into(),
+                tag: Some("attributes".to_string())
+            }
+        );
+        assert_eq!(
+            targets[3],
+            Target {
+                location: Location::XPath("//div[4]/div[2]".to_string()).into(),
+                tag: Some("position".to_string())
+            }
+        );
     }
 
     #[test]
@@ -1113,7 +1898,239 @@ mod tests {
 
         let reader = file.as_slice();
         let err = parse(reader).unwrap_err();
-        assert!(matches!(err, ParseError::NotUniqTestName(0, 1)))
+        assert!(matches!(err.kind, ParseErrorKind::NotUniqTestName(0, 1)))
+    }
+
+    #[test]
+    fn _parse_suites() {
+        let file = side_file();
+        let file = parse(file.as_slice()).unwrap();
+        assert_eq!(file.suites.len(), 1);
+        let suite = &file.suites[0];
+        assert_eq!(suite.id, "925de5ce-03ae-4dcb-9146-c956ff3f090d");
+        assert_eq!(suite.name, "Default Suite");
+        assert!(!suite.persist_session);
+        assert!(!suite.parallel);
+        assert_eq!(suite.timeout, Duration::from_secs(300));
+        assert_eq!(suite.tests, vec!["5d61ce01-d373-4b14-a1a1-7474a4e192e5"]);
+    }
+
+    #[test]
+    fn _error_on_suite_with_unknown_test() {
+        let file: Vec<u8> = r#"{
+            "id": "bfc1bd56-39bd-4a0d-be2b-583ad75ac104",
+            "version": "2.0",
+            "name": "",
+            "url": "",
+            "tests": [],
+            "suites": [{
+              "id": "925de5ce-03ae-4dcb-9146-c956ff3f090d",
+              "name": "Default Suite",
+              "persistSession": false,
+              "parallel": false,
+              "timeout": 300,
+              "tests": ["5d61ce01-d373-4b14-a1a1-7474a4e192e5"]
+            }],
+            "urls": [""],
+            "plugins": []
+          }"#
+        .as_bytes()
+        .to_vec();
+
+        let err = parse(file.as_slice()).unwrap_err();
+        assert!(matches!(err.kind, ParseErrorKind::ValidationError(..)))
+    }
+
+    #[test]
+    fn _error_points_to_a_command() {
+        let file: Vec<u8> = r#"{
+            "id": "", "version": "2.0", "name": "", "url": "",
+            "tests": [{
+                "id": "1",
+                "name": "login",
+                "commands": [
+                    { "id": "c1", "comment": "", "command": "open", "target": "/", "targets": [], "value": "" },
+                    { "id": "c2", "comment": "", "command": "click", "target": "name", "targets": [], "value": "" }
+                ]
+            }],
+            "suites": []
+          }"#
+        .as_bytes()
+        .to_vec();
+
+        let err = parse(file.as_slice()).unwrap_err();
+        assert!(matches!(err.kind, ParseErrorKind::LocatorFormatError(..)));
+        assert_eq!(err.test.as_deref(), Some("login"));
+        assert_eq!(err.index, Some(1));
+        assert_eq!(err.id.as_deref(), Some("c2"));
+        assert_eq!(err.field, Some(CommandField::Target));
+        assert_eq!(
+            err.to_string(),
+            "test 'login' step 1 (id=c2) target: locator has wrong format: \
+             target should contain a type of selector and a selector splited by '='"
+        );
+    }
+
+    #[test]
+    fn _collect_all_errors() {
+        let file: Vec<u8> = r#"{
+            "id": "", "version": "2.0", "name": "", "url": "",
+            "tests": [{
+                "id": "1",
+                "name": "first",
+                "commands": [
+                    { "id": "c1", "comment": "", "command": "foo", "target": "", "targets": [], "value": "" },
+                    { "id": "c2", "comment": "", "command": "pause", "target": "1000", "targets": [], "value": "" },
+                    { "id": "c3", "comment": "", "command": "waitForElementVisible", "target": "id=name", "targets": [], "value": "soon" }
+                ]
+            }, {
+                "id": "2",
+                "name": "second",
+                "commands": [
+                    { "id": "c4", "comment": "", "command": "storeText", "target": "name", "targets": [], "value": "text" }
+                ]
+            }, {
+                "id": "3",
+                "name": "first",
+                "commands": [
+                    { "id": "c5", "comment": "", "command": "bar", "target": "", "targets": [], "value": "" }
+                ]
+            }],
+            "suites": [{
+                "id": "s1", "name": "suite", "persistSession": false, "parallel": false, "timeout": 300,
+                "tests": ["4"]
+            }]
+          }"#
+        .as_bytes()
+        .to_vec();
+
+        let errors = parse_all(file.as_slice()).unwrap_err();
+        assert!(matches!(
+            errors[3].kind,
+            ParseErrorKind::NotUniqTestName(0, 2)
+        ));
+        assert!(matches!(
+            errors[5].kind,
+            ParseErrorKind::ValidationError(..)
+        ));
+        let errors = errors
+            .iter()
+            .map(|err| (err.test.as_deref(), err.index, err.id.as_deref(), err.field))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            errors,
+            vec![
+                (
+                    Some("first"),
+                    Some(0),
+                    Some("c1"),
+                    Some(CommandField::Command)
+                ),
+                (
+                    Some("first"),
+                    Some(2),
+                    Some("c3"),
+                    Some(CommandField::Value)
+                ),
+                (
+                    Some("second"),
+                    Some(0),
+                    Some("c4"),
+                    Some(CommandField::Target)
+                ),
+                (Some("first"), None, None, None),
+                (
+                    Some("first"),
+                    Some(0),
+                    Some("c5"),
+                    Some(CommandField::Command)
+                ),
+                (None, None, None, None),
+            ]
+        );
+
+        let err = parse(file.as_slice()).unwrap_err();
+        assert_eq!(err.id.as_deref(), Some("c1"));
+    }
+
+    #[test]
+    fn _skip_unparsable_alternative_targets() {
+        let file = r#"{
+            "id": "", "version": "2.0", "name": "", "url": "",
+            "tests": [{
+                "id": "1",
+                "name": "first",
+                "commands": [
+                    {
+                        "id": "c1", "comment": "", "command": "click", "target": "id=name",
+                        "targets": [["xpath", "xpath:position"], ["css=#name", "css:finder"]],
+                        "value": ""
+                    }
+                ]
+            }],
+            "suites": []
+          }"#;
+
+        let file = parse(file.as_bytes()).unwrap();
+        assert_eq!(
+            file.tests[0].commands[0].targets,
+            vec![Target {
+                location: Location::Css("#name".to_owned()).into(),
+                tag: Some("finder".to_owned()),
+            }]
+        );
+    }
+
+    #[test]
+    fn _parse_params_with_variables() {
+        let file = r#"{
+            "id": "", "version": "2.0", "name": "", "url": "",
+            "tests": [{
+                "id": "1",
+                "name": "first",
+                "commands": [
+                    { "id": "c1", "comment": "", "command": "pause", "target": "${timeout}", "targets": [], "value": "" },
+                    { "id": "c2", "comment": "", "command": "setWindowSize", "target": "${width}x600", "targets": [], "value": "" },
+                    { "id": "c3", "comment": "", "command": "clickAt", "target": "id=${id}", "targets": [], "value": "${x},${y}" },
+                    { "id": "c4", "comment": "", "command": "waitForElementPresent", "target": "css=p", "targets": [], "value": "3000" },
+                    { "id": "c5", "comment": "", "command": "click", "target": "${locator}", "targets": [], "value": "" },
+                    { "id": "c6", "comment": "", "command": "times", "target": "${n}", "targets": [], "value": "" }
+                ]
+            }],
+            "suites": []
+          }"#;
+
+        let file = parse(file.as_bytes()).unwrap();
+        let commands = file.tests[0]
+            .commands
+            .iter()
+            .map(|c| c.cmd.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            commands,
+            vec![
+                Cmd::Pause(Param::Incomplete("${timeout}".to_owned())),
+                Cmd::SetWindowSize(Param::Incomplete("${width}x600".to_owned())),
+                Cmd::ClickAt(
+                    Target::new(Param::Incomplete("id=${id}".to_owned())),
+                    Param::Incomplete("${x},${y}".to_owned())
+                ),
+                Cmd::WaitForElementPresent {
+                    target: Target::new(Location::Css("p".to_owned())),
+                    timeout: Param::Completed(Duration::from_secs(3)),
+                },
+                Cmd::Click(Target::new(Param::Incomplete("${locator}".to_owned()))),
+                Cmd::Times(Param::Incomplete("${n}".to_owned())),
+            ]
+        );
+        assert_eq!(
+            crate::writer::format_cmd(&commands[2]),
+            (
+                "clickAt".to_owned(),
+                "id=${id}".to_owned(),
+                "${x},${y}".to_owned()
+            )
+        );
     }
 
     fn side_file() -> Vec<u8> {
@@ -1163,6 +2180,126 @@ mod tests {
         .to_vec()
     }
 
+    #[test]
+    fn _parse_opens_window() {
+        let file = r#"{
+            "id": "",
+            "version": "2.0",
+            "name": "",
+            "url": "",
+            "tests": [{
+                "id": "",
+                "name": "",
+                "commands": [
+                    {
+                        "id": "",
+                        "comment": "",
+                        "command": "click",
+                        "target": "css=#popup",
+                        "targets": [],
+                        "value": "",
+                        "opensWindow": true,
+                        "windowHandleName": "win1",
+                        "windowTimeout": 3000
+                    },
+                    {
+                        "id": "",
+                        "comment": "",
+                        "command": "selectWindow",
+                        "target": "handle=${win1}",
+                        "targets": [],
+                        "value": ""
+                    }
+                ]
+            }]
+        }"#;
+
+        let file = parse(file.as_bytes()).unwrap();
+        let commands = &file.tests[0].commands;
+
+        assert_eq!(
+            commands[0].opens_window,
+            Some(NewWindow {
+                handle_name: "win1".to_string(),
+                timeout: Some(Duration::from_millis(3000)),
+            })
+        );
+        assert_eq!(commands[1].opens_window, None);
+        assert_eq!(
+            commands[1].cmd,
+            Cmd::SelectWindow(WindowLocator::Handle("${win1}".to_string()))
+        );
+    }
+
+    #[test]
+    fn parse_window_locator_test() {
+        assert_eq!(
+            parse_window_locator("handle=${win}").unwrap(),
+            WindowLocator::Handle("${win}".to_owned())
+        );
+        assert_eq!(
+            parse_window_locator("name=popup").unwrap(),
+            WindowLocator::Name("popup".to_owned())
+        );
+        assert_eq!(
+            parse_window_locator("win_ser_local").unwrap(),
+            WindowLocator::Serialized("win_ser_local".to_owned())
+        );
+        assert_eq!(
+            parse_window_locator("win_ser_1").unwrap(),
+            WindowLocator::Serialized("win_ser_1".to_owned())
+        );
+        assert!(parse_window_locator("title=Popup").is_err());
+        assert!(parse_window_locator("popup").is_err());
+    }
+
+    #[test]
+    fn parse_frame_locator_test() {
+        assert_eq!(
+            parse_frame_locator("relative=top").unwrap(),
+            FrameLocator::Top
+        );
+        assert_eq!(
+            parse_frame_locator("relative=parent").unwrap(),
+            FrameLocator::Parent
+        );
+        assert_eq!(
+            parse_frame_locator("index=2").unwrap(),
+            FrameLocator::Index(2)
+        );
+        assert_eq!(
+            parse_frame_locator("id=frame").unwrap(),
+            FrameLocator::Element(Target::new(Location::Id("frame".to_owned())))
+        );
+        assert!(parse_frame_locator("relative=child").is_err());
+        assert!(parse_frame_locator("index=first").is_err());
+    }
+
+    #[test]
+    fn parse_location_test() {
+        let locations = [
+            ("xpath=//a", Location::XPath("//a".to_owned())),
+            ("css=#id", Location::Css("#id".to_owned())),
+            ("id=id", Location::Id("id".to_owned())),
+            ("name=user", Location::Name("user".to_owned())),
+            (
+                "linkText=Read more",
+                Location::LinkText("Read more".to_owned()),
+            ),
+            (
+                "partialLinkText=more",
+                Location::PartialLinkText("more".to_owned()),
+            ),
+        ];
+        for (text, location) in locations {
+            assert_eq!(parse_location(text).unwrap(), location);
+            assert_eq!(location.to_string(), text);
+        }
+
+        assert!(parse_location("link=Read more").is_err());
+        assert!(parse_location("name").is_err());
+    }
+
     #[test]
     fn parse_coordinates_test() {
         assert_eq!(parse_coordinates("12,120").unwrap(), (12, 120));
@@ -3,10 +3,12 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 use serde_json::Value;
+use std::{collections::HashMap, time::Instant};
 
 use crate::{
-    error::RunnerErrorKind, parser::Cmd, runner::Runner, validation::validate_conditions,
-    webdriver, Command, File, RunnerError, Test,
+    error::RunnerErrorKind, healing::HealedTarget, listener::CommandEvent, parser::Cmd,
+    runner::Runner, validation::validate_conditions, webdriver, writer::format_cmd, Command, File,
+    RunnerError, Target, Test,
 };
 
 pub struct Playground {
@@ -15,7 +17,7 @@ pub struct Playground {
 
 impl Playground {
     pub fn new(test: &Test) -> Result<Self, RunnerError> {
-        validate_conditions(&test.commands).map_err(|e| Self::add_error_context(e, test))?;
+        validate_conditions(&test.commands).map_err(|e| e.with_context(test))?;
         let nodes = build_nodes(&test.commands);
         Ok(Self { nodes })
     }
@@ -26,9 +28,9 @@ impl Playground {
         file: &File,
         test: &Test,
     ) -> Result<(), RunnerError> {
-        run_nodes(runner, &self.nodes, file)
+        run_nodes(runner, &self.nodes, file, test)
             .await
-            .map_err(|e| Self::add_error_context(e, test))
+            .map_err(|e| e.with_context(test))
     }
 
     pub async fn run_test<D: webdriver::Webdriver>(
@@ -37,13 +39,23 @@ impl Playground {
         test_index: usize,
     ) -> Result<(), RunnerError> {
         let test = &file.tests[test_index];
-        let p = Self::new(test)?;
-        p.run(runner, file, test).await
-    }
+        let start = Instant::now();
+        runner.notify(|listener| listener.test_started(test));
+
+        let result = match Self::new(test) {
+            Ok(p) => p.run(runner, file, test).await,
+            Err(e) => Err(e),
+        };
 
-    fn add_error_context(mut e: RunnerError, test: &Test) -> RunnerError {
-        e.test = Some(test.name.clone());
-        e
+        let duration = start.elapsed();
+        runner.notify(|listener| {
+            if let Err(e) = &result {
+                listener.error(e);
+            }
+            listener.test_finished(test, duration, result.as_ref().map(|_| ()));
+        });
+
+        result
     }
 }
 
@@ -57,11 +69,14 @@ async fn run_nodes<D: webdriver::Webdriver>(
     runner: &mut Runner<D>,
     nodes: &[Node],
     file: &File,
+    test: &Test,
 ) -> Result<(), RunnerError> {
     if nodes.is_empty() {
         return Ok(());
     }
 
+    // Iterations of loops which are being run, by a position of a loop's node.
+    let mut iterations = HashMap::new();
     let mut i = 0;
     loop {
         if i >= nodes.len() {
@@ -79,20 +94,44 @@ async fn run_nodes<D: webdriver::Webdriver>(
             Transition::Next => {
                 i += 1;
                 let cmd = &node.command;
-                runner
-                    .run_command(file, cmd)
-                    .await
-                    .map_err(|e| RunnerError::new(e, node.index))?;
+                let event = command_event(runner, test, node);
+                runner.notify(|listener| listener.command_started(&event));
+
+                let start = Instant::now();
+                let command = &test.commands[node.index];
+                let result = match run_command(runner, file, command, cmd).await {
+                    Err(err) if !command.targets.is_empty() => {
+                        run_with_alternatives(runner, file, command, cmd, &event, err).await
+                    }
+                    result => result,
+                };
+
+                let duration = start.elapsed();
+                runner.notify(|listener| {
+                    listener.command_finished(&event, duration, result.as_ref().map(|_| ()))
+                });
+
+                match result {
+                    Ok(()) => {}
+                    // A failed verification doesn't stop a test unless the session is gone.
+                    Err(e) if cmd.is_verification() && !e.ends_session() => {
+                        let e = failure(runner, test, node, e).await;
+                        runner.add_soft_failure(e);
+                    }
+                    Err(e) => return Err(failure(runner, test, node, e).await),
+                }
             }
             Transition::Conditional { next, end: or_else } => {
+                let position = i;
                 match &node.command {
                     Cmd::While(condition)
                     | Cmd::ElseIf(condition)
                     | Cmd::If(condition)
                     | Cmd::RepeatIf(condition) => {
-                        let cond = run_condition(runner, condition)
-                            .await
-                            .map_err(|e| RunnerError::new(e, i))?;
+                        let cond = match run_condition(runner, condition).await {
+                            Ok(cond) => cond,
+                            Err(e) => return Err(failure(runner, test, node, e).await),
+                        };
                         if cond {
                             i = next;
                         } else {
@@ -133,7 +172,7 @@ async fn run_nodes<D: webdriver::Webdriver>(
                                 } else {
                                     let e = arr.remove(0);
                                     runner.save_value(var.clone(), e);
-                                    runner.save_value(key, array);
+                                    runner.save_internal_value(key, array);
                                     i = next;
                                 }
                             }
@@ -148,26 +187,20 @@ async fn run_nodes<D: webdriver::Webdriver>(
                                 if n == 0 {
                                     i = or_else;
                                 } else {
-                                    runner.save_value(key.clone(), (n - 1).into());
+                                    runner.save_internal_value(key.clone(), (n - 1).into());
                                     i = next;
                                 }
                             }
                             None => {
-                                let n = runner.emit(n);
-                                let n: u64 = match n.parse() {
+                                let n = match runner.resolve(n) {
                                     Ok(n) => n,
-                                    _ => {
-                                        // Itarator is invalid; skip inner block
-                                        // TODO: it may be worth to not stick with Selenium IDE behaivour but rather raise a erorr?
-                                        i = or_else;
-                                        continue;
-                                    }
+                                    Err(e) => return Err(failure(runner, test, node, e).await),
                                 };
 
                                 if n == 0 {
                                     i = or_else;
                                 } else {
-                                    runner.save_value(key.clone(), (n - 1).into());
+                                    runner.save_internal_value(key.clone(), (n - 1).into());
                                     i = next;
                                 }
                             }
@@ -176,6 +209,22 @@ async fn run_nodes<D: webdriver::Webdriver>(
                     }
                     _ => unreachable!("unexpected condition"),
                 };
+
+                let is_loop = matches!(
+                    node.command,
+                    Cmd::While(..) | Cmd::RepeatIf(..) | Cmd::ForEach { .. } | Cmd::Times(..)
+                );
+                if is_loop {
+                    if i == next {
+                        let iteration = iterations.entry(position).or_insert(0);
+                        *iteration += 1;
+                        let iteration = *iteration;
+                        let event = command_event(runner, test, node);
+                        runner.notify(|listener| listener.loop_iteration(&event, iteration));
+                    } else {
+                        iterations.remove(&position);
+                    }
+                }
             }
         };
     }
@@ -183,6 +232,105 @@ async fn run_nodes<D: webdriver::Webdriver>(
     Ok(())
 }
 
+async fn run_command<D: webdriver::Webdriver>(
+    runner: &mut Runner<D>,
+    file: &File,
+    command: &Command,
+    cmd: &Cmd,
+) -> Result<(), RunnerErrorKind> {
+    match &command.opens_window {
+        Some(window) => runner.run_command_opening_window(file, cmd, window).await,
+        None => runner.run_command(file, cmd).await,
+    }
+}
+
+/// Reruns a failed command with alternative targets if an element can't be found by its own target.
+///
+/// The first alternative which matches an element is used.
+async fn run_with_alternatives<D: webdriver::Webdriver>(
+    runner: &mut Runner<D>,
+    file: &File,
+    command: &Command,
+    cmd: &Cmd,
+    event: &CommandEvent<'_>,
+    err: RunnerErrorKind,
+) -> Result<(), RunnerErrorKind> {
+    // Commands which check that an element is absent have nothing to fall back to.
+    let target = match cmd {
+        Cmd::WaitForElementNotPresent { .. }
+        | Cmd::WaitForElementNotVisible { .. }
+        | Cmd::AssertElementNotPresent(..)
+        | Cmd::VerifyElementNotPresent(..) => return Err(err),
+        cmd => match cmd.target() {
+            Some(target) => target,
+            None => return Err(err),
+        },
+    };
+
+    if is_present(runner, target).await {
+        return Err(err);
+    }
+
+    for alternative in &command.targets {
+        if alternative.location == target.location || !is_present(runner, alternative).await {
+            continue;
+        }
+
+        runner.notify(|listener| listener.target_fallback(event, alternative));
+        run_command(runner, file, command, &cmd.with_target(alternative.clone())).await?;
+        runner.add_healed_target(HealedTarget {
+            test: event.test.name.clone(),
+            index: event.index,
+            id: command.id.clone(),
+            target: target.clone(),
+            alternative: alternative.clone(),
+        });
+
+        return Ok(());
+    }
+
+    Err(err)
+}
+
+async fn is_present<D: webdriver::Webdriver>(runner: &mut Runner<D>, target: &Target) -> bool {
+    let locator = match runner.locator(target) {
+        Ok(locator) => locator,
+        Err(..) => return false,
+    };
+    runner
+        .get_webdriver()
+        .find_all(locator)
+        .await
+        .is_ok_and(|elements| !elements.is_empty())
+}
+
+fn command_event<'a, D: webdriver::Webdriver>(
+    runner: &Runner<D>,
+    test: &'a Test,
+    node: &Node,
+) -> CommandEvent<'a> {
+    let (name, target, value) = format_cmd(&node.command);
+    CommandEvent {
+        test,
+        command: &test.commands[node.index],
+        index: node.index,
+        name,
+        target: runner.emit(&target),
+        value: runner.emit(&value),
+    }
+}
+
+async fn failure<D: webdriver::Webdriver>(
+    runner: &mut Runner<D>,
+    test: &Test,
+    node: &Node,
+    kind: RunnerErrorKind,
+) -> RunnerError {
+    let mut e = RunnerError::new(kind, node.index).with_context(test);
+    runner.capture_artifacts(&mut e).await;
+    e
+}
+
 async fn run_condition<D: webdriver::Webdriver>(
     runner: &mut Runner<D>,
     condition: &str,
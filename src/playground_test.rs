@@ -552,10 +552,19 @@ fn test_creating_run_list_with_2_whiles_and_2_ifs() {
     )
 }
 
+fn blank_cmd(cmd: Cmd) -> Command {
+    Command::new("", "", cmd)
+}
+
 #[cfg(test)]
 mod flow {
     use super::*;
-    use crate::parser::{Cmd, Command, File, Location, Target, Test};
+    use crate::error::{RunnerError, RunnerErrorKind};
+    use crate::listener::{CommandEvent, RunListener};
+    use crate::parser::{
+        Cmd, Command, File, FrameLocator, Location, NewWindow, Param, Suite, Target, Test,
+        WindowLocator,
+    };
     use mock::{Call, Client};
     use std::sync::{Arc, Mutex};
 
@@ -566,7 +575,7 @@ mod flow {
             Cmd::Click(Target::new(Location::Css("".to_owned()))),
         ]);
         let client = Client::new();
-        let mut runner = Runner::_new(client.clone());
+        let mut runner = Runner::with_backend(client.clone());
 
         let res = runner.run(&file).await;
         assert!(res.is_ok());
@@ -583,7 +592,7 @@ mod flow {
             Cmd::Click(Target::new(Location::Css("".to_owned()))),
         ]);
         let client = Client::new();
-        let mut runner = Runner::_new(client.clone());
+        let mut runner = Runner::with_backend(client.clone());
 
         let res = runner.run(&file).await;
         assert!(res.is_ok());
@@ -604,7 +613,7 @@ mod flow {
             Cmd::End,
         ]);
         let client = Client::new();
-        let mut runner = Runner::_new(client.clone());
+        let mut runner = Runner::with_backend(client.clone());
         runner.save_value("array".to_string(), serde_json::json!(["E1", "E2", "E3"]));
 
         let echo_vector: Arc<Mutex<Vec<String>>> = Arc::default();
@@ -624,12 +633,12 @@ mod flow {
     async fn test_times() {
         let file = blank_file(vec![
             Cmd::Open("http://example.com".to_string()),
-            Cmd::Times("4".to_string()),
+            Cmd::Times(4.into()),
             Cmd::Echo("".to_string()),
             Cmd::End,
         ]);
         let client = Client::new();
-        let mut runner = Runner::_new(client.clone());
+        let mut runner = Runner::with_backend(client.clone());
 
         let echo_counter: Arc<Mutex<usize>> = Arc::default();
         let echo_counter1 = echo_counter.clone();
@@ -645,12 +654,12 @@ mod flow {
     async fn test_times_with_var() {
         let file = blank_file(vec![
             Cmd::Open("http://example.com".to_string()),
-            Cmd::Times("${N}".to_string()),
+            Cmd::Times(Param::Incomplete("${N}".to_string())),
             Cmd::Echo("".to_string()),
             Cmd::End,
         ]);
         let client = Client::new();
-        let mut runner = Runner::_new(client.clone());
+        let mut runner = Runner::with_backend(client.clone());
         runner.save_value("N".to_string(), 4u64.into());
 
         let echo_counter: Arc<Mutex<usize>> = Arc::default();
@@ -673,11 +682,7 @@ mod flow {
             vec![Test {
                 id: String::new(),
                 name: String::new(),
-                commands: vec![Command::new(
-                    "".to_owned(),
-                    "".to_owned(),
-                    Cmd::Open("/index.html".to_owned()),
-                )],
+                commands: vec![Command::new("", "", Cmd::Open("/index.html".to_owned()))],
             }],
         );
 
@@ -696,13 +701,1009 @@ mod flow {
             None,
             None,
         );
-        let mut runner = Runner::_new(client.clone());
+        let mut runner = Runner::with_backend(client.clone());
+
+        runner.run(&file).await.unwrap();
+
+        assert_eq!(client.calls()[Call::Goto], 1);
+    }
+
+    #[tokio::test]
+    async fn test_verify_doesnt_stop_a_test() {
+        let file = blank_file(vec![
+            Cmd::Store {
+                var: "var".to_string(),
+                value: "value".to_string(),
+            },
+            Cmd::Verify {
+                var: "var".to_string(),
+                value: "another value".to_string(),
+            },
+            Cmd::Verify {
+                var: "var".to_string(),
+                value: "value".to_string(),
+            },
+            Cmd::VerifyTitle("title".to_string()),
+            Cmd::Echo("${var}".to_string()),
+        ]);
+        let client = Client::new();
+        let mut runner = Runner::with_backend(client.clone());
+
+        let echo_counter: Arc<Mutex<usize>> = Arc::default();
+        let echo_counter1 = echo_counter.clone();
+        runner.set_echo(move |_| *echo_counter1.lock().unwrap() += 1);
+
+        let err = runner.run(&file).await.unwrap_err();
+        assert_eq!(*echo_counter.lock().unwrap(), 1);
+        assert_eq!(client.calls()[Call::Title], 1);
+        assert_eq!(err.index, 1);
+        match err.kind {
+            RunnerErrorKind::VerificationFailed(failures) => {
+                assert_eq!(failures.len(), 2);
+                assert_eq!(failures[0].index, 1);
+                assert_eq!(failures[1].index, 3);
+            }
+            kind => panic!("unexpected error {:?}", kind),
+        }
+        assert!(runner.get_soft_failures().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_verify_missing_element() {
+        use crate::testing::MockDriver;
+
+        let file = blank_file(vec![
+            Cmd::VerifyText(
+                Target::new(Location::Id("header".to_string())),
+                "Header".to_string(),
+            ),
+            Cmd::Echo("next".to_string()),
+        ]);
+        let mut runner = Runner::with_backend(MockDriver::new());
+
+        let echo_counter: Arc<Mutex<usize>> = Arc::default();
+        let echo_counter1 = echo_counter.clone();
+        runner.set_echo(move |_| *echo_counter1.lock().unwrap() += 1);
+
+        let err = runner.run(&file).await.unwrap_err();
+        assert_eq!(*echo_counter.lock().unwrap(), 1);
+        match err.kind {
+            RunnerErrorKind::VerificationFailed(failures) => {
+                assert_eq!(failures.len(), 1);
+                assert_eq!(failures[0].index, 0);
+                assert!(matches!(
+                    failures[0].kind,
+                    RunnerErrorKind::WebdriverError(..)
+                ));
+            }
+            kind => panic!("unexpected error {:?}", kind),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_verify_failures_are_kept_on_error() {
+        let file = blank_file(vec![
+            Cmd::Verify {
+                var: "var".to_string(),
+                value: "value".to_string(),
+            },
+            Cmd::Assert {
+                var: "var".to_string(),
+                value: "value".to_string(),
+            },
+        ]);
+        let mut runner = Runner::with_backend(Client::new());
+
+        let err = runner.run(&file).await.unwrap_err();
+        assert!(matches!(err.kind, RunnerErrorKind::AssertFailed { .. }));
+        assert_eq!(err.index, 1);
+        assert_eq!(runner.get_soft_failures().len(), 1);
+        assert_eq!(runner.get_soft_failures()[0].index, 0);
+    }
+
+    #[tokio::test]
+    async fn test_verify_in_inner_test() {
+        let file = File::new(
+            "".into(),
+            "".into(),
+            "".into(),
+            "".into(),
+            vec![
+                blank_test("1", vec![Cmd::RunTest("2".to_string())]),
+                blank_test("2", vec![Cmd::VerifyTitle("title".to_string())]),
+            ],
+        );
+        let mut runner = Runner::with_backend(Client::new());
+
+        let err = runner.run_test(&file, "1").await.unwrap_err();
+        assert_eq!(err.test, Some("2".to_string()));
+        assert!(matches!(err.kind, RunnerErrorKind::VerificationFailed(..)));
+    }
+
+    #[tokio::test]
+    async fn test_select_frame() {
+        let file = blank_file(vec![
+            Cmd::SelectFrame(FrameLocator::Index(0)),
+            Cmd::SelectFrame(FrameLocator::Element(Target::new(Location::Css(
+                "iframe".to_owned(),
+            )))),
+            Cmd::SelectFrame(FrameLocator::Parent),
+            Cmd::SelectFrame(FrameLocator::Top),
+        ]);
+        let client = Client::new();
+        let mut runner = Runner::with_backend(client.clone());
+
+        runner.run(&file).await.unwrap();
+
+        let calls = client.calls();
+        assert_eq!(calls[Call::SwitchToFrame], 1);
+        assert_eq!(calls[Call::SwitchToFrameElement], 1);
+        assert_eq!(calls[Call::SwitchToParentFrame], 1);
+        assert_eq!(calls[Call::SwitchToDefaultContent], 1);
+    }
+
+    #[tokio::test]
+    async fn test_mouse_pointer_commands() {
+        let target = Target::new(Location::Css("div".to_owned()));
+        let file = blank_file(vec![
+            Cmd::MouseOver(target.clone()),
+            Cmd::MouseMoveAt(target.clone(), (10, 10).into()),
+            Cmd::MouseDownAt(target.clone(), (10, 10).into()),
+            Cmd::MouseUpAt(target.clone(), (-10, 5).into()),
+            Cmd::MouseOut(target),
+        ]);
+        let client = Client::new();
+        let mut runner = Runner::with_backend(client.clone());
+
+        runner.run(&file).await.unwrap();
+
+        let calls = client.calls();
+        assert_eq!(calls[Call::MouseOver], 1);
+        assert_eq!(calls[Call::MouseMoveAt], 1);
+        assert_eq!(calls[Call::MouseDownAt], 1);
+        assert_eq!(calls[Call::MouseUpAt], 1);
+        assert_eq!(calls[Call::MouseOut], 1);
+    }
+
+    #[tokio::test]
+    async fn test_drag_and_drop_to_object() {
+        let file = blank_file(vec![Cmd::DragAndDropToObject(
+            Target::new(Location::Id("card".to_owned())),
+            Target::new(Location::Css("#column".to_owned())),
+        )]);
+        let client = Client::new();
+        let mut runner = Runner::with_backend(client.clone());
+
+        runner.run(&file).await.unwrap();
+
+        let calls = client.calls();
+        assert_eq!(calls[Call::DragAndDrop], 1);
+    }
+
+    #[tokio::test]
+    async fn test_submit() {
+        let file = blank_file(vec![Cmd::Submit(Target::new(Location::Css(
+            "form".to_owned(),
+        )))]);
+        let client = Client::new();
+        let mut runner = Runner::with_backend(client.clone());
+
+        runner.run(&file).await.unwrap();
+
+        let calls = client.calls();
+        assert_eq!(calls[Call::Find], 1);
+        assert_eq!(calls[Call::Submit], 1);
+    }
+
+    #[tokio::test]
+    async fn test_webdriver_visible_dialogs() {
+        let file = blank_file(vec![
+            Cmd::WebdriverAnswerOnVisiblePrompt("answer".to_string()),
+            Cmd::WebdriverChooseOkOnVisibleConfirmation,
+            Cmd::WebdriverChooseCancelOnVisibleConfirmation,
+            Cmd::WebdriverChooseCancelOnVisiblePrompt,
+        ]);
+        let client = Client::new();
+        let mut runner = Runner::with_backend(client.clone());
+
+        runner.run(&file).await.unwrap();
+
+        let calls = client.calls();
+        assert_eq!(calls[Call::AlertSendText], 1);
+        assert_eq!(calls[Call::AlertAccept], 2);
+        assert_eq!(calls[Call::AlertDissmis], 2);
+    }
+
+    #[tokio::test]
+    async fn test_new_window() {
+        let mut file = blank_file(vec![
+            Cmd::StoreWindowHandle("root".to_string()),
+            Cmd::Click(Target::new(Location::Css("a".to_owned()))),
+            Cmd::SelectWindow(WindowLocator::Handle("${popup}".to_string())),
+        ]);
+        file.tests[0].commands[1].opens_window = Some(NewWindow {
+            handle_name: "popup".to_string(),
+            timeout: Some(std::time::Duration::from_secs(1)),
+        });
+        let client = Client::new();
+        let mut runner = Runner::with_backend(client.clone());
+
+        runner.run(&file).await.unwrap();
+
+        assert_eq!(runner.get_value("root"), Some(&serde_json::json!("main")));
+        assert_eq!(runner.get_value("popup"), Some(&serde_json::json!("popup")));
+        let calls = client.calls();
+        assert_eq!(calls[Call::Click], 1);
+        assert_eq!(calls[Call::WindowHandles], 2);
+        assert_eq!(calls[Call::SwitchToWindow], 1);
+    }
+
+    #[tokio::test]
+    async fn test_select_window_by_name() {
+        use crate::testing::{Call, MockDriver, MockElement};
+        use crate::webdriver::Locator;
+
+        let file = blank_file(vec![
+            Cmd::Click(Target::new(Location::Css("a".to_owned()))),
+            Cmd::SelectWindow(WindowLocator::Name("popup".to_string())),
+        ]);
+        let driver = MockDriver::new();
+        driver.add_element(
+            Locator::Css("a".to_string()),
+            MockElement::new().opens_window("popup"),
+        );
+        driver.add_script_result("return window.name;", serde_json::json!("popup"));
+        let mut runner = Runner::with_backend(driver.clone());
+
+        runner.run(&file).await.unwrap();
+        assert!(driver
+            .calls()
+            .contains(&Call::Execute("return window.name;".to_string())));
+
+        let file = blank_file(vec![Cmd::SelectWindow(WindowLocator::Name(
+            "another".to_string(),
+        ))]);
+        let err = runner.run(&file).await.unwrap_err();
+        assert!(matches!(err.kind, RunnerErrorKind::ActionFailed(..)));
+        assert_eq!(
+            driver.calls().last(),
+            Some(&Call::SwitchToWindow("main".to_string()))
+        );
+    }
 
+    #[tokio::test]
+    async fn test_select_serialized_window() {
+        use crate::testing::{Call, MockDriver, MockElement};
+        use crate::webdriver::Locator;
+
+        let mut file = blank_file(vec![
+            Cmd::Click(Target::new(Location::Css("a".to_owned()))),
+            Cmd::SelectWindow(WindowLocator::Handle("${popup}".to_string())),
+            Cmd::SelectWindow(WindowLocator::Serialized("win_ser_local".to_string())),
+            Cmd::SelectWindow(WindowLocator::Serialized("win_ser_1".to_string())),
+        ]);
+        file.tests[0].commands[0].opens_window = Some(NewWindow {
+            handle_name: "popup".to_string(),
+            timeout: Some(std::time::Duration::from_secs(1)),
+        });
+        let driver = MockDriver::new();
+        driver.add_element(
+            Locator::Css("a".to_string()),
+            MockElement::new().opens_window("popup"),
+        );
+        let mut runner = Runner::with_backend(driver.clone());
+
+        let err = runner.run(&file).await.unwrap_err();
+
+        assert_eq!(err.index, 3);
+        assert!(matches!(err.kind, RunnerErrorKind::ActionFailed(..)));
+        let switches = driver
+            .calls()
+            .into_iter()
+            .filter(|call| matches!(call, Call::SwitchToWindow(..)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            switches,
+            vec![
+                Call::SwitchToWindow("popup".to_string()),
+                Call::SwitchToWindow("main".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_select_serialized_window_a_test_started_in() {
+        use crate::testing::{Call, MockDriver, MockElement};
+        use crate::webdriver::Locator;
+
+        let mut file = blank_file(vec![
+            Cmd::Click(Target::new(Location::Css("a".to_owned()))),
+            Cmd::SelectWindow(WindowLocator::Handle("${popup}".to_string())),
+        ]);
+        file.tests[0].commands[0].opens_window = Some(NewWindow {
+            handle_name: "popup".to_string(),
+            timeout: Some(std::time::Duration::from_secs(1)),
+        });
+        let driver = MockDriver::new();
+        driver.add_element(
+            Locator::Css("a".to_string()),
+            MockElement::new().opens_window("popup"),
+        );
+        let mut runner = Runner::with_backend(driver.clone());
+        runner.run(&file).await.unwrap();
+
+        // The next test starts in the popup so it's the local window even though it's not the first one.
+        let file = blank_file(vec![
+            Cmd::SelectWindow(WindowLocator::Handle("main".to_string())),
+            Cmd::SelectWindow(WindowLocator::Serialized("win_ser_local".to_string())),
+        ]);
         runner.run(&file).await.unwrap();
 
+        assert_eq!(
+            driver.calls().last(),
+            Some(&Call::SwitchToWindow("popup".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_suite() {
+        let mut file = File::new(
+            "".into(),
+            "".into(),
+            "".into(),
+            "".into(),
+            vec![
+                blank_test("1", vec![Cmd::Echo("first".to_string())]),
+                blank_test("2", vec![Cmd::Echo("second".to_string())]),
+                blank_test("3", vec![Cmd::Echo("third".to_string())]),
+            ],
+        );
+        file.suites.push(blank_suite(vec!["3", "1"], true));
+
+        let client = Client::new();
+        let mut runner = Runner::with_backend(client.clone());
+
+        let echo_vector: Arc<Mutex<Vec<String>>> = Arc::default();
+        let echo_vector1 = echo_vector.clone();
+        runner.set_echo(move |e| echo_vector1.lock().unwrap().push(e.to_string()));
+
+        runner.run_suite(&file, "suite").await.unwrap();
+
+        assert_eq!(*echo_vector.lock().unwrap(), vec!["third", "first"]);
+        assert_eq!(client.calls()[Call::DeleteCookies], 0);
+        assert_eq!(client.calls()[Call::Goto], 0);
+    }
+
+    #[tokio::test]
+    async fn test_run_suite_without_persistent_session() {
+        let mut file = File::new(
+            "".into(),
+            "".into(),
+            "".into(),
+            "".into(),
+            vec![
+                blank_test(
+                    "1",
+                    vec![Cmd::Store {
+                        var: "var".to_string(),
+                        value: "changed".to_string(),
+                    }],
+                ),
+                blank_test("2", vec![Cmd::Echo("${var}".to_string())]),
+            ],
+        );
+        file.suites.push(blank_suite(vec!["1", "2"], false));
+
+        let client = Client::new();
+        let mut runner = Runner::with_backend(client.clone());
+        runner.save_value("var".to_string(), "initial".into());
+
+        let echo_vector: Arc<Mutex<Vec<String>>> = Arc::default();
+        let echo_vector1 = echo_vector.clone();
+        runner.set_echo(move |e| echo_vector1.lock().unwrap().push(e.to_string()));
+
+        runner.run_suite(&file, "suite").await.unwrap();
+
+        assert_eq!(*echo_vector.lock().unwrap(), vec!["initial"]);
+        assert_eq!(client.calls()[Call::DeleteCookies], 1);
         assert_eq!(client.calls()[Call::Goto], 1);
     }
 
+    #[tokio::test]
+    async fn test_run_suite_timeout() {
+        let mut file = File::new(
+            "".into(),
+            "".into(),
+            "".into(),
+            "".into(),
+            vec![blank_test(
+                "1",
+                vec![Cmd::Pause(std::time::Duration::from_secs(10).into())],
+            )],
+        );
+        let mut suite = blank_suite(vec!["1"], true);
+        suite.timeout = std::time::Duration::from_millis(10);
+        file.suites.push(suite);
+
+        let mut runner = Runner::with_backend(Client::new());
+
+        let err = runner.run_suite(&file, "suite").await.unwrap_err();
+        assert!(matches!(err.kind, RunnerErrorKind::Timeout(..)));
+        assert_eq!(err.test, Some("1".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_run_suite_not_found() {
+        let file = blank_file(vec![]);
+        let mut runner = Runner::with_backend(Client::new());
+
+        let err = runner.run_suite(&file, "suite").await.unwrap_err();
+        assert!(matches!(err.kind, RunnerErrorKind::SuiteNotFound(..)));
+    }
+
+    #[tokio::test]
+    async fn test_run_suite_each() {
+        let mut file = File::new(
+            "".into(),
+            "".into(),
+            "".into(),
+            "".into(),
+            vec![
+                blank_test(
+                    "1",
+                    vec![Cmd::Assert {
+                        var: "var".to_string(),
+                        value: "another value".to_string(),
+                    }],
+                ),
+                blank_test("2", vec![Cmd::Echo("second".to_string())]),
+            ],
+        );
+        file.suites.push(blank_suite(vec!["1", "2"], true));
+
+        let mut runner = Runner::with_backend(Client::new());
+        runner.save_value("var".to_string(), "value".into());
+
+        let mut results = Vec::new();
+        runner
+            .run_suite_each(&file, "suite", |name, _, result| {
+                results.push((name.to_string(), result.map_err(|e| e.index)))
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(
+            results,
+            vec![("1".to_string(), Err(0)), ("2".to_string(), Ok(()))]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_artifacts_on_failure() {
+        let file = File::new(
+            "".into(),
+            "".into(),
+            "".into(),
+            "".into(),
+            vec![blank_test(
+                "log in",
+                vec![
+                    Cmd::Store {
+                        var: "var".to_string(),
+                        value: "value".to_string(),
+                    },
+                    Cmd::Assert {
+                        var: "var".to_string(),
+                        value: "another value".to_string(),
+                    },
+                ],
+            )],
+        );
+        let dir = std::env::temp_dir().join(format!("siderunner-artifacts-{}", std::process::id()));
+        let client = Client::new();
+        let mut runner = Runner::with_backend(client.clone());
+        runner.set_artifacts_dir(&dir);
+
+        let err = runner.run(&file).await.unwrap_err();
+
+        assert_eq!(client.calls()[Call::Screenshot], 1);
+        assert_eq!(client.calls()[Call::PageSource], 1);
+        assert_eq!(
+            err.screenshot.as_deref(),
+            Some(dir.join("1-log_in-1.png").as_path())
+        );
+        assert_eq!(
+            err.page_source.as_deref(),
+            Some(dir.join("1-log_in-1.html").as_path())
+        );
+        assert_eq!(std::fs::read(dir.join("1-log_in-1.png")).unwrap(), b"png");
+        assert_eq!(
+            std::fs::read_to_string(dir.join("1-log_in-1.html")).unwrap(),
+            "<html></html>"
+        );
+
+        let err = runner.run(&file).await.unwrap_err();
+        assert_eq!(
+            err.screenshot.as_deref(),
+            Some(dir.join("2-log_in-1.png").as_path())
+        );
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_artifacts_on_nested_failure() {
+        let file = File::new(
+            "".into(),
+            "".into(),
+            "".into(),
+            "".into(),
+            vec![
+                blank_test("outer", vec![Cmd::RunTest("inner".to_string())]),
+                blank_test(
+                    "inner",
+                    vec![
+                        Cmd::Echo("".to_string()),
+                        Cmd::Assert {
+                            var: "var".to_string(),
+                            value: "value".to_string(),
+                        },
+                    ],
+                ),
+            ],
+        );
+        let dir = std::env::temp_dir().join(format!(
+            "siderunner-nested-artifacts-{}",
+            std::process::id()
+        ));
+        let client = Client::new();
+        let mut runner = Runner::with_backend(client.clone());
+        runner.set_echo(|_| {});
+        runner.set_artifacts_dir(&dir);
+
+        let err = runner.run_test(&file, "outer").await.unwrap_err();
+
+        assert_eq!(client.calls()[Call::Screenshot], 1);
+        assert_eq!(client.calls()[Call::PageSource], 1);
+        assert_eq!(
+            err.screenshot.as_deref(),
+            Some(dir.join("1-inner-1.png").as_path())
+        );
+        assert_eq!(
+            err.page_source.as_deref(),
+            Some(dir.join("1-inner-1.html").as_path())
+        );
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_artifacts_on_suite_timeout() {
+        let mut file = File::new(
+            "".into(),
+            "".into(),
+            "".into(),
+            "".into(),
+            vec![blank_test(
+                "1",
+                vec![Cmd::Pause(std::time::Duration::from_secs(10).into())],
+            )],
+        );
+        let mut suite = blank_suite(vec!["1"], true);
+        suite.timeout = std::time::Duration::from_millis(10);
+        file.suites.push(suite);
+        let dir = std::env::temp_dir().join(format!(
+            "siderunner-timeout-artifacts-{}",
+            std::process::id()
+        ));
+        let client = Client::new();
+        let mut runner = Runner::with_backend(client.clone());
+        runner.set_artifacts_dir(&dir);
+
+        let err = runner.run_suite(&file, "suite").await.unwrap_err();
+
+        assert!(matches!(err.kind, RunnerErrorKind::Timeout(..)));
+        assert_eq!(client.calls()[Call::Screenshot], 1);
+        assert_eq!(
+            err.screenshot.as_deref(),
+            Some(dir.join("1-1-0.png").as_path())
+        );
+        assert_eq!(
+            err.page_source.as_deref(),
+            Some(dir.join("1-1-0.html").as_path())
+        );
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_no_artifacts_by_default() {
+        let file = blank_file(vec![Cmd::VerifyTitle("title".to_string())]);
+        let client = Client::new();
+        let mut runner = Runner::with_backend(client.clone());
+
+        let err = runner.run(&file).await.unwrap_err();
+
+        assert_eq!(client.calls()[Call::Screenshot], 0);
+        assert_eq!(err.screenshot, None);
+        assert_eq!(err.page_source, None);
+    }
+
+    #[tokio::test]
+    async fn test_target_fallback() {
+        use crate::testing::{Call, MockDriver, MockElement};
+        use crate::webdriver::Locator;
+
+        #[derive(Default, Clone)]
+        struct Fallbacks(Arc<Mutex<Vec<String>>>);
+
+        impl RunListener for Fallbacks {
+            fn target_fallback(&mut self, event: &CommandEvent<'_>, target: &Target) {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push(format!("{} {}", event.index, target.location));
+            }
+        }
+
+        let old = Target::new(Location::Css("#old".to_string()));
+        let mut file = blank_file(vec![
+            Cmd::Click(old.clone()),
+            Cmd::AssertText(old.clone(), "Submit".to_string()),
+        ]);
+        let targets = vec![
+            old,
+            Target::new(Location::XPath("//button".to_string())),
+            Target::new(Location::Id("submit".to_string())),
+        ];
+        for command in &mut file.tests[0].commands {
+            command.targets = targets.clone();
+        }
+
+        let driver = MockDriver::new();
+        driver.add_element(
+            Locator::Id("submit".to_string()),
+            MockElement::new().text("Submit"),
+        );
+        let fallbacks = Fallbacks::default();
+        let mut runner = Runner::with_backend(driver.clone());
+        runner.add_listener(fallbacks.clone());
+
+        runner.run(&file).await.unwrap();
+
+        assert_eq!(
+            *fallbacks.0.lock().unwrap(),
+            vec!["0 id=submit", "1 id=submit"]
+        );
+        let calls = driver.calls();
+        assert!(calls.contains(&Call::Click(Locator::Id("submit".to_string()))));
+        // Alternatives are probed in order so the xpath one is checked first.
+        assert!(calls.contains(&Call::FindAll(Locator::XPath("//button".to_string()))));
+    }
+
+    #[tokio::test]
+    async fn test_target_fallback_isnt_used_for_present_element() {
+        use crate::testing::{MockDriver, MockElement};
+        use crate::webdriver::Locator;
+
+        let target = Target::new(Location::Id("header".to_string()));
+        let mut file = blank_file(vec![Cmd::AssertText(target.clone(), "Title".to_string())]);
+        file.tests[0].commands[0].targets =
+            vec![target, Target::new(Location::Css("h1".to_string()))];
+
+        let driver = MockDriver::new();
+        driver.add_element(
+            Locator::Id("header".to_string()),
+            MockElement::new().text("Header"),
+        );
+        driver.add_element(
+            Locator::Css("h1".to_string()),
+            MockElement::new().text("Title"),
+        );
+        let mut runner = Runner::with_backend(driver);
+
+        let err = runner.run(&file).await.unwrap_err();
+        assert!(matches!(err.kind, RunnerErrorKind::AssertFailed { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_element_not_present() {
+        use crate::testing::{MockDriver, MockElement};
+        use crate::webdriver::Locator;
+
+        let file = blank_file(vec![
+            Cmd::AssertElementNotPresent(Target::new(Location::Id("footer".to_string()))),
+            Cmd::VerifyElementNotPresent(Target::new(Location::Id("footer".to_string()))),
+            Cmd::VerifyElementNotPresent(Target::new(Location::Id("header".to_string()))),
+        ]);
+
+        let driver = MockDriver::new();
+        driver.add_element(Locator::Id("header".to_string()), MockElement::new());
+        let mut runner = Runner::with_backend(driver);
+
+        let err = runner.run(&file).await.unwrap_err();
+        assert_eq!(err.index, 2);
+        assert!(matches!(err.kind, RunnerErrorKind::VerificationFailed(..)));
+    }
+
+    #[tokio::test]
+    async fn test_healed_targets() {
+        use crate::healing::HealedTarget;
+        use crate::testing::{MockDriver, MockElement};
+        use crate::webdriver::Locator;
+
+        let old = Target::new(Location::Css("#old".to_string()));
+        let new = Target::new(Location::Id("submit".to_string()));
+        let mut file = blank_file(vec![
+            Cmd::Click(old.clone()),
+            Cmd::Click(old.clone()),
+            Cmd::Click(new.clone()),
+        ]);
+        for command in &mut file.tests[0].commands {
+            command.targets = vec![old.clone(), new.clone()];
+        }
+
+        let driver = MockDriver::new();
+        driver.add_element(Locator::Id("submit".to_string()), MockElement::new());
+
+        let mut runner = Runner::with_backend(driver.clone());
+        runner.run(&file).await.unwrap();
+        assert!(runner.get_healed_targets().is_empty());
+
+        runner.set_healing(true);
+        runner.run(&file).await.unwrap();
+        runner.run(&file).await.unwrap();
+
+        let healed = |index: usize| HealedTarget {
+            test: String::new(),
+            index,
+            id: String::new(),
+            target: old.clone(),
+            alternative: new.clone(),
+        };
+        assert_eq!(runner.get_healed_targets(), &[healed(0), healed(1)][..]);
+    }
+
+    #[tokio::test]
+    async fn test_variables_in_params() {
+        use crate::testing::{Call, MockDriver, MockElement};
+        use crate::webdriver::Locator;
+
+        let target = Target::new(Param::Incomplete("id=${id}".to_string()));
+        let file = blank_file(vec![
+            Cmd::Store {
+                var: "id".to_string(),
+                value: "submit".to_string(),
+            },
+            Cmd::Store {
+                var: "locator".to_string(),
+                value: "id=submit".to_string(),
+            },
+            Cmd::MouseOver(Target::new(Param::Incomplete("${locator}".to_string()))),
+            Cmd::Store {
+                var: "x".to_string(),
+                value: "10".to_string(),
+            },
+            Cmd::Click(target.clone()),
+            Cmd::ClickAt(target.clone(), Param::Incomplete("${x},-5".to_string())),
+            Cmd::Type(target.clone(), "${x} items".to_string()),
+            Cmd::SetWindowSize(Param::Incomplete("${x}x${x}".to_string())),
+            Cmd::Pause(Param::Incomplete("${x}".to_string())),
+        ]);
+
+        let driver = MockDriver::new();
+        driver.add_element(Locator::Id("submit".to_string()), MockElement::new());
+
+        let mut runner = Runner::with_backend(driver.clone());
+        runner.run(&file).await.unwrap();
+
+        let calls = driver.calls();
+        let submit = Locator::Id("submit".to_string());
+        assert!(calls.contains(&Call::MouseOver(submit.clone())));
+        assert!(calls.contains(&Call::Click(submit.clone())));
+        assert!(calls.contains(&Call::ClickAt(submit.clone(), (10, -5))));
+        assert!(calls.contains(&Call::SendKeys(submit, "10 items".to_string())));
+        assert!(calls.contains(&Call::SetWindowSize(10, 10)));
+    }
+
+    #[tokio::test]
+    async fn test_special_keys() {
+        use crate::testing::{Call, MockDriver, MockElement};
+        use crate::webdriver::Locator;
+
+        let target = Target::new(Location::Id("q".to_string()));
+        let file = blank_file(vec![
+            Cmd::Store {
+                var: "query".to_string(),
+                value: "rust".to_string(),
+            },
+            Cmd::SendKeys(target.clone(), "${query}${KEY_ENTER}".to_string()),
+            Cmd::Type(
+                target.clone(),
+                "${KEY_SHIFT}a${KEY_TAB}${KEY_UNKNOWN}".to_string(),
+            ),
+        ]);
+
+        let driver = MockDriver::new();
+        driver.add_element(Locator::Id("q".to_string()), MockElement::new());
+
+        let mut runner = Runner::with_backend(driver.clone());
+        runner.run(&file).await.unwrap();
+
+        let calls = driver.calls();
+        let q = Locator::Id("q".to_string());
+        assert!(calls.contains(&Call::SendKeys(q.clone(), "rust\u{E007}".to_string())));
+        assert!(calls.contains(&Call::SendKeys(
+            q,
+            "\u{E008}a\u{E004}${KEY_UNKNOWN}".to_string()
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_variables_in_params_are_checked() {
+        let file = blank_file(vec![
+            Cmd::Store {
+                var: "timeout".to_string(),
+                value: "soon".to_string(),
+            },
+            Cmd::Pause(Param::Incomplete("${timeout}".to_string())),
+        ]);
+
+        let mut runner = Runner::with_backend(Client::new());
+        let err = runner.run(&file).await.unwrap_err();
+        assert_eq!(err.index, 1);
+        assert!(matches!(err.kind, RunnerErrorKind::MismatchedType(..)));
+
+        let file = blank_file(vec![
+            Cmd::Store {
+                var: "locator".to_string(),
+                value: "submit".to_string(),
+            },
+            Cmd::Click(Target::new(Param::Incomplete("${locator}".to_string()))),
+        ]);
+        let err = runner.run(&file).await.unwrap_err();
+        assert_eq!(err.index, 1);
+        assert!(matches!(err.kind, RunnerErrorKind::MismatchedType(..)));
+
+        let file = blank_file(vec![
+            Cmd::Store {
+                var: "n".to_string(),
+                value: "many".to_string(),
+            },
+            Cmd::Times(Param::Incomplete("${n}".to_string())),
+            Cmd::Echo("".to_string()),
+            Cmd::End,
+        ]);
+        let err = runner.run(&file).await.unwrap_err();
+        assert_eq!(err.index, 1);
+        assert!(matches!(err.kind, RunnerErrorKind::MismatchedType(..)));
+    }
+
+    #[tokio::test]
+    async fn test_listener() {
+        #[derive(Default, Clone)]
+        struct Log(Arc<Mutex<Vec<String>>>);
+
+        impl RunListener for Log {
+            fn test_started(&mut self, test: &Test) {
+                self.0.lock().unwrap().push(format!("test {:?}", test.name));
+            }
+
+            fn test_finished(
+                &mut self,
+                test: &Test,
+                _: std::time::Duration,
+                result: Result<(), &RunnerError>,
+            ) {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push(format!("test {:?} ok={}", test.name, result.is_ok()));
+            }
+
+            fn command_started(&mut self, event: &CommandEvent<'_>) {
+                self.0.lock().unwrap().push(format!(
+                    "command {} {} {:?} {:?}",
+                    event.index, event.name, event.target, event.value
+                ));
+            }
+
+            fn command_finished(
+                &mut self,
+                event: &CommandEvent<'_>,
+                _: std::time::Duration,
+                result: Result<(), &RunnerErrorKind>,
+            ) {
+                self.0.lock().unwrap().push(format!(
+                    "command {} ok={}",
+                    event.index,
+                    result.is_ok()
+                ));
+            }
+
+            fn variable_stored(&mut self, name: &str, value: &serde_json::Value) {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push(format!("variable {} {}", name, value));
+            }
+
+            fn loop_iteration(&mut self, event: &CommandEvent<'_>, iteration: usize) {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push(format!("loop {} {}", event.index, iteration));
+            }
+
+            fn error(&mut self, error: &RunnerError) {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push(format!("error {}", error.index));
+            }
+        }
+
+        let file = blank_file(vec![
+            Cmd::Store {
+                var: "name".to_string(),
+                value: "siderunner".to_string(),
+            },
+            Cmd::Times(2.into()),
+            Cmd::Echo("${name}".to_string()),
+            Cmd::End,
+            Cmd::Assert {
+                var: "name".to_string(),
+                value: "selenium".to_string(),
+            },
+        ]);
+        let log = Log::default();
+        let mut runner = Runner::with_backend(Client::new());
+        runner.set_echo(|_| {});
+        runner.add_listener(log.clone());
+
+        assert!(runner.run(&file).await.is_err());
+
+        let log = log.0.lock().unwrap().clone();
+        assert_eq!(
+            log,
+            vec![
+                r#"test """#,
+                r#"command 0 store "siderunner" "name""#,
+                r#"variable name "siderunner""#,
+                "command 0 ok=true",
+                "loop 1 1",
+                r#"command 2 echo "siderunner" """#,
+                "command 2 ok=true",
+                "loop 1 2",
+                r#"command 2 echo "siderunner" """#,
+                "command 2 ok=true",
+                r#"command 4 assert "name" "selenium""#,
+                "command 4 ok=false",
+                "error 4",
+                r#"test "" ok=false"#,
+            ]
+        );
+    }
+
+    fn blank_test(name: &str, commands: Vec<Cmd>) -> Test {
+        Test {
+            id: name.to_string(),
+            name: name.to_string(),
+            commands: commands.into_iter().map(blank_cmd).collect(),
+        }
+    }
+
+    fn blank_suite(tests: Vec<&str>, persist_session: bool) -> Suite {
+        Suite {
+            id: String::new(),
+            name: "suite".to_string(),
+            persist_session,
+            parallel: false,
+            timeout: std::time::Duration::from_secs(300),
+            tests: tests.into_iter().map(String::from).collect(),
+        }
+    }
+
     fn blank_file(commands: Vec<Cmd>) -> File {
         let commands = commands.into_iter().map(blank_cmd).collect();
 
@@ -716,6 +1717,9 @@ mod flow {
                 name: String::new(),
                 commands,
             }],
+            suites: Vec::new(),
+            urls: Vec::new(),
+            plugins: Vec::new(),
         }
     }
 
@@ -789,7 +1793,7 @@ mod flow {
         }
 
         #[async_trait::async_trait]
-        impl<'a> Webdriver for Arc<Client> {
+        impl Webdriver for Arc<Client> {
             type Element = Element;
 
             async fn goto(&mut self, url: &str) -> Result<(), RunnerErrorKind> {
@@ -894,6 +1898,11 @@ mod flow {
                 Ok(())
             }
 
+            async fn delete_cookies(&mut self) -> Result<(), RunnerErrorKind> {
+                self.inc(Call::DeleteCookies);
+                Ok(())
+            }
+
             async fn alert_text(&mut self) -> Result<String, RunnerErrorKind> {
                 self.inc(Call::AlertText);
                 Ok("".to_string())
@@ -909,6 +1918,11 @@ mod flow {
                 Ok(())
             }
 
+            async fn alert_send_text(&mut self, _: &str) -> Result<(), RunnerErrorKind> {
+                self.inc(Call::AlertSendText);
+                Ok(())
+            }
+
             async fn double_click(&mut self, _: Locator) -> Result<(), RunnerErrorKind> {
                 self.inc(Call::DoubleClick);
                 Ok(())
@@ -924,6 +1938,52 @@ mod flow {
                 Ok(())
             }
 
+            async fn mouse_over(&mut self, _: Locator) -> Result<(), RunnerErrorKind> {
+                self.inc(Call::MouseOver);
+                Ok(())
+            }
+
+            async fn mouse_out(&mut self, _: Locator) -> Result<(), RunnerErrorKind> {
+                self.inc(Call::MouseOut);
+                Ok(())
+            }
+
+            async fn mouse_move_at(
+                &mut self,
+                _: Locator,
+                _: (i32, i32),
+            ) -> Result<(), RunnerErrorKind> {
+                self.inc(Call::MouseMoveAt);
+                Ok(())
+            }
+
+            async fn mouse_down_at(
+                &mut self,
+                _: Locator,
+                _: (i32, i32),
+            ) -> Result<(), RunnerErrorKind> {
+                self.inc(Call::MouseDownAt);
+                Ok(())
+            }
+
+            async fn mouse_up_at(
+                &mut self,
+                _: Locator,
+                _: (i32, i32),
+            ) -> Result<(), RunnerErrorKind> {
+                self.inc(Call::MouseUpAt);
+                Ok(())
+            }
+
+            async fn drag_and_drop(
+                &mut self,
+                _: Locator,
+                _: Locator,
+            ) -> Result<(), RunnerErrorKind> {
+                self.inc(Call::DragAndDrop);
+                Ok(())
+            }
+
             async fn title(&mut self) -> Result<String, RunnerErrorKind> {
                 self.inc(Call::Title);
                 Ok(String::new())
@@ -942,6 +2002,57 @@ mod flow {
                 self.inc(Call::DoubleClickAt);
                 Ok(())
             }
+
+            async fn switch_to_default_content(&mut self) -> Result<(), RunnerErrorKind> {
+                self.inc(Call::SwitchToDefaultContent);
+                Ok(())
+            }
+
+            async fn switch_to_parent_frame(&mut self) -> Result<(), RunnerErrorKind> {
+                self.inc(Call::SwitchToParentFrame);
+                Ok(())
+            }
+
+            async fn switch_to_frame(&mut self, _: u16) -> Result<(), RunnerErrorKind> {
+                self.inc(Call::SwitchToFrame);
+                Ok(())
+            }
+
+            async fn switch_to_frame_element(&mut self, _: Locator) -> Result<(), RunnerErrorKind> {
+                self.inc(Call::SwitchToFrameElement);
+                Ok(())
+            }
+
+            async fn window_handles(&mut self) -> Result<Vec<String>, RunnerErrorKind> {
+                self.inc(Call::WindowHandles);
+                // a new window appears after the first call
+                if self.calls()[Call::WindowHandles] > 1 {
+                    Ok(vec!["main".to_string(), "popup".to_string()])
+                } else {
+                    Ok(vec!["main".to_string()])
+                }
+            }
+
+            async fn current_window_handle(&mut self) -> Result<String, RunnerErrorKind> {
+                self.inc(Call::CurrentWindowHandle);
+                Ok("main".to_string())
+            }
+
+            async fn switch_to_window(&mut self, handle: &str) -> Result<(), RunnerErrorKind> {
+                assert_eq!(handle, "popup");
+                self.inc(Call::SwitchToWindow);
+                Ok(())
+            }
+
+            async fn screenshot(&mut self) -> Result<Vec<u8>, RunnerErrorKind> {
+                self.inc(Call::Screenshot);
+                Ok(b"png".to_vec())
+            }
+
+            async fn page_source(&mut self) -> Result<String, RunnerErrorKind> {
+                self.inc(Call::PageSource);
+                Ok("<html></html>".to_owned())
+            }
         }
 
         pub struct Element(Arc<Client>);
@@ -1004,6 +2115,11 @@ mod flow {
                 Ok(())
             }
 
+            async fn submit(mut self) -> Result<(), RunnerErrorKind> {
+                self.inc(Call::Submit);
+                Ok(())
+            }
+
             async fn select_by_label(mut self, _: &str) -> Result<Self::Driver, RunnerErrorKind> {
                 self.inc(Call::SelectByLabel);
                 Ok(self.0)
@@ -1040,6 +2156,7 @@ mod flow {
             Exec,
             ExecAsync,
             Close,
+            DeleteCookies,
             CurrentUrl,
             SetWSize,
             W8Visib,
@@ -1062,19 +2179,36 @@ mod flow {
             SendKeys,
             MouseDown,
             MouseUp,
+            MouseOver,
+            MouseOut,
+            MouseMoveAt,
+            MouseDownAt,
+            MouseUpAt,
+            DragAndDrop,
+            AlertSendText,
+            Submit,
             Title,
             IsSelected,
             IsPresent,
             IsEnabled,
             ClickAt,
             DoubleClickAt,
+            SwitchToDefaultContent,
+            SwitchToParentFrame,
+            SwitchToFrame,
+            SwitchToFrameElement,
+            WindowHandles,
+            CurrentWindowHandle,
+            SwitchToWindow,
+            Screenshot,
+            PageSource,
         }
 
         impl Index<Call> for CallCount {
             type Output = usize;
 
             fn index(&self, call: Call) -> &Self::Output {
-                self.inner.get(&call).unwrap()
+                self.inner.get(&call).unwrap_or(&0)
             }
         }
 
@@ -1085,7 +2219,3 @@ mod flow {
         }
     }
 }
-
-fn blank_cmd(cmd: Cmd) -> Command {
-    Command::new("".to_owned(), "".to_owned(), cmd)
-}
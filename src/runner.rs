@@ -8,23 +8,35 @@ use crate::command::{
     AssertNotChecked, AssertNotSelectedValue, AssertNotText, AssertSelectedLabel,
     AssertSelectedValue, AssertText, AssertTitle, AssertValue, Check,
     ChooseCancelOnNextConfirmation, ChooseCancelOnNextPrompt, ChooseOkOnNextConfirmation, Click,
-    ClickAt, Close, DoubleClick, DoubleClickAt, Echo, EditContent, Execute, ExecuteAsync,
-    MouseDown, MouseUp, Open, Pause, RemoveSelection, RunScript, Select, SendKeys, SetWindowSize,
-    Store, StoreAttribute, StoreJson, StoreText, StoreTitle, StoreValue, StoreXpathCount, Type,
-    UnCheck, WaitForElementEditable, WaitForElementNotEditable, WaitForElementNotPresent,
-    WaitForElementNotVisible, WaitForElementPresent, WaitForElementVisible,
+    ClickAt, Close, DoubleClick, DoubleClickAt, DragAndDropToObject, Echo, EditContent, Execute,
+    ExecuteAsync, MouseDown, MouseDownAt, MouseMoveAt, MouseOut, MouseOver, MouseUp, MouseUpAt,
+    Open, Pause, RemoveSelection, RunScript, Select, SelectFrame, SelectWindow, SendKeys,
+    SetWindowSize, Store, StoreAttribute, StoreJson, StoreText, StoreTitle, StoreValue,
+    StoreWindowHandle, StoreXpathCount, Submit, Type, UnCheck, WaitForElementEditable,
+    WaitForElementNotEditable, WaitForElementNotPresent, WaitForElementNotVisible,
+    WaitForElementPresent, WaitForElementVisible, WebdriverAnswerOnVisiblePrompt,
+    WebdriverChooseCancelOnVisibleConfirmation, WebdriverChooseCancelOnVisiblePrompt,
+    WebdriverChooseOkOnVisibleConfirmation,
 };
 use crate::command::{AssertPrompt, Command as Cmd1};
-use crate::parser::{SelectLocator, Target};
+use crate::healing::HealedTarget;
+use crate::keys::key_code;
+use crate::listener::RunListener;
+use crate::parser::{
+    Argument, NewWindow, Param, SelectLocator, Suite, Target, DEFAULT_WINDOW_TIMEOUT,
+};
 use crate::playground::Playground;
-use crate::webdriver::{Locator, Webdriver};
+use crate::webdriver::{IntoWebdriver, Locator, Webdriver};
 use crate::File;
 use crate::{
     error::{RunnerError, RunnerErrorKind},
     parser::{Cmd, Location},
 };
+use futures::future::{self, Either};
 use serde_json::Value;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 /// A runtime for running test
 ///
@@ -34,15 +46,43 @@ pub struct Runner<D> {
     webdriver: D,
     data: HashMap<String, Value>,
     echo_hook: Box<dyn Fn(&str) + Send>,
+    soft_failures: Vec<RunnerError>,
+    listeners: Vec<Box<dyn RunListener + Send>>,
+    artifacts_dir: Option<PathBuf>,
+    artifacts_count: usize,
+    nested_artifacts: Option<Artifacts>,
+    healed_targets: Option<Vec<HealedTarget>>,
+    start_window: Option<String>,
 }
 
 impl<D> Runner<D> {
-    /// Create a new Runner which uses a client as a Backend
-    pub(crate) fn _new(client: D) -> Runner<D> {
+    /// Create a new runner which uses a client of one of the supported backends.
+    ///
+    /// It's either `&thirtyfour::WebDriver` or `siderunner::webdriver::fantoccini::Client`.
+    pub fn new<C>(client: C) -> Runner<D>
+    where
+        C: IntoWebdriver<Webdriver = D>,
+    {
+        Self::with_backend(client.into_webdriver())
+    }
+
+    /// Create a new runner which uses a custom backend.
+    ///
+    /// It's an extension point for backends which aren't supported out of the box,
+    /// e.g. a proxy over another driver or a fake one for tests.
+    /// See [`Webdriver`](crate::webdriver::Webdriver).
+    pub fn with_backend(client: D) -> Runner<D> {
         Self {
             webdriver: client,
             data: HashMap::new(),
             echo_hook: Box::new(|s| println!("{}", s)),
+            soft_failures: Vec::new(),
+            listeners: Vec::new(),
+            artifacts_dir: None,
+            artifacts_count: 0,
+            nested_artifacts: None,
+            healed_targets: None,
+            start_window: None,
         }
     }
 
@@ -50,6 +90,7 @@ impl<D> Runner<D> {
     ///
     /// All tests which will be run afterwards will able to work with the saved variable.
     pub fn save_value(&mut self, var: String, value: Value) {
+        self.notify(|listener| listener.variable_stored(&var, &value));
         self.data.insert(var, value);
     }
 
@@ -63,6 +104,48 @@ impl<D> Runner<D> {
         self.echo_hook = Box::new(func);
     }
 
+    /// Adds a listener which is notified about events of the following runs.
+    ///
+    /// Listeners are notified in the order they were added.
+    pub fn add_listener<L: RunListener + Send + 'static>(&mut self, listener: L) {
+        self.listeners.push(Box::new(listener));
+    }
+
+    /// Sets a directory where a screenshot and a source of a page are saved when a command fails.
+    ///
+    /// Files are named after a number of the failure within the runner, a test and an index of the failed command,
+    /// e.g. `1-login-3.png` and `1-login-3.html`.
+    /// Their paths are attached to the error.
+    pub fn set_artifacts_dir<P: Into<PathBuf>>(&mut self, dir: P) {
+        self.artifacts_dir = Some(dir.into());
+    }
+
+    /// Turns on tracking of commands which were run with an alternative target.
+    ///
+    /// See [`healing`](crate::healing).
+    pub fn set_healing(&mut self, enabled: bool) {
+        self.healed_targets = if enabled { Some(Vec::new()) } else { None };
+    }
+
+    /// Gets a list of commands which were run with an alternative target
+    /// because an element couldn't be found by their own one.
+    ///
+    /// It's populated only if healing is turned on by [`Runner::set_healing`].
+    pub fn get_healed_targets(&self) -> &[HealedTarget] {
+        self.healed_targets.as_deref().unwrap_or_default()
+    }
+
+    pub(crate) fn add_healed_target(&mut self, healed: HealedTarget) {
+        if let Some(healed_targets) = &mut self.healed_targets {
+            let known = healed_targets
+                .iter()
+                .any(|h| h.test == healed.test && h.index == healed.index);
+            if !known {
+                healed_targets.push(healed);
+            }
+        }
+    }
+
     /// Gets a list of variables which were collected over the runs.
     pub fn get_data(&self) -> &HashMap<String, Value> {
         &self.data
@@ -73,6 +156,33 @@ impl<D> Runner<D> {
         &mut self.webdriver
     }
 
+    /// Gets a list of failed `verify*` commands of the last run.
+    ///
+    /// Verifications don't stop a test so they're only collected here.
+    /// A successful run returns them as [`RunnerErrorKind::VerificationFailed`] error,
+    /// so the list is only populated if a run was stopped by another error.
+    pub fn get_soft_failures(&self) -> &[RunnerError] {
+        &self.soft_failures
+    }
+
+    pub(crate) fn add_soft_failure(&mut self, err: RunnerError) {
+        self.notify(|listener| listener.error(&err));
+        self.soft_failures.push(err);
+    }
+
+    /// Saves a value which is used to keep a state of control flow.
+    ///
+    /// Unlike [`Runner::save_value`] it's not reported to listeners.
+    pub(crate) fn save_internal_value(&mut self, var: String, value: Value) {
+        self.data.insert(var, value);
+    }
+
+    pub(crate) fn notify<F: FnMut(&mut dyn RunListener)>(&mut self, mut f: F) {
+        for listener in &mut self.listeners {
+            f(listener.as_mut());
+        }
+    }
+
     pub(crate) fn get_value_mut(&mut self, var: &str) -> Option<&mut Value> {
         self.data.get_mut(var)
     }
@@ -80,6 +190,11 @@ impl<D> Runner<D> {
     pub(crate) fn echo(&self, message: &str) {
         self.echo_hook.as_ref()(message)
     }
+
+    /// A handle of the window the running test was started in.
+    pub(crate) fn get_start_window(&self) -> Option<&str> {
+        self.start_window.as_deref()
+    }
 }
 
 impl<D> Runner<D>
@@ -95,11 +210,13 @@ where
 
     /// Run all tests in a side file starting from first test.
     pub async fn run(&mut self, file: &File) -> Result<(), RunnerError> {
+        self.soft_failures.clear();
         for test in 0..file.tests.len() {
-            self.run_test_by_index(file, test).await?;
+            self.save_start_window().await;
+            Playground::run_test(self, file, test).await?;
         }
 
-        Ok(())
+        self.check_soft_failures()
     }
 
     /// Run a particular test in a file.
@@ -119,7 +236,189 @@ where
         file: &File,
         index: usize,
     ) -> Result<(), RunnerError> {
-        Playground::run_test(self, file, index).await
+        self.soft_failures.clear();
+        self.save_start_window().await;
+        Playground::run_test(self, file, index).await?;
+        self.check_soft_failures()
+    }
+
+    /// Run a particular suite in a file.
+    ///
+    /// Tests are run in the order they are listed in the suite
+    /// and each of them is limited by the suite's timeout.
+    ///
+    /// If the suite doesn't persist a session, cookies are deleted,
+    /// the browser is navigated to a blank page and variables are restored
+    /// to the state they had before the suite was started between tests.
+    pub async fn run_suite<S: AsRef<str>>(
+        &mut self,
+        file: &File,
+        suite: S,
+    ) -> Result<(), RunnerError> {
+        let suite = look_up_suite(file, suite).map_err(|e| RunnerError::new(e, 0))?;
+        self.soft_failures.clear();
+        let data = self.data.clone();
+        for i in 0..suite.tests.len() {
+            self.run_suite_test(file, suite, i, &data).await?;
+        }
+
+        self.check_soft_failures()
+    }
+
+    /// Run a particular suite in a file and report each test of it.
+    ///
+    /// Tests are run the way [`Runner::run_suite`] runs them,
+    /// but a failed test doesn't stop the suite.
+    /// A name of each test, a time it took and its result are passed to `report`.
+    pub async fn run_suite_each<S, F>(
+        &mut self,
+        file: &File,
+        suite: S,
+        mut report: F,
+    ) -> Result<(), RunnerError>
+    where
+        S: AsRef<str>,
+        F: FnMut(&str, Duration, Result<(), RunnerError>),
+    {
+        let suite = look_up_suite(file, suite).map_err(|e| RunnerError::new(e, 0))?;
+        let data = self.data.clone();
+        for (i, test_id) in suite.tests.iter().enumerate() {
+            self.soft_failures.clear();
+            let start = Instant::now();
+            let result = match self.run_suite_test(file, suite, i, &data).await {
+                Ok(()) => self.check_soft_failures(),
+                Err(e) => Err(e),
+            };
+
+            let name = look_up_test_by_id(file, test_id)
+                .map(|index| file.tests[index].name.as_str())
+                .unwrap_or(test_id);
+            report(name, start.elapsed(), result);
+        }
+
+        Ok(())
+    }
+
+    /// Runs an i-th test of a suite, `data` is the state variables are restored to
+    /// if the suite doesn't persist a session.
+    async fn run_suite_test(
+        &mut self,
+        file: &File,
+        suite: &Suite,
+        i: usize,
+        data: &HashMap<String, Value>,
+    ) -> Result<(), RunnerError> {
+        let index =
+            look_up_test_by_id(file, &suite.tests[i]).map_err(|e| RunnerError::new(e, 0))?;
+
+        if i > 0 && !suite.persist_session {
+            self.reset_session(data.clone()).await.map_err(|e| {
+                let mut e = RunnerError::new(e, 0);
+                e.test = Some(file.tests[index].name.clone());
+                e
+            })?;
+        }
+
+        self.save_start_window().await;
+        self.run_test_with_timeout(file, index, suite.timeout).await
+    }
+
+    fn check_soft_failures(&mut self) -> Result<(), RunnerError> {
+        if self.soft_failures.is_empty() {
+            return Ok(());
+        }
+
+        let failures = std::mem::take(&mut self.soft_failures);
+        let index = failures[0].index;
+        let test = failures[0].test.clone();
+        let command = failures[0].command.clone();
+        let mut err = RunnerError::new(RunnerErrorKind::VerificationFailed(failures), index);
+        err.test = test;
+        err.command = command;
+        Err(err)
+    }
+
+    async fn run_test_with_timeout(
+        &mut self,
+        file: &File,
+        index: usize,
+        timeout: Duration,
+    ) -> Result<(), RunnerError> {
+        let test = Box::pin(Playground::run_test(self, file, index));
+        let timer = futures_timer::Delay::new(timeout);
+        if let Either::Left((result, _)) = future::select(test, timer).await {
+            return result;
+        }
+
+        let test = &file.tests[index];
+        let mut e = RunnerError::new(
+            RunnerErrorKind::Timeout(format!("the test exceeded the suite timeout {:?}", timeout)),
+            0,
+        );
+        e.test = Some(test.name.clone());
+        self.capture_artifacts(&mut e).await;
+        self.notify(|listener| {
+            listener.error(&e);
+            listener.test_finished(test, timeout, Err(&e));
+        });
+        Err(e)
+    }
+
+    /// Saves a screenshot and a source of the current page for a failed command
+    /// if an artifacts directory is set.
+    ///
+    /// Artifacts which can't be taken or saved are skipped so the original error isn't lost.
+    /// A failure of a test run by `run` reuses artifacts captured for the failed command of that test.
+    pub(crate) async fn capture_artifacts(&mut self, err: &mut RunnerError) {
+        if let Some((screenshot, page_source)) = self.nested_artifacts.take() {
+            err.screenshot = screenshot;
+            err.page_source = page_source;
+            return;
+        }
+
+        let dir = match &self.artifacts_dir {
+            Some(dir) => dir.clone(),
+            None => return,
+        };
+        if std::fs::create_dir_all(&dir).is_err() {
+            return;
+        }
+
+        self.artifacts_count += 1;
+        let test = err.test.as_deref().unwrap_or_default();
+        let name = format!(
+            "{}-{}-{}",
+            self.artifacts_count,
+            artifact_name(test),
+            err.index
+        );
+
+        if let Ok(png) = self.webdriver.screenshot().await {
+            let path = dir.join(format!("{}.png", name));
+            if std::fs::write(&path, png).is_ok() {
+                err.screenshot = Some(path.into_boxed_path());
+            }
+        }
+
+        if let Ok(source) = self.webdriver.page_source().await {
+            let path = dir.join(format!("{}.html", name));
+            if std::fs::write(&path, source).is_ok() {
+                err.page_source = Some(path.into_boxed_path());
+            }
+        }
+    }
+
+    /// Remembers the window a test is started in, which Selenium IDE refers to as `win_ser_local`.
+    ///
+    /// If the handle can't be got the test is run anyway and only selecting that window fails.
+    async fn save_start_window(&mut self) {
+        self.start_window = self.webdriver.current_window_handle().await.ok();
+    }
+
+    async fn reset_session(&mut self, data: HashMap<String, Value>) -> Result<(), RunnerErrorKind> {
+        self.data = data;
+        self.webdriver.delete_cookies().await?;
+        self.webdriver.goto("about:blank").await
     }
 
     #[async_recursion::async_recursion]
@@ -128,15 +427,14 @@ where
         file: &File,
         cmd: &Cmd,
     ) -> Result<(), RunnerErrorKind> {
-        // TODO: emit variables in value field too
         match cmd {
-            Cmd::Open(url) => Open::new(url.clone(), file.url.clone()).run(self).await,
+            Cmd::Open(url) => Open::new(self.emit(url), file.url.clone()).run(self).await,
             Cmd::StoreText { var, target, .. } => {
-                StoreText::new(target.clone().into(), var.to_owned())
+                StoreText::new(self.locator(target)?, var.to_owned())
                     .run(self)
                     .await
             }
-            Cmd::Store { var, value } => Store::new(var.clone(), value.clone()).run(self).await,
+            Cmd::Store { var, value } => Store::new(var.clone(), self.emit(value)).run(self).await,
             Cmd::Execute { script, var } => {
                 Execute::new(script.clone(), var.clone()).run(self).await
             }
@@ -145,140 +443,150 @@ where
                     .run(self)
                     .await
             }
-            Cmd::Echo(text) => Echo::new(text.clone()).run(self).await,
+            Cmd::Echo(text) => Echo::new(self.emit(text)).run(self).await,
             Cmd::WaitForElementVisible { timeout, target } => {
-                WaitForElementVisible::new(target.clone().into(), *timeout)
+                WaitForElementVisible::new(self.locator(target)?, self.resolve(timeout)?)
                     .run(self)
                     .await
             }
             Cmd::WaitForElementNotVisible { timeout, target } => {
-                WaitForElementNotVisible::new(target.clone().into(), *timeout)
+                WaitForElementNotVisible::new(self.locator(target)?, self.resolve(timeout)?)
                     .run(self)
                     .await
             }
             Cmd::WaitForElementPresent { timeout, target } => {
-                WaitForElementPresent::new(target.clone().into(), *timeout)
+                WaitForElementPresent::new(self.locator(target)?, self.resolve(timeout)?)
                     .run(self)
                     .await
             }
             Cmd::WaitForElementNotPresent { timeout, target } => {
-                WaitForElementNotPresent::new(target.clone().into(), *timeout)
+                WaitForElementNotPresent::new(self.locator(target)?, self.resolve(timeout)?)
                     .run(self)
                     .await
             }
             Cmd::WaitForElementEditable { timeout, target } => {
-                WaitForElementEditable::new(target.clone().into(), *timeout)
+                WaitForElementEditable::new(self.locator(target)?, self.resolve(timeout)?)
                     .run(self)
                     .await
             }
             Cmd::WaitForElementNotEditable { timeout, target } => {
-                WaitForElementNotEditable::new(target.clone().into(), *timeout)
+                WaitForElementNotEditable::new(self.locator(target)?, self.resolve(timeout)?)
                     .run(self)
                     .await
             }
             Cmd::Select { locator, target } => {
-                Select::new(target.clone().into(), locator.clone())
+                Select::new(self.locator(target)?, locator.map(|s| self.emit(s)))
                     .run(self)
                     .await
             }
             Cmd::RemoveSelection(target, locator) => {
-                RemoveSelection::new(target.clone().into(), locator.clone())
+                RemoveSelection::new(self.locator(target)?, self.emit(locator))
                     .run(self)
                     .await
             }
             Cmd::AddSelection(target, locator) => {
-                Select::new(target.clone().into(), SelectLocator::Label(locator.clone()))
-                    .run(self)
-                    .await
+                Select::new(
+                    self.locator(target)?,
+                    SelectLocator::Label(self.emit(locator)),
+                )
+                .run(self)
+                .await
+            }
+            Cmd::Click(target) => Click::new(self.locator(target)?).run(self).await,
+            Cmd::Pause(timeout) => Pause::new(self.resolve(timeout)?).run(self).await,
+            Cmd::SetWindowSize(size) => {
+                let (width, height) = self.resolve(size)?;
+                SetWindowSize::new(width, height).run(self).await
             }
-            Cmd::Click(target) => Click::new(target.clone().into()).run(self).await,
-            Cmd::Pause(timeout) => Pause::new(*timeout).run(self).await,
-            Cmd::SetWindowSize(w, h) => SetWindowSize::new(*w, *h).run(self).await,
             Cmd::StoreXpathCount { var, xpath } => {
-                StoreXpathCount::new(xpath.clone(), var.clone())
+                StoreXpathCount::new(self.emit(xpath), var.clone())
                     .run(self)
                     .await
             }
             Cmd::Close => Close.run(self).await,
-            Cmd::Assert { var, value } => Assert::new(var.clone(), value.clone()).run(self).await,
+            Cmd::Assert { var, value } => {
+                Assert::new(var.clone(), self.emit(value)).run(self).await
+            }
             Cmd::RunScript { script } => RunScript::new(script.clone()).run(self).await,
             Cmd::AnswerOnNextPrompt(message) => {
-                AnswerOnNextPrompt::new(message.clone()).run(self).await
+                AnswerOnNextPrompt::new(self.emit(message)).run(self).await
             }
-            Cmd::AssertAlert(expect) => AssertAlert::new(expect.clone()).run(self).await,
-            Cmd::AssertChecked(target) => AssertChecked::new(target.clone().into()).run(self).await,
+            Cmd::AssertAlert(expect) => AssertAlert::new(self.emit(expect)).run(self).await,
+            Cmd::AssertChecked(target) => AssertChecked::new(self.locator(target)?).run(self).await,
             Cmd::AssertNotChecked(target) => {
-                AssertNotChecked::new(target.clone().into()).run(self).await
+                AssertNotChecked::new(self.locator(target)?).run(self).await
             }
-            Cmd::AssertPrompt(expect) => AssertPrompt::new(expect.clone()).run(self).await,
+            Cmd::AssertPrompt(expect) => AssertPrompt::new(self.emit(expect)).run(self).await,
             Cmd::AssertSelectedValue(target, value) => {
-                AssertSelectedValue::new(target.clone().into(), value.clone())
+                AssertSelectedValue::new(self.locator(target)?, self.emit(value))
                     .run(self)
                     .await
             }
             Cmd::AssertNotSelectedValue(target, value) => {
-                AssertNotSelectedValue::new(target.clone().into(), value.clone())
+                AssertNotSelectedValue::new(self.locator(target)?, self.emit(value))
                     .run(self)
                     .await
             }
             Cmd::AssertText(target, value) => {
-                AssertText::new(target.clone().into(), value.clone())
+                AssertText::new(self.locator(target)?, self.emit(value))
                     .run(self)
                     .await
             }
             Cmd::AssertNotText(target, value) => {
-                AssertNotText::new(target.clone().into(), value.clone())
+                AssertNotText::new(self.locator(target)?, self.emit(value))
                     .run(self)
                     .await
             }
-            Cmd::DoubleClick(target) => DoubleClick::new(target.clone().into()).run(self).await,
+            Cmd::DoubleClick(target) => DoubleClick::new(self.locator(target)?).run(self).await,
             Cmd::EditContent(target, value) => {
-                EditContent::new(target.clone().into(), value.clone())
+                EditContent::new(self.locator(target)?, self.emit(value))
                     .run(self)
                     .await
             }
             Cmd::SendKeys(target, value) => {
-                SendKeys::new(target.clone().into(), value.clone())
+                SendKeys::new(self.locator(target)?, self.emit_keys(value))
                     .run(self)
                     .await
             }
             Cmd::Type(target, value) => {
-                Type::new(target.clone().into(), value.clone())
+                Type::new(self.locator(target)?, self.emit_keys(value))
                     .run(self)
                     .await
             }
-            Cmd::Check(target) => Check::new(target.clone().into()).run(self).await,
-            Cmd::UnCheck(target) => UnCheck::new(target.clone().into()).run(self).await,
-            Cmd::MouseDown(target) => MouseDown::new(target.clone().into()).run(self).await,
-            Cmd::MouseUp(target) => MouseUp::new(target.clone().into()).run(self).await,
+            Cmd::Check(target) => Check::new(self.locator(target)?).run(self).await,
+            Cmd::UnCheck(target) => UnCheck::new(self.locator(target)?).run(self).await,
+            Cmd::MouseDown(target) => MouseDown::new(self.locator(target)?).run(self).await,
+            Cmd::MouseUp(target) => MouseUp::new(self.locator(target)?).run(self).await,
             Cmd::ChooseCancelOnNextConfirmation => ChooseCancelOnNextConfirmation.run(self).await,
             Cmd::ChooseOkOnNextConfirmation => ChooseOkOnNextConfirmation.run(self).await,
             Cmd::ChooseCancelOnNextPrompt => ChooseCancelOnNextPrompt.run(self).await,
-            Cmd::AssertTitle(t) => AssertTitle::new(t.clone()).run(self).await,
+            Cmd::AssertTitle(t) => AssertTitle::new(self.emit(t)).run(self).await,
             Cmd::StoreTitle(t) => StoreTitle::new(t.clone()).run(self).await,
             Cmd::AssertValue(target, value) => {
-                AssertValue::new(target.clone().into(), value.clone())
+                AssertValue::new(self.locator(target)?, self.emit(value))
                     .run(self)
                     .await
             }
             Cmd::AssertConfirmation(target) => {
-                AssertConfirmation::new(target.clone()).run(self).await
+                AssertConfirmation::new(self.emit(target)).run(self).await
             }
             Cmd::AssertSelectedLabel(target, value) => {
-                AssertSelectedLabel::new(target.clone().into(), value.clone())
+                AssertSelectedLabel::new(self.locator(target)?, self.emit(value))
                     .run(self)
                     .await
             }
             Cmd::StoreValue(target, value) => {
-                StoreValue::new(target.clone().into(), value.clone())
+                StoreValue::new(self.locator(target)?, value.clone())
                     .run(self)
                     .await
             }
             Cmd::StoreJson(json, value) => {
-                StoreJson::new(json.clone(), value.clone()).run(self).await
+                StoreJson::new(self.emit(json), value.clone())
+                    .run(self)
+                    .await
             }
             Cmd::StoreAttribute(target, attr, value) => {
-                StoreAttribute::new(target.clone().into(), attr.clone(), value.clone())
+                StoreAttribute::new(self.locator(target)?, self.emit(attr), value.clone())
                     .run(self)
                     .await
             }
@@ -291,33 +599,138 @@ where
                 // So we follow its rule by Box the returned future.
                 // But we are using [`async-recursion`] crate for this.
 
-                self.run_test(file, test).await.map_err(|e| e.kind)
+                // Verifications of the inner test are kept so they're reported by the outer run.
+                let index = look_up_test(file, test)?;
+                Playground::run_test(self, file, index).await.map_err(|e| {
+                    self.nested_artifacts = Some((e.screenshot, e.page_source));
+                    e.kind
+                })
             }
             Cmd::AssertElementPresent(target) => {
-                AssertElementPresent::new(target.clone().into())
+                AssertElementPresent::new(self.locator(target)?)
                     .run(self)
                     .await
             }
             Cmd::AssertElementNotPresent(target) => {
-                AssertElementNotPresent::new(target.clone().into())
+                AssertElementNotPresent::new(self.locator(target)?)
                     .run(self)
                     .await
             }
             Cmd::AssertEditable(target) => {
-                AssertElementEditable::new(target.clone().into())
+                AssertElementEditable::new(self.locator(target)?)
                     .run(self)
                     .await
             }
             Cmd::AssertNotEditable(target) => {
-                AssertElementNotEditable::new(target.clone().into())
+                AssertElementNotEditable::new(self.locator(target)?)
                     .run(self)
                     .await
             }
             Cmd::ClickAt(target, coord) => {
-                ClickAt::new(target.clone().into(), *coord).run(self).await
+                ClickAt::new(self.locator(target)?, self.resolve(coord)?)
+                    .run(self)
+                    .await
             }
             Cmd::DoubleClickAt(target, coord) => {
-                DoubleClickAt::new(target.clone().into(), *coord)
+                DoubleClickAt::new(self.locator(target)?, self.resolve(coord)?)
+                    .run(self)
+                    .await
+            }
+            Cmd::MouseOver(target) => MouseOver::new(self.locator(target)?).run(self).await,
+            Cmd::MouseOut(target) => MouseOut::new(self.locator(target)?).run(self).await,
+            Cmd::MouseMoveAt(target, coord) => {
+                MouseMoveAt::new(self.locator(target)?, self.resolve(coord)?)
+                    .run(self)
+                    .await
+            }
+            Cmd::MouseDownAt(target, coord) => {
+                MouseDownAt::new(self.locator(target)?, self.resolve(coord)?)
+                    .run(self)
+                    .await
+            }
+            Cmd::MouseUpAt(target, coord) => {
+                MouseUpAt::new(self.locator(target)?, self.resolve(coord)?)
+                    .run(self)
+                    .await
+            }
+            Cmd::DragAndDropToObject(source, destination) => {
+                DragAndDropToObject::new(self.locator(source)?, self.locator(destination)?)
+                    .run(self)
+                    .await
+            }
+            Cmd::Submit(target) => Submit::new(self.locator(target)?).run(self).await,
+            Cmd::WebdriverAnswerOnVisiblePrompt(answer) => {
+                WebdriverAnswerOnVisiblePrompt::new(self.emit(answer))
+                    .run(self)
+                    .await
+            }
+            Cmd::WebdriverChooseOkOnVisibleConfirmation => {
+                WebdriverChooseOkOnVisibleConfirmation.run(self).await
+            }
+            Cmd::WebdriverChooseCancelOnVisibleConfirmation => {
+                WebdriverChooseCancelOnVisibleConfirmation.run(self).await
+            }
+            Cmd::WebdriverChooseCancelOnVisiblePrompt => {
+                WebdriverChooseCancelOnVisiblePrompt.run(self).await
+            }
+            Cmd::SelectWindow(locator) => SelectWindow::new(locator.clone()).run(self).await,
+            Cmd::StoreWindowHandle(var) => StoreWindowHandle::new(var.clone()).run(self).await,
+            Cmd::SelectFrame(locator) => SelectFrame::new(locator.clone()).run(self).await,
+            Cmd::Verify { var, value } => {
+                Assert::new(var.clone(), self.emit(value)).run(self).await
+            }
+            Cmd::VerifyChecked(target) => AssertChecked::new(self.locator(target)?).run(self).await,
+            Cmd::VerifyNotChecked(target) => {
+                AssertNotChecked::new(self.locator(target)?).run(self).await
+            }
+            Cmd::VerifyEditable(target) => {
+                AssertElementEditable::new(self.locator(target)?)
+                    .run(self)
+                    .await
+            }
+            Cmd::VerifyNotEditable(target) => {
+                AssertElementNotEditable::new(self.locator(target)?)
+                    .run(self)
+                    .await
+            }
+            Cmd::VerifyElementPresent(target) => {
+                AssertElementPresent::new(self.locator(target)?)
+                    .run(self)
+                    .await
+            }
+            Cmd::VerifyElementNotPresent(target) => {
+                AssertElementNotPresent::new(self.locator(target)?)
+                    .run(self)
+                    .await
+            }
+            Cmd::VerifySelectedLabel(target, value) => {
+                AssertSelectedLabel::new(self.locator(target)?, self.emit(value))
+                    .run(self)
+                    .await
+            }
+            Cmd::VerifySelectedValue(target, value) => {
+                AssertSelectedValue::new(self.locator(target)?, self.emit(value))
+                    .run(self)
+                    .await
+            }
+            Cmd::VerifyNotSelectedValue(target, value) => {
+                AssertNotSelectedValue::new(self.locator(target)?, self.emit(value))
+                    .run(self)
+                    .await
+            }
+            Cmd::VerifyText(target, value) => {
+                AssertText::new(self.locator(target)?, self.emit(value))
+                    .run(self)
+                    .await
+            }
+            Cmd::VerifyNotText(target, value) => {
+                AssertNotText::new(self.locator(target)?, self.emit(value))
+                    .run(self)
+                    .await
+            }
+            Cmd::VerifyTitle(t) => AssertTitle::new(self.emit(t)).run(self).await,
+            Cmd::VerifyValue(target, value) => {
+                AssertValue::new(self.locator(target)?, self.emit(value))
                     .run(self)
                     .await
             }
@@ -334,6 +747,40 @@ where
         }
     }
 
+    /// Run a command which opens a new window
+    /// and save the window's handle in a variable.
+    pub(crate) async fn run_command_opening_window(
+        &mut self,
+        file: &File,
+        cmd: &Cmd,
+        window: &NewWindow,
+    ) -> Result<(), RunnerErrorKind> {
+        let handles = self.webdriver.window_handles().await?;
+        self.run_command(file, cmd).await?;
+
+        let now = std::time::Instant::now();
+        loop {
+            let new_handle = self
+                .webdriver
+                .window_handles()
+                .await?
+                .into_iter()
+                .find(|handle| !handles.contains(handle));
+            if let Some(handle) = new_handle {
+                self.save_value(window.handle_name.clone(), Value::String(handle));
+                break Ok(());
+            }
+
+            if now.elapsed() > window.timeout.unwrap_or(DEFAULT_WINDOW_TIMEOUT) {
+                break Err(RunnerErrorKind::Timeout(
+                    "Wait for a new window timeout".to_string(),
+                ));
+            }
+
+            futures_timer::Delay::new(Duration::from_millis(100)).await;
+        }
+    }
+
     pub(crate) async fn exec(
         &mut self,
         script: &str,
@@ -367,13 +814,74 @@ where
     pub(crate) fn emit(&self, s: &str) -> String {
         emit_variables(s, &self.data)
     }
+
+    /// Substitutes variables and special keys like `${KEY_ENTER}` which are sent to an element.
+    pub(crate) fn emit_keys(&self, s: &str) -> String {
+        emit_keys(s, &self.data)
+    }
+
+    /// Converts a target to a locator of a webdriver substituting variables.
+    pub(crate) fn locator(&self, target: &Target) -> Result<Locator, RunnerErrorKind> {
+        let location = self.resolve(&target.location)?;
+        Ok(location.into())
+    }
+
+    /// Substitutes variables in an argument of a command and parses it.
+    pub(crate) fn resolve<T: Argument + Clone>(
+        &self,
+        param: &Param<T>,
+    ) -> Result<T, RunnerErrorKind> {
+        match param {
+            Param::Completed(value) => Ok(value.clone()),
+            Param::Incomplete(s) => {
+                let s = self.emit(s);
+                T::parse(&s).map_err(|err| {
+                    RunnerErrorKind::MismatchedType(format!("{} but got {:?}", err, s))
+                })
+            }
+        }
+    }
+}
+
+/// Paths to a screenshot and a page source saved on a failure.
+type Artifacts = (Option<Box<Path>>, Option<Box<Path>>);
+
+/// Makes a test name usable as a file name.
+fn artifact_name(test: &str) -> String {
+    let name = test
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+
+    if name.is_empty() {
+        "test".to_owned()
+    } else {
+        name
+    }
 }
 
 fn emit_variables(s: &str, vars: &HashMap<String, Value>) -> String {
-    emit_vars(s, |var| match vars.get(var) {
+    emit_vars(s, |var| print_variable(var, vars))
+}
+
+fn emit_keys(s: &str, vars: &HashMap<String, Value>) -> String {
+    emit_vars(s, |var| match key_code(var) {
+        Some(key) => key.to_string(),
+        None => print_variable(var, vars),
+    })
+}
+
+fn print_variable(var: &str, vars: &HashMap<String, Value>) -> String {
+    match vars.get(var) {
         Some(value) => print_plain_value(value),
         None => format!("${{{}}}", var),
-    })
+    }
 }
 
 fn emit_variables_custom(text: &str) -> (String, Vec<String>) {
@@ -417,19 +925,22 @@ fn print_plain_value(val: &Value) -> String {
         Value::Object(..) => "[object Object]".to_string(), // is it ok behaviour?
         Value::Array(values) => values
             .iter()
-            .map(|v| print_plain_value(v))
+            .map(print_plain_value)
             .collect::<Vec<_>>()
             .join(","),
         Value::Bool(val) => val.to_string(),
     }
 }
 
-impl From<Target> for Locator {
-    fn from(target: Target) -> Self {
-        match target.location {
+impl From<Location> for Locator {
+    fn from(location: Location) -> Self {
+        match location {
             Location::Css(css) => Locator::Css(css),
             Location::Id(id) => Locator::Id(id),
             Location::XPath(path) => Locator::XPath(path),
+            Location::Name(name) => Locator::Name(name),
+            Location::LinkText(text) => Locator::LinkText(text),
+            Location::PartialLinkText(text) => Locator::PartialLinkText(text),
         }
     }
 }
@@ -441,6 +952,20 @@ fn look_up_test<S: AsRef<str>>(file: &File, test: S) -> Result<usize, RunnerErro
         .ok_or_else(|| RunnerErrorKind::TestNotFound(test.as_ref().to_string()))
 }
 
+fn look_up_test_by_id(file: &File, id: &str) -> Result<usize, RunnerErrorKind> {
+    file.tests
+        .iter()
+        .position(|t| t.id == id)
+        .ok_or_else(|| RunnerErrorKind::TestNotFound(id.to_string()))
+}
+
+fn look_up_suite<S: AsRef<str>>(file: &File, suite: S) -> Result<&Suite, RunnerErrorKind> {
+    file.suites
+        .iter()
+        .find(|s| s.name == suite.as_ref())
+        .ok_or_else(|| RunnerErrorKind::SuiteNotFound(suite.as_ref().to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,955 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! A scriptable in-memory backend for running `.side` files without a browser.
+//!
+//! [`MockDriver`] models a page without DOM.
+//! Elements are registered by [`Locator`]s, and the driver keeps a title, a URL,
+//! results of scripts and a queue of dialogs.
+//! Each call to the driver is recorded so it can be checked after a run.
+//!
+//! ```
+//! use siderunner::{
+//!     testing::{Call, MockDriver, MockElement},
+//!     webdriver::Locator,
+//!     Runner,
+//! };
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let file = r#"{
+//!     "id": "", "version": "2.0", "name": "", "url": "http://localhost",
+//!     "tests": [{ "id": "", "name": "test", "commands": [
+//!         { "id": "", "comment": "", "command": "open", "target": "/", "targets": [], "value": "" },
+//!         { "id": "", "comment": "", "command": "click", "target": "id=button", "targets": [], "value": "" },
+//!         { "id": "", "comment": "", "command": "assertTitle", "target": "Home", "targets": [], "value": "" }
+//!     ]}]
+//! }"#;
+//! let file = siderunner::parse(file.as_bytes()).unwrap();
+//!
+//! let driver = MockDriver::new();
+//! driver.set_title("Home");
+//! driver.add_element(Locator::Id("button".to_owned()), MockElement::new());
+//!
+//! let mut runner = Runner::with_backend(driver.clone());
+//! runner.run(&file).await.unwrap();
+//!
+//! assert!(driver
+//!     .calls()
+//!     .contains(&Call::Click(Locator::Id("button".to_owned()))));
+//! # }
+//! ```
+
+pub mod server;
+
+use crate::{
+    error::RunnerErrorKind,
+    webdriver::{Element, Locator, Webdriver},
+};
+use serde_json::Value as Json;
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+    sync::{Arc, Mutex, MutexGuard},
+    time::Duration,
+};
+
+const MAIN_WINDOW: &str = "main";
+
+/// A call made to [`MockDriver`] or to one of its elements.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Call {
+    Goto(String),
+    Find(Locator),
+    FindAll(Locator),
+    CurrentUrl,
+    WaitForVisible(Locator),
+    WaitForNotVisible(Locator),
+    WaitForPresent(Locator),
+    WaitForNotPresent(Locator),
+    WaitForEditable(Locator),
+    WaitForNotEditable(Locator),
+    SetWindowSize(u32, u32),
+    Execute(String),
+    ExecuteAsync(String),
+    Close,
+    DeleteCookies,
+    AlertText,
+    AlertAccept,
+    AlertDismiss,
+    AlertSendText(String),
+    DoubleClick(Locator),
+    MouseDown(Locator),
+    MouseUp(Locator),
+    MouseOver(Locator),
+    MouseOut(Locator),
+    MouseMoveAt(Locator, (i32, i32)),
+    MouseDownAt(Locator, (i32, i32)),
+    MouseUpAt(Locator, (i32, i32)),
+    DragAndDrop(Locator, Locator),
+    Title,
+    ClickAt(Locator, (i32, i32)),
+    DoubleClickAt(Locator, (i32, i32)),
+    SwitchToDefaultContent,
+    SwitchToParentFrame,
+    SwitchToFrame(u16),
+    SwitchToFrameElement(Locator),
+    WindowHandles,
+    CurrentWindowHandle,
+    SwitchToWindow(String),
+    Screenshot,
+    PageSource,
+    Attr(Locator, String),
+    Prop(Locator, String),
+    Text(Locator),
+    Html(Locator),
+    FindIn(Locator, Locator),
+    Click(Locator),
+    SelectByIndex(Locator, usize),
+    SelectByValue(Locator, String),
+    SelectByLabel(Locator, String),
+    SendKeys(Locator, String),
+    Submit(Locator),
+    IsSelected(Locator),
+    IsPresent(Locator),
+    IsEnabled(Locator),
+}
+
+/// An error returned by [`MockDriver`].
+///
+/// It's wrapped into [`RunnerErrorKind::WebdriverError`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum MockError {
+    NoSuchElement(Locator),
+    NoSuchAlert,
+    NoSuchWindow(String),
+}
+
+impl fmt::Display for MockError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoSuchElement(locator) => write!(f, "no such element {:?}", locator),
+            Self::NoSuchAlert => write!(f, "no such alert"),
+            Self::NoSuchWindow(handle) => write!(f, "no such window {:?}", handle),
+        }
+    }
+}
+
+impl std::error::Error for MockError {}
+
+impl From<MockError> for RunnerErrorKind {
+    fn from(err: MockError) -> Self {
+        RunnerErrorKind::WebdriverError(Box::new(err))
+    }
+}
+
+/// A description of an element registered in [`MockDriver`].
+#[derive(Debug, Clone)]
+pub struct MockElement {
+    text: String,
+    html: String,
+    attributes: HashMap<String, String>,
+    properties: HashMap<String, String>,
+    visible: bool,
+    enabled: bool,
+    selected: bool,
+    opens_window: Option<String>,
+}
+
+impl MockElement {
+    /// Creates a visible and enabled element without any content.
+    pub fn new() -> Self {
+        Self {
+            text: String::new(),
+            html: String::new(),
+            attributes: HashMap::new(),
+            properties: HashMap::new(),
+            visible: true,
+            enabled: true,
+            selected: false,
+            opens_window: None,
+        }
+    }
+
+    /// Sets a visible text of the element.
+    pub fn text<S: Into<String>>(mut self, text: S) -> Self {
+        self.text = text.into();
+        self
+    }
+
+    /// Sets HTML of the element.
+    pub fn html<S: Into<String>>(mut self, html: S) -> Self {
+        self.html = html.into();
+        self
+    }
+
+    /// Sets an attribute of the element.
+    pub fn attr<S: Into<String>>(mut self, name: S, value: S) -> Self {
+        self.attributes.insert(name.into(), value.into());
+        self
+    }
+
+    /// Sets a property of the element.
+    ///
+    /// `value` property is changed by typing into the element.
+    pub fn prop<S: Into<String>>(mut self, name: S, value: S) -> Self {
+        self.properties.insert(name.into(), value.into());
+        self
+    }
+
+    /// Sets whether the element is displayed.
+    pub fn visible(mut self, visible: bool) -> Self {
+        self.visible = visible;
+        self
+    }
+
+    /// Sets whether the element is enabled.
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Sets whether the element is selected or checked.
+    pub fn selected(mut self, selected: bool) -> Self {
+        self.selected = selected;
+        self
+    }
+
+    /// Makes a click on the element open a new window with a given handle.
+    pub fn opens_window<S: Into<String>>(mut self, handle: S) -> Self {
+        self.opens_window = Some(handle.into());
+        self
+    }
+
+    fn is_editable(&self) -> bool {
+        self.visible && self.enabled && !self.attributes.contains_key("readonly")
+    }
+}
+
+impl Default for MockElement {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+struct State {
+    calls: Vec<Call>,
+    elements: HashMap<Locator, Vec<MockElement>>,
+    url: url::Url,
+    title: String,
+    pages: HashMap<String, String>,
+    scripts: Vec<(String, Json)>,
+    alerts: VecDeque<String>,
+    windows: Vec<String>,
+    current_window: String,
+    source: String,
+}
+
+/// MockDriver is an in-memory [`Webdriver`] which can be scripted in tests.
+///
+/// Clones share the same state, so one clone may be given to a [`Runner`]
+/// while another one is used to set a page up and to check recorded calls.
+///
+/// [`Runner`]: crate::Runner
+#[derive(Clone)]
+pub struct MockDriver(Arc<Mutex<State>>);
+
+impl MockDriver {
+    /// Creates a driver with a blank page and a single window.
+    pub fn new() -> Self {
+        let state = State {
+            calls: Vec::new(),
+            elements: HashMap::new(),
+            url: url::Url::parse("about:blank").unwrap(),
+            title: String::new(),
+            pages: HashMap::new(),
+            scripts: Vec::new(),
+            alerts: VecDeque::new(),
+            windows: vec![MAIN_WINDOW.to_owned()],
+            current_window: MAIN_WINDOW.to_owned(),
+            source: String::new(),
+        };
+
+        Self(Arc::new(Mutex::new(state)))
+    }
+
+    /// Registers an element which can be found by a locator.
+    ///
+    /// Several elements may be registered by the same locator.
+    pub fn add_element(&self, locator: Locator, element: MockElement) {
+        self.state()
+            .elements
+            .entry(locator)
+            .or_default()
+            .push(element);
+    }
+
+    /// Removes all elements registered by a locator.
+    pub fn remove_elements(&self, locator: &Locator) {
+        self.state().elements.remove(locator);
+    }
+
+    /// Sets a title of the current page.
+    pub fn set_title<S: Into<String>>(&self, title: S) {
+        self.state().title = title.into();
+    }
+
+    /// Sets a title which is used after navigating to a given URL.
+    pub fn add_page<S: Into<String>>(&self, url: S, title: S) {
+        self.state().pages.insert(url.into(), title.into());
+    }
+
+    /// Sets a source of the current page.
+    ///
+    /// Screenshots are always empty.
+    pub fn set_page_source<S: Into<String>>(&self, source: S) {
+        self.state().source = source.into();
+    }
+
+    /// Sets a URL of the current page.
+    pub fn set_url(&self, url: url::Url) {
+        self.state().url = url;
+    }
+
+    /// Sets a value returned by scripts which contain a given pattern.
+    ///
+    /// Patterns are checked in the order they were added.
+    /// A script which doesn't match any pattern returns `null`.
+    pub fn add_script_result<S: Into<String>>(&self, pattern: S, value: Json) {
+        self.state().scripts.push((pattern.into(), value));
+    }
+
+    /// Opens a dialog with a given text.
+    ///
+    /// Dialogs are queued and closed by accepting or dismissing them.
+    pub fn push_alert<S: Into<String>>(&self, text: S) {
+        self.state().alerts.push_back(text.into());
+    }
+
+    /// Returns all calls recorded so far.
+    pub fn calls(&self) -> Vec<Call> {
+        self.state().calls.clone()
+    }
+
+    /// Clears recorded calls.
+    pub fn clear_calls(&self) {
+        self.state().calls.clear();
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        // A panic in a test which holds the lock shouldn't hide the original error.
+        self.0.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn record(&self, call: Call) {
+        self.state().calls.push(call);
+    }
+
+    fn record_locked(&self, call: Call) -> MutexGuard<'_, State> {
+        let mut state = self.state();
+        state.calls.push(call);
+        state
+    }
+
+    fn element(&self, locator: &Locator, index: usize) -> Result<MockElement, RunnerErrorKind> {
+        self.state()
+            .elements
+            .get(locator)
+            .and_then(|elements| elements.get(index))
+            .cloned()
+            .ok_or_else(|| MockError::NoSuchElement(locator.clone()).into())
+    }
+
+    fn check_element(&self, locator: &Locator) -> Result<(), RunnerErrorKind> {
+        self.element(locator, 0).map(|_| ())
+    }
+
+    fn wait_for<F>(
+        &self,
+        call: Call,
+        locator: &Locator,
+        condition: F,
+    ) -> Result<(), RunnerErrorKind>
+    where
+        F: Fn(Option<&MockElement>) -> bool,
+    {
+        let state = self.record_locked(call);
+        let element = state
+            .elements
+            .get(locator)
+            .and_then(|elements| elements.first());
+        if condition(element) {
+            Ok(())
+        } else {
+            // Nothing changes on a mocked page so there's no point in waiting.
+            Err(RunnerErrorKind::Timeout(format!(
+                "Wait for element {:?} timeout",
+                locator
+            )))
+        }
+    }
+}
+
+impl Default for MockDriver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl Webdriver for MockDriver {
+    type Element = MockElementHandle;
+
+    async fn goto(&mut self, url: &str) -> Result<(), RunnerErrorKind> {
+        let mut state = self.record_locked(Call::Goto(url.to_owned()));
+        state.url = url::Url::parse(url)?;
+        if let Some(title) = state.pages.get(url).cloned() {
+            state.title = title;
+        }
+
+        Ok(())
+    }
+
+    async fn find(&mut self, locator: Locator) -> Result<Self::Element, RunnerErrorKind> {
+        self.record(Call::Find(locator.clone()));
+        self.check_element(&locator)?;
+        Ok(MockElementHandle::new(self.clone(), locator, 0))
+    }
+
+    async fn find_all(&mut self, locator: Locator) -> Result<Vec<Self::Element>, RunnerErrorKind> {
+        let count = self
+            .record_locked(Call::FindAll(locator.clone()))
+            .elements
+            .get(&locator)
+            .map_or(0, |elements| elements.len());
+        let elements = (0..count)
+            .map(|index| MockElementHandle::new(self.clone(), locator.clone(), index))
+            .collect();
+        Ok(elements)
+    }
+
+    async fn current_url(&mut self) -> Result<url::Url, RunnerErrorKind> {
+        Ok(self.record_locked(Call::CurrentUrl).url.clone())
+    }
+
+    async fn wait_for_visible(
+        &mut self,
+        locator: Locator,
+        _: Duration,
+    ) -> Result<(), RunnerErrorKind> {
+        self.wait_for(
+            Call::WaitForVisible(locator.clone()),
+            &locator,
+            |e| matches!(e, Some(e) if e.visible),
+        )
+    }
+
+    async fn wait_for_not_visible(
+        &mut self,
+        locator: Locator,
+        _: Duration,
+    ) -> Result<(), RunnerErrorKind> {
+        self.wait_for(
+            Call::WaitForNotVisible(locator.clone()),
+            &locator,
+            |e| !matches!(e, Some(e) if e.visible),
+        )
+    }
+
+    async fn wait_for_present(
+        &mut self,
+        locator: Locator,
+        _: Duration,
+    ) -> Result<(), RunnerErrorKind> {
+        self.wait_for(Call::WaitForPresent(locator.clone()), &locator, |e| {
+            e.is_some()
+        })
+    }
+
+    async fn wait_for_not_present(
+        &mut self,
+        locator: Locator,
+        _: Duration,
+    ) -> Result<(), RunnerErrorKind> {
+        self.wait_for(Call::WaitForNotPresent(locator.clone()), &locator, |e| {
+            e.is_none()
+        })
+    }
+
+    async fn wait_for_editable(
+        &mut self,
+        locator: Locator,
+        _: Duration,
+    ) -> Result<(), RunnerErrorKind> {
+        self.wait_for(
+            Call::WaitForEditable(locator.clone()),
+            &locator,
+            |e| matches!(e, Some(e) if e.is_editable()),
+        )
+    }
+
+    async fn wait_for_not_editable(
+        &mut self,
+        locator: Locator,
+        _: Duration,
+    ) -> Result<(), RunnerErrorKind> {
+        self.wait_for(
+            Call::WaitForNotEditable(locator.clone()),
+            &locator,
+            |e| matches!(e, Some(e) if !e.is_editable()),
+        )
+    }
+
+    async fn set_window_size(&mut self, width: u32, height: u32) -> Result<(), RunnerErrorKind> {
+        self.record(Call::SetWindowSize(width, height));
+        Ok(())
+    }
+
+    async fn execute(&mut self, script: &str, _: Vec<Json>) -> Result<Json, RunnerErrorKind> {
+        let state = self.record_locked(Call::Execute(script.to_owned()));
+        Ok(script_result(&state, script))
+    }
+
+    async fn execute_async(&mut self, script: &str, _: Vec<Json>) -> Result<Json, RunnerErrorKind> {
+        let state = self.record_locked(Call::ExecuteAsync(script.to_owned()));
+        Ok(script_result(&state, script))
+    }
+
+    async fn close(&mut self) -> Result<(), RunnerErrorKind> {
+        let mut state = self.record_locked(Call::Close);
+        let current = state.current_window.clone();
+        state.windows.retain(|handle| handle != &current);
+        Ok(())
+    }
+
+    async fn delete_cookies(&mut self) -> Result<(), RunnerErrorKind> {
+        self.record(Call::DeleteCookies);
+        Ok(())
+    }
+
+    async fn alert_text(&mut self) -> Result<String, RunnerErrorKind> {
+        let state = self.record_locked(Call::AlertText);
+        let text = state
+            .alerts
+            .front()
+            .cloned()
+            .ok_or(MockError::NoSuchAlert)?;
+        Ok(text)
+    }
+
+    async fn alert_accept(&mut self) -> Result<(), RunnerErrorKind> {
+        let mut state = self.record_locked(Call::AlertAccept);
+        state.alerts.pop_front().ok_or(MockError::NoSuchAlert)?;
+        Ok(())
+    }
+
+    async fn alert_dissmis(&mut self) -> Result<(), RunnerErrorKind> {
+        let mut state = self.record_locked(Call::AlertDismiss);
+        state.alerts.pop_front().ok_or(MockError::NoSuchAlert)?;
+        Ok(())
+    }
+
+    async fn alert_send_text(&mut self, text: &str) -> Result<(), RunnerErrorKind> {
+        let state = self.record_locked(Call::AlertSendText(text.to_owned()));
+        if state.alerts.is_empty() {
+            return Err(MockError::NoSuchAlert.into());
+        }
+
+        Ok(())
+    }
+
+    async fn double_click(&mut self, locator: Locator) -> Result<(), RunnerErrorKind> {
+        self.record(Call::DoubleClick(locator.clone()));
+        self.check_element(&locator)
+    }
+
+    async fn mouse_down(&mut self, locator: Locator) -> Result<(), RunnerErrorKind> {
+        self.record(Call::MouseDown(locator.clone()));
+        self.check_element(&locator)
+    }
+
+    async fn mouse_up(&mut self, locator: Locator) -> Result<(), RunnerErrorKind> {
+        self.record(Call::MouseUp(locator.clone()));
+        self.check_element(&locator)
+    }
+
+    async fn mouse_over(&mut self, locator: Locator) -> Result<(), RunnerErrorKind> {
+        self.record(Call::MouseOver(locator.clone()));
+        self.check_element(&locator)
+    }
+
+    async fn mouse_out(&mut self, locator: Locator) -> Result<(), RunnerErrorKind> {
+        self.record(Call::MouseOut(locator.clone()));
+        self.check_element(&locator)
+    }
+
+    async fn mouse_move_at(
+        &mut self,
+        locator: Locator,
+        coord: (i32, i32),
+    ) -> Result<(), RunnerErrorKind> {
+        self.record(Call::MouseMoveAt(locator.clone(), coord));
+        self.check_element(&locator)
+    }
+
+    async fn mouse_down_at(
+        &mut self,
+        locator: Locator,
+        coord: (i32, i32),
+    ) -> Result<(), RunnerErrorKind> {
+        self.record(Call::MouseDownAt(locator.clone(), coord));
+        self.check_element(&locator)
+    }
+
+    async fn mouse_up_at(
+        &mut self,
+        locator: Locator,
+        coord: (i32, i32),
+    ) -> Result<(), RunnerErrorKind> {
+        self.record(Call::MouseUpAt(locator.clone(), coord));
+        self.check_element(&locator)
+    }
+
+    async fn drag_and_drop(
+        &mut self,
+        source: Locator,
+        destination: Locator,
+    ) -> Result<(), RunnerErrorKind> {
+        self.record(Call::DragAndDrop(source.clone(), destination.clone()));
+        self.check_element(&source)?;
+        self.check_element(&destination)
+    }
+
+    async fn title(&mut self) -> Result<String, RunnerErrorKind> {
+        Ok(self.record_locked(Call::Title).title.clone())
+    }
+
+    async fn click_at(
+        &mut self,
+        locator: Locator,
+        coord: (i32, i32),
+    ) -> Result<(), RunnerErrorKind> {
+        self.record(Call::ClickAt(locator.clone(), coord));
+        self.check_element(&locator)
+    }
+
+    async fn double_click_at(
+        &mut self,
+        locator: Locator,
+        coord: (i32, i32),
+    ) -> Result<(), RunnerErrorKind> {
+        self.record(Call::DoubleClickAt(locator.clone(), coord));
+        self.check_element(&locator)
+    }
+
+    async fn switch_to_default_content(&mut self) -> Result<(), RunnerErrorKind> {
+        self.record(Call::SwitchToDefaultContent);
+        Ok(())
+    }
+
+    async fn switch_to_parent_frame(&mut self) -> Result<(), RunnerErrorKind> {
+        self.record(Call::SwitchToParentFrame);
+        Ok(())
+    }
+
+    async fn switch_to_frame(&mut self, index: u16) -> Result<(), RunnerErrorKind> {
+        self.record(Call::SwitchToFrame(index));
+        Ok(())
+    }
+
+    async fn switch_to_frame_element(&mut self, locator: Locator) -> Result<(), RunnerErrorKind> {
+        self.record(Call::SwitchToFrameElement(locator.clone()));
+        self.check_element(&locator)
+    }
+
+    async fn window_handles(&mut self) -> Result<Vec<String>, RunnerErrorKind> {
+        Ok(self.record_locked(Call::WindowHandles).windows.clone())
+    }
+
+    async fn current_window_handle(&mut self) -> Result<String, RunnerErrorKind> {
+        Ok(self
+            .record_locked(Call::CurrentWindowHandle)
+            .current_window
+            .clone())
+    }
+
+    async fn switch_to_window(&mut self, handle: &str) -> Result<(), RunnerErrorKind> {
+        let mut state = self.record_locked(Call::SwitchToWindow(handle.to_owned()));
+        if !state.windows.iter().any(|window| window == handle) {
+            return Err(MockError::NoSuchWindow(handle.to_owned()).into());
+        }
+
+        state.current_window = handle.to_owned();
+        Ok(())
+    }
+
+    async fn screenshot(&mut self) -> Result<Vec<u8>, RunnerErrorKind> {
+        self.record(Call::Screenshot);
+        Ok(Vec::new())
+    }
+
+    async fn page_source(&mut self) -> Result<String, RunnerErrorKind> {
+        Ok(self.record_locked(Call::PageSource).source.clone())
+    }
+}
+
+fn script_result(state: &State, script: &str) -> Json {
+    state
+        .scripts
+        .iter()
+        .find(|(pattern, _)| script.contains(pattern.as_str()))
+        .map_or(Json::Null, |(_, value)| value.clone())
+}
+
+/// A handle of an element found by [`MockDriver`].
+pub struct MockElementHandle {
+    driver: MockDriver,
+    locator: Locator,
+    index: usize,
+}
+
+impl MockElementHandle {
+    fn new(driver: MockDriver, locator: Locator, index: usize) -> Self {
+        Self {
+            driver,
+            locator,
+            index,
+        }
+    }
+
+    fn element(&self) -> Result<MockElement, RunnerErrorKind> {
+        self.driver.element(&self.locator, self.index)
+    }
+
+    fn update<F>(&self, f: F) -> Result<(), RunnerErrorKind>
+    where
+        F: FnOnce(&mut MockElement),
+    {
+        let mut state = self.driver.state();
+        let element = state
+            .elements
+            .get_mut(&self.locator)
+            .and_then(|elements| elements.get_mut(self.index))
+            .ok_or_else(|| MockError::NoSuchElement(self.locator.clone()))?;
+        f(element);
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Element for MockElementHandle {
+    type Driver = MockDriver;
+
+    async fn attr(&mut self, attribute: &str) -> Result<Option<String>, RunnerErrorKind> {
+        self.driver
+            .record(Call::Attr(self.locator.clone(), attribute.to_owned()));
+        Ok(self.element()?.attributes.get(attribute).cloned())
+    }
+
+    async fn prop(&mut self, prop: &str) -> Result<Option<String>, RunnerErrorKind> {
+        self.driver
+            .record(Call::Prop(self.locator.clone(), prop.to_owned()));
+        let element = self.element()?;
+        match element.properties.get(prop) {
+            Some(value) => Ok(Some(value.clone())),
+            None if prop == "checked" || prop == "selected" => {
+                Ok(Some(element.selected.to_string()))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn text(&mut self) -> Result<String, RunnerErrorKind> {
+        self.driver.record(Call::Text(self.locator.clone()));
+        Ok(self.element()?.text)
+    }
+
+    async fn html(&mut self, _: bool) -> Result<String, RunnerErrorKind> {
+        self.driver.record(Call::Html(self.locator.clone()));
+        Ok(self.element()?.html)
+    }
+
+    /// Elements are looked up by a locator on the whole page
+    /// as there's no hierarchy in a mocked page.
+    async fn find(&mut self, search: Locator) -> Result<Self, RunnerErrorKind>
+    where
+        Self: Sized,
+    {
+        self.driver
+            .record(Call::FindIn(self.locator.clone(), search.clone()));
+        self.driver.check_element(&search)?;
+        Ok(MockElementHandle::new(self.driver.clone(), search, 0))
+    }
+
+    async fn click(mut self) -> Result<Self::Driver, RunnerErrorKind> {
+        self.driver.record(Call::Click(self.locator.clone()));
+        if let Some(handle) = self.element()?.opens_window {
+            self.driver.state().windows.push(handle);
+        }
+
+        Ok(self.driver)
+    }
+
+    async fn select_by_index(mut self, index: usize) -> Result<Self::Driver, RunnerErrorKind> {
+        self.driver
+            .record(Call::SelectByIndex(self.locator.clone(), index));
+        self.element()?;
+        Ok(self.driver)
+    }
+
+    async fn select_by_value(mut self, value: &str) -> Result<Self::Driver, RunnerErrorKind> {
+        self.driver
+            .record(Call::SelectByValue(self.locator.clone(), value.to_owned()));
+        self.update(|e| {
+            e.properties.insert("value".to_owned(), value.to_owned());
+        })?;
+        Ok(self.driver)
+    }
+
+    async fn select_by_label(mut self, value: &str) -> Result<Self::Driver, RunnerErrorKind> {
+        self.driver
+            .record(Call::SelectByLabel(self.locator.clone(), value.to_owned()));
+        self.element()?;
+        Ok(self.driver)
+    }
+
+    async fn send_keys(mut self, value: &str) -> Result<(), RunnerErrorKind> {
+        self.driver
+            .record(Call::SendKeys(self.locator.clone(), value.to_owned()));
+        self.update(|e| {
+            e.properties
+                .entry("value".to_owned())
+                .or_default()
+                .push_str(value);
+        })
+    }
+
+    async fn submit(mut self) -> Result<(), RunnerErrorKind> {
+        self.driver.record(Call::Submit(self.locator.clone()));
+        self.element()?;
+        Ok(())
+    }
+
+    async fn is_selected(&mut self) -> Result<bool, RunnerErrorKind> {
+        self.driver.record(Call::IsSelected(self.locator.clone()));
+        Ok(self.element()?.selected)
+    }
+
+    async fn is_present(&mut self) -> Result<bool, RunnerErrorKind> {
+        self.driver.record(Call::IsPresent(self.locator.clone()));
+        Ok(self.element().is_ok())
+    }
+
+    async fn is_enabled(&mut self) -> Result<bool, RunnerErrorKind> {
+        self.driver.record(Call::IsEnabled(self.locator.clone()));
+        Ok(self.element()?.enabled)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::{parse, Runner};
+
+    pub(crate) fn side_file(commands: &[(&str, &str, &str)]) -> crate::File {
+        let commands = commands
+            .iter()
+            .map(|(command, target, value)| {
+                serde_json::json!({
+                    "id": "",
+                    "comment": "",
+                    "command": command,
+                    "target": target,
+                    "targets": [],
+                    "value": value,
+                })
+            })
+            .collect::<Vec<_>>();
+        let file = serde_json::json!({
+            "id": "",
+            "version": "2.0",
+            "name": "",
+            "url": "http://localhost",
+            "tests": [{ "id": "", "name": "test", "commands": commands }],
+        });
+
+        parse(file.to_string().as_bytes()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn run_file_against_mock() {
+        let file = side_file(&[
+            ("open", "/index.html", ""),
+            ("assertTitle", "Index", ""),
+            ("type", "id=name", "siderunner"),
+            ("assertValue", "id=name", "siderunner"),
+            ("storeText", "css=h1", "header"),
+            ("click", "css=button", ""),
+        ]);
+
+        let driver = MockDriver::new();
+        driver.add_page("http://localhost/index.html", "Index");
+        driver.add_element(Locator::Id("name".to_owned()), MockElement::new());
+        driver.add_element(
+            Locator::Css("h1".to_owned()),
+            MockElement::new().text("Hello"),
+        );
+        driver.add_element(Locator::Css("button".to_owned()), MockElement::new());
+
+        let mut runner = Runner::with_backend(driver.clone());
+        runner.run(&file).await.unwrap();
+
+        assert_eq!(
+            runner.get_value("header"),
+            Some(&serde_json::json!("Hello"))
+        );
+        let calls = driver.calls();
+        assert!(calls.contains(&Call::Goto("http://localhost/index.html".to_owned())));
+        assert!(calls.contains(&Call::SendKeys(
+            Locator::Id("name".to_owned()),
+            "siderunner".to_owned()
+        )));
+        assert_eq!(
+            calls.last(),
+            Some(&Call::Click(Locator::Css("button".to_owned())))
+        );
+    }
+
+    #[tokio::test]
+    async fn missing_element_is_an_error() {
+        let file = side_file(&[("click", "id=missing", "")]);
+
+        let driver = MockDriver::new();
+        let mut runner = Runner::with_backend(driver.clone());
+        let err = runner.run(&file).await.unwrap_err();
+
+        match err.kind {
+            RunnerErrorKind::WebdriverError(err) => assert_eq!(
+                err.downcast_ref::<MockError>(),
+                Some(&MockError::NoSuchElement(Locator::Id("missing".to_owned())))
+            ),
+            kind => panic!("unexpected error {:?}", kind),
+        }
+    }
+
+    #[tokio::test]
+    async fn scripts_and_alerts() {
+        let file = side_file(&[
+            ("executeScript", "return window.answer", "answer"),
+            ("assertAlert", "Hello", ""),
+            ("webdriverChooseOkOnVisibleConfirmation", "", ""),
+        ]);
+
+        let driver = MockDriver::new();
+        driver.add_script_result("window.answer", serde_json::json!(42));
+        driver.push_alert("Hello");
+
+        let mut runner = Runner::with_backend(driver.clone());
+        runner.run(&file).await.unwrap();
+
+        assert_eq!(runner.get_value("answer"), Some(&serde_json::json!(42)));
+        assert!(driver.calls().contains(&Call::AlertAccept));
+        assert!(Runner::with_backend(driver).run(&file).await.is_err());
+    }
+}
@@ -0,0 +1,1056 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! A static model of an HTML document.
+//!
+//! The parser is forgiving in the way browsers are, but it doesn't try to follow
+//! the HTML specification. Scripts are kept as text and never run.
+
+use serde_json::Value as Json;
+use url::Url;
+
+pub(crate) type NodeId = usize;
+
+/// An id of a document node which is a root of every document.
+pub(crate) const ROOT: NodeId = 0;
+
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+const RAW_TEXT_ELEMENTS: &[&str] = &["script", "style", "textarea", "title"];
+const NOT_RENDERED_ELEMENTS: &[&str] = &[
+    "head", "link", "meta", "noscript", "script", "style", "template", "title",
+];
+const BLOCK_ELEMENTS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "body",
+    "dd",
+    "div",
+    "dl",
+    "dt",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "html",
+    "li",
+    "main",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "tr",
+    "ul",
+];
+const FORM_CONTROLS: &[&str] = &[
+    "button", "fieldset", "input", "optgroup", "option", "select", "textarea",
+];
+const BOOLEAN_ATTRIBUTES: &[&str] = &[
+    "async",
+    "autofocus",
+    "autoplay",
+    "checked",
+    "compact",
+    "complete",
+    "controls",
+    "declare",
+    "defaultchecked",
+    "defaultselected",
+    "defer",
+    "disabled",
+    "draggable",
+    "ended",
+    "formnovalidate",
+    "hidden",
+    "indeterminate",
+    "iscontenteditable",
+    "ismap",
+    "itemscope",
+    "loop",
+    "multiple",
+    "muted",
+    "nohref",
+    "noresize",
+    "noshade",
+    "novalidate",
+    "nowrap",
+    "open",
+    "paused",
+    "pubdate",
+    "readonly",
+    "required",
+    "reversed",
+    "scoped",
+    "seamless",
+    "seeking",
+    "selected",
+    "truespeed",
+    "willvalidate",
+];
+const REFLECTED_PROPERTIES: &[&str] = &[
+    "action",
+    "alt",
+    "dir",
+    "lang",
+    "method",
+    "name",
+    "placeholder",
+    "rel",
+    "src",
+    "target",
+    "title",
+];
+
+pub(crate) struct Document {
+    pub(crate) url: Url,
+    nodes: Vec<Node>,
+}
+
+struct Node {
+    parent: Option<NodeId>,
+    children: Vec<NodeId>,
+    data: NodeData,
+}
+
+enum NodeData {
+    Document,
+    Element(ElementData),
+    Text(String),
+}
+
+struct ElementData {
+    tag: String,
+    attributes: Vec<(String, String)>,
+    checked: bool,
+    selected: bool,
+    value: Option<String>,
+}
+
+/// A navigation caused by an interaction with an element.
+pub(crate) struct Navigation {
+    pub(crate) url: Url,
+    pub(crate) new_window: bool,
+}
+
+impl Document {
+    pub(crate) fn parse(url: Url, html: &str) -> Self {
+        let mut doc = Self {
+            url,
+            nodes: vec![Node {
+                parent: None,
+                children: Vec::new(),
+                data: NodeData::Document,
+            }],
+        };
+
+        let mut open = vec![ROOT];
+        let mut rest = html;
+        while !rest.is_empty() {
+            let parent = *open.last().unwrap();
+            if let Some(comment) = rest.strip_prefix("<!--") {
+                rest = comment.find("-->").map_or("", |i| &comment[i + 3..]);
+            } else if rest.starts_with("<!") || rest.starts_with("<?") {
+                rest = rest.find('>').map_or("", |i| &rest[i + 1..]);
+            } else if let Some(tag) = rest.strip_prefix("</") {
+                let end = tag.find('>').unwrap_or(tag.len());
+                let name = tag[..end].trim().to_ascii_lowercase();
+                rest = tag.get(end + 1..).unwrap_or("");
+                if let Some(pos) = open.iter().rposition(|&id| doc.tag(id) == Some(&name)) {
+                    open.truncate(pos);
+                }
+            } else if rest.starts_with('<')
+                && rest[1..].starts_with(|c: char| c.is_ascii_alphabetic())
+            {
+                let (tag, attributes, self_closing, tail) = parse_tag(&rest[1..]);
+                rest = tail;
+
+                let closes = implicitly_closes(&tag);
+                if doc.tag(parent).is_some_and(|t| closes.contains(&t)) {
+                    open.pop();
+                }
+                let parent = *open.last().unwrap();
+
+                let element = ElementData {
+                    checked: has_attribute(&attributes, "checked"),
+                    selected: has_attribute(&attributes, "selected"),
+                    value: None,
+                    tag,
+                    attributes,
+                };
+                let tag = element.tag.clone();
+                let id = doc.push(parent, NodeData::Element(element));
+
+                if RAW_TEXT_ELEMENTS.contains(&tag.as_str()) && !self_closing {
+                    let (text, tail) = raw_text(rest, &tag);
+                    rest = tail;
+                    if !text.is_empty() {
+                        let text = if tag == "script" || tag == "style" {
+                            text.to_owned()
+                        } else {
+                            decode_entities(text)
+                        };
+                        doc.push(id, NodeData::Text(text));
+                    }
+                } else if !self_closing && !VOID_ELEMENTS.contains(&tag.as_str()) {
+                    open.push(id);
+                }
+            } else {
+                let first = rest.chars().next().map_or(1, char::len_utf8);
+                let end = rest[first..].find('<').map_or(rest.len(), |i| i + first);
+                doc.push(parent, NodeData::Text(decode_entities(&rest[..end])));
+                rest = &rest[end..];
+            }
+        }
+
+        doc.select_default_options();
+
+        doc
+    }
+
+    fn push(&mut self, parent: NodeId, data: NodeData) -> NodeId {
+        let id = self.nodes.len();
+        self.nodes.push(Node {
+            parent: Some(parent),
+            children: Vec::new(),
+            data,
+        });
+        self.nodes[parent].children.push(id);
+        id
+    }
+
+    /// Browsers select the first option of a single choice `select` if none is selected.
+    fn select_default_options(&mut self) {
+        let selects = self
+            .elements()
+            .filter(|&id| self.tag(id) == Some("select") && self.attr(id, "multiple").is_none())
+            .collect::<Vec<_>>();
+        for select in selects {
+            let options = self.options(select);
+            let selected = options.iter().rposition(|&id| self.element(id).selected);
+            for (i, &option) in options.iter().enumerate() {
+                self.element_mut(option).selected = selected.unwrap_or(0) == i;
+            }
+        }
+    }
+
+    pub(crate) fn contains(&self, id: NodeId) -> bool {
+        id < self.nodes.len()
+    }
+
+    /// Returns all elements in a document order.
+    pub(crate) fn elements(&self) -> impl Iterator<Item = NodeId> + '_ {
+        (0..self.nodes.len()).filter(move |&id| self.is_element(id))
+    }
+
+    pub(crate) fn is_element(&self, id: NodeId) -> bool {
+        matches!(self.nodes[id].data, NodeData::Element(_))
+    }
+
+    pub(crate) fn is_text(&self, id: NodeId) -> bool {
+        matches!(self.nodes[id].data, NodeData::Text(_))
+    }
+
+    pub(crate) fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.nodes[id].parent
+    }
+
+    pub(crate) fn children(&self, id: NodeId) -> &[NodeId] {
+        &self.nodes[id].children
+    }
+
+    pub(crate) fn child_elements(&self, id: NodeId) -> Vec<NodeId> {
+        self.children(id)
+            .iter()
+            .copied()
+            .filter(|&id| self.is_element(id))
+            .collect()
+    }
+
+    /// Returns all descendants of a node in a document order.
+    pub(crate) fn descendants(&self, id: NodeId) -> Vec<NodeId> {
+        let mut nodes = Vec::new();
+        let mut stack = self.children(id).iter().rev().copied().collect::<Vec<_>>();
+        while let Some(id) = stack.pop() {
+            nodes.push(id);
+            stack.extend(self.children(id).iter().rev());
+        }
+
+        nodes
+    }
+
+    /// Returns the node and its ancestors starting from the node.
+    pub(crate) fn ancestors(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        std::iter::successors(Some(id), move |&id| self.parent(id))
+    }
+
+    fn element(&self, id: NodeId) -> &ElementData {
+        match &self.nodes[id].data {
+            NodeData::Element(e) => e,
+            _ => unreachable!("node {} is not an element", id),
+        }
+    }
+
+    fn element_mut(&mut self, id: NodeId) -> &mut ElementData {
+        match &mut self.nodes[id].data {
+            NodeData::Element(e) => e,
+            _ => unreachable!("node {} is not an element", id),
+        }
+    }
+
+    pub(crate) fn tag(&self, id: NodeId) -> Option<&str> {
+        match &self.nodes[id].data {
+            NodeData::Element(e) => Some(&e.tag),
+            _ => None,
+        }
+    }
+
+    /// Returns a value of an attribute as it's written in the markup.
+    pub(crate) fn attr(&self, id: NodeId, name: &str) -> Option<&str> {
+        match &self.nodes[id].data {
+            NodeData::Element(e) => e
+                .attributes
+                .iter()
+                .find(|(attr, _)| attr == name)
+                .map(|(_, value)| value.as_str()),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn attrs(&self, id: NodeId) -> &[(String, String)] {
+        match &self.nodes[id].data {
+            NodeData::Element(e) => &e.attributes,
+            _ => &[],
+        }
+    }
+
+    pub(crate) fn title(&self) -> String {
+        self.elements()
+            .find(|&id| self.tag(id) == Some("title"))
+            .map(|id| collapse_whitespace(&self.text_content(id)))
+            .unwrap_or_default()
+    }
+
+    /// Returns `iframe` and `frame` elements in a document order.
+    pub(crate) fn frames(&self) -> Vec<NodeId> {
+        self.elements()
+            .filter(|&id| matches!(self.tag(id), Some("iframe") | Some("frame")))
+            .collect()
+    }
+
+    /// Returns a concatenation of all text nodes including hidden ones.
+    pub(crate) fn text_content(&self, id: NodeId) -> String {
+        match &self.nodes[id].data {
+            NodeData::Text(text) => text.clone(),
+            _ => self
+                .descendants(id)
+                .into_iter()
+                .filter_map(|id| match &self.nodes[id].data {
+                    NodeData::Text(text) => Some(text.as_str()),
+                    _ => None,
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns a text as a user would see it.
+    pub(crate) fn rendered_text(&self, id: NodeId) -> String {
+        if !self.is_displayed(id) {
+            return String::new();
+        }
+
+        let mut text = String::new();
+        self.collect_rendered_text(id, &mut text);
+        text.split('\n')
+            .map(collapse_whitespace)
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn collect_rendered_text(&self, id: NodeId, text: &mut String) {
+        match &self.nodes[id].data {
+            NodeData::Text(t) => text.push_str(&t.replace('\n', " ")),
+            NodeData::Element(e) => {
+                if !self.is_rendered(id) {
+                    return;
+                }
+
+                if e.tag == "br" {
+                    text.push('\n');
+                }
+
+                let block = BLOCK_ELEMENTS.contains(&e.tag.as_str());
+                if block {
+                    text.push('\n');
+                }
+                for &child in self.children(id) {
+                    self.collect_rendered_text(child, text);
+                }
+                if block {
+                    text.push('\n');
+                }
+            }
+            NodeData::Document => {
+                for &child in self.children(id) {
+                    self.collect_rendered_text(child, text);
+                }
+            }
+        }
+    }
+
+    pub(crate) fn inner_html(&self, id: NodeId) -> String {
+        let mut html = String::new();
+        for &child in self.children(id) {
+            self.serialize(child, &mut html);
+        }
+
+        html
+    }
+
+    pub(crate) fn outer_html(&self, id: NodeId) -> String {
+        let mut html = String::new();
+        self.serialize(id, &mut html);
+        html
+    }
+
+    fn serialize(&self, id: NodeId, html: &mut String) {
+        match &self.nodes[id].data {
+            NodeData::Text(text) => {
+                let raw = self
+                    .parent(id)
+                    .and_then(|parent| self.tag(parent))
+                    .is_some_and(|tag| tag == "script" || tag == "style");
+                if raw {
+                    html.push_str(text);
+                } else {
+                    html.push_str(&escape(text, false));
+                }
+            }
+            NodeData::Element(e) => {
+                html.push('<');
+                html.push_str(&e.tag);
+                for (name, value) in &e.attributes {
+                    html.push_str(&format!(" {}=\"{}\"", name, escape(value, true)));
+                }
+                html.push('>');
+                if VOID_ELEMENTS.contains(&e.tag.as_str()) {
+                    return;
+                }
+                html.push_str(&self.inner_html(id));
+                html.push_str(&format!("</{}>", e.tag));
+            }
+            NodeData::Document => html.push_str(&self.inner_html(id)),
+        }
+    }
+
+    /// Checks whether the element itself is rendered without looking at its ancestors.
+    fn is_rendered(&self, id: NodeId) -> bool {
+        let tag = match self.tag(id) {
+            Some(tag) => tag,
+            None => return true,
+        };
+        if NOT_RENDERED_ELEMENTS.contains(&tag) || self.attr(id, "hidden").is_some() {
+            return false;
+        }
+        if tag == "input" && self.attr(id, "type") == Some("hidden") {
+            return false;
+        }
+
+        let style = self
+            .attr(id, "style")
+            .unwrap_or("")
+            .to_ascii_lowercase()
+            .replace(' ', "");
+        !style.contains("display:none") && !style.contains("visibility:hidden")
+    }
+
+    pub(crate) fn is_displayed(&self, id: NodeId) -> bool {
+        self.ancestors(id).all(|id| self.is_rendered(id))
+    }
+
+    pub(crate) fn is_enabled(&self, id: NodeId) -> bool {
+        let is_control = |id| self.tag(id).is_some_and(|tag| FORM_CONTROLS.contains(&tag));
+        !is_control(id)
+            || !self
+                .ancestors(id)
+                .any(|id| is_control(id) && self.attr(id, "disabled").is_some())
+    }
+
+    pub(crate) fn is_selected(&self, id: NodeId) -> bool {
+        match self.tag(id) {
+            Some("input") => self.element(id).checked,
+            Some("option") => self.element(id).selected,
+            _ => false,
+        }
+    }
+
+    fn input_type(&self, id: NodeId) -> String {
+        self.attr(id, "type").unwrap_or("text").to_ascii_lowercase()
+    }
+
+    fn is_editable(&self, id: NodeId) -> bool {
+        match self.tag(id) {
+            Some("textarea") => true,
+            Some("input") => !matches!(
+                self.input_type(id).as_str(),
+                "button" | "checkbox" | "hidden" | "image" | "radio" | "reset" | "submit"
+            ),
+            _ => false,
+        }
+    }
+
+    /// Returns options of a `select` element.
+    fn options(&self, select: NodeId) -> Vec<NodeId> {
+        self.descendants(select)
+            .into_iter()
+            .filter(|&id| self.tag(id) == Some("option"))
+            .collect()
+    }
+
+    fn value(&self, id: NodeId) -> String {
+        match self.tag(id) {
+            Some("input") => match &self.element(id).value {
+                Some(value) => value.clone(),
+                None => match self.attr(id, "value") {
+                    Some(value) => value.to_owned(),
+                    None if matches!(self.input_type(id).as_str(), "checkbox" | "radio") => {
+                        "on".to_owned()
+                    }
+                    None => String::new(),
+                },
+            },
+            Some("textarea") => self
+                .element(id)
+                .value
+                .clone()
+                .unwrap_or_else(|| self.text_content(id)),
+            Some("select") => self
+                .options(id)
+                .into_iter()
+                .find(|&option| self.element(option).selected)
+                .map(|option| self.value(option))
+                .unwrap_or_default(),
+            Some("option") => self
+                .attr(id, "value")
+                .map(|value| value.to_owned())
+                .unwrap_or_else(|| collapse_whitespace(&self.text_content(id))),
+            _ => self.attr(id, "value").unwrap_or("").to_owned(),
+        }
+    }
+
+    /// Returns a value of a DOM property.
+    pub(crate) fn property(&self, id: NodeId, name: &str) -> Json {
+        let tag = self.tag(id).unwrap_or("");
+        match name {
+            "value" if matches!(tag, "input" | "textarea" | "select" | "option" | "button") => {
+                Json::String(self.value(id))
+            }
+            "checked" if tag == "input" => Json::Bool(self.element(id).checked),
+            "selected" if tag == "option" => Json::Bool(self.element(id).selected),
+            "selectedIndex" if tag == "select" => {
+                let index = self
+                    .options(id)
+                    .into_iter()
+                    .position(|option| self.element(option).selected);
+                index.map_or(Json::from(-1), Json::from)
+            }
+            "index" if tag == "option" => {
+                let index = self
+                    .ancestors(id)
+                    .find(|&id| self.tag(id) == Some("select"))
+                    .and_then(|select| self.options(select).iter().position(|&o| o == id))
+                    .unwrap_or(0);
+                Json::from(index)
+            }
+            "disabled" if FORM_CONTROLS.contains(&tag) => {
+                Json::Bool(self.attr(id, "disabled").is_some())
+            }
+            "readOnly" if matches!(tag, "input" | "textarea") => {
+                Json::Bool(self.attr(id, "readonly").is_some())
+            }
+            "type" if tag == "input" => Json::String(self.input_type(id)),
+            "href" if tag == "a" || tag == "link" => match self.attr(id, "href") {
+                Some(href) => self
+                    .url
+                    .join(href)
+                    .map_or(Json::String(href.to_owned()), |url| {
+                        Json::String(url.into())
+                    }),
+                None => Json::String(String::new()),
+            },
+            "innerHTML" => Json::String(self.inner_html(id)),
+            "outerHTML" => Json::String(self.outer_html(id)),
+            "textContent" => Json::String(self.text_content(id)),
+            "innerText" => Json::String(self.rendered_text(id)),
+            "tagName" | "nodeName" => Json::String(tag.to_ascii_uppercase()),
+            "id" => Json::String(self.attr(id, "id").unwrap_or("").to_owned()),
+            "className" => Json::String(self.attr(id, "class").unwrap_or("").to_owned()),
+            name if REFLECTED_PROPERTIES.contains(&name) => {
+                Json::String(self.attr(id, name).unwrap_or("").to_owned())
+            }
+            _ => Json::Null,
+        }
+    }
+
+    /// Returns an attribute the way Selenium's `getAttribute` does.
+    ///
+    /// Boolean attributes are reported as `"true"`
+    /// and a few attributes fall back to properties.
+    pub(crate) fn attribute(&self, id: NodeId, name: &str) -> Option<String> {
+        let name = name.to_ascii_lowercase();
+        match name.as_str() {
+            "checked" | "selected" if matches!(self.tag(id), Some("input") | Some("option")) => {
+                Some("true".to_owned()).filter(|_| self.is_selected(id))
+            }
+            name if BOOLEAN_ATTRIBUTES.contains(&name) => self
+                .attr(id, name)
+                .filter(|value| *value != "false")
+                .map(|_| "true".to_owned()),
+            "value" | "index" => match self.property(id, &name) {
+                Json::Null => self.attr(id, &name).map(|value| value.to_owned()),
+                Json::String(value) => Some(value),
+                value => Some(value.to_string()),
+            },
+            name => self.attr(id, name).map(|value| value.to_owned()),
+        }
+    }
+
+    /// Clicks on an element.
+    ///
+    /// Only default actions are modeled as there are no scripts on a page.
+    pub(crate) fn click(&mut self, id: NodeId) -> Option<Navigation> {
+        if !self.is_enabled(id) {
+            return None;
+        }
+
+        match self.tag(id) {
+            Some("input") => match self.input_type(id).as_str() {
+                "checkbox" => {
+                    let checked = self.element(id).checked;
+                    self.element_mut(id).checked = !checked;
+                }
+                "radio" => self.check_radio(id),
+                "submit" | "image" => return self.submit(id),
+                _ => (),
+            },
+            Some("button") => {
+                if matches!(self.attr(id, "type"), None | Some("submit")) {
+                    return self.submit(id);
+                }
+            }
+            Some("option") => self.select_option(id),
+            Some("label") => {
+                let control = match self.attr(id, "for") {
+                    Some(target) => self
+                        .elements()
+                        .find(|&e| self.attr(e, "id") == Some(target)),
+                    None => self
+                        .descendants(id)
+                        .into_iter()
+                        .find(|&e| FORM_CONTROLS.contains(&self.tag(e).unwrap_or(""))),
+                };
+                if let Some(control) = control.filter(|&control| control != id) {
+                    return self.click(control);
+                }
+            }
+            _ => (),
+        }
+
+        let link = self
+            .ancestors(id)
+            .find(|&id| self.tag(id) == Some("a") && self.attr(id, "href").is_some())?;
+        let href = self.attr(link, "href")?;
+        if href.starts_with('#') || href.starts_with("javascript:") {
+            return None;
+        }
+
+        self.url.join(href).ok().map(|url| Navigation {
+            url,
+            new_window: self.attr(link, "target") == Some("_blank"),
+        })
+    }
+
+    fn check_radio(&mut self, id: NodeId) {
+        let form = self.form(id);
+        let name = self.attr(id, "name").map(|name| name.to_owned());
+        let group = self
+            .elements()
+            .filter(|&e| {
+                self.tag(e) == Some("input")
+                    && self.input_type(e) == "radio"
+                    && name.is_some()
+                    && self.attr(e, "name") == name.as_deref()
+                    && self.form(e) == form
+            })
+            .collect::<Vec<_>>();
+        for radio in group {
+            self.element_mut(radio).checked = false;
+        }
+
+        self.element_mut(id).checked = true;
+    }
+
+    fn select_option(&mut self, id: NodeId) {
+        let select = match self.ancestors(id).find(|&e| self.tag(e) == Some("select")) {
+            Some(select) => select,
+            None => return,
+        };
+
+        if self.attr(select, "multiple").is_some() {
+            let selected = self.element(id).selected;
+            self.element_mut(id).selected = !selected;
+        } else {
+            for option in self.options(select) {
+                self.element_mut(option).selected = option == id;
+            }
+        }
+    }
+
+    fn form(&self, id: NodeId) -> Option<NodeId> {
+        self.ancestors(id).find(|&e| self.tag(e) == Some("form"))
+    }
+
+    /// Submits a form an element belongs to.
+    pub(crate) fn submit(&mut self, id: NodeId) -> Option<Navigation> {
+        let form = self.form(id)?;
+        let mut url = self
+            .url
+            .join(self.attr(form, "action").unwrap_or(""))
+            .ok()?;
+        url.set_fragment(None);
+
+        let method = self
+            .attr(form, "method")
+            .unwrap_or("get")
+            .to_ascii_lowercase();
+        if method == "get" {
+            let fields = self.form_fields(form);
+            url.set_query(None);
+            if !fields.is_empty() {
+                url.query_pairs_mut().extend_pairs(fields);
+            }
+        }
+
+        Some(Navigation {
+            url,
+            new_window: self.attr(form, "target") == Some("_blank"),
+        })
+    }
+
+    fn form_fields(&self, form: NodeId) -> Vec<(String, String)> {
+        let mut fields = Vec::new();
+        for id in self.descendants(form) {
+            let name = match self.attr(id, "name") {
+                Some(name) if self.is_enabled(id) => name.to_owned(),
+                _ => continue,
+            };
+            match self.tag(id) {
+                Some("input") => match self.input_type(id).as_str() {
+                    "checkbox" | "radio" if !self.element(id).checked => (),
+                    "button" | "image" | "reset" | "submit" => (),
+                    _ => fields.push((name, self.value(id))),
+                },
+                Some("textarea") => fields.push((name, self.value(id))),
+                Some("select") => {
+                    for option in self.options(id) {
+                        if self.element(option).selected {
+                            fields.push((name.clone(), self.value(option)));
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        fields
+    }
+
+    /// Types a text into an element.
+    ///
+    /// WebDriver special keys are ignored except of `Backspace` and `Enter`.
+    pub(crate) fn send_keys(&mut self, id: NodeId, text: &str) -> Option<Navigation> {
+        if !self.is_editable(id) || !self.is_enabled(id) || self.attr(id, "readonly").is_some() {
+            return None;
+        }
+
+        let mut value = self.value(id);
+        for c in text.chars() {
+            match c {
+                '\u{E003}' => {
+                    value.pop();
+                }
+                '\u{E006}' | '\u{E007}' if self.tag(id) == Some("input") => {
+                    self.element_mut(id).value = Some(value);
+                    return self.submit(id);
+                }
+                '\u{E007}' => value.push('\n'),
+                '\u{E000}'..='\u{F8FF}' => (),
+                c => value.push(c),
+            }
+        }
+
+        self.element_mut(id).value = Some(value);
+        None
+    }
+
+    pub(crate) fn clear(&mut self, id: NodeId) {
+        if self.is_editable(id) {
+            self.element_mut(id).value = Some(String::new());
+        }
+    }
+
+    pub(crate) fn source(&self) -> String {
+        self.inner_html(ROOT)
+    }
+}
+
+fn has_attribute(attributes: &[(String, String)], name: &str) -> bool {
+    attributes.iter().any(|(attr, _)| attr == name)
+}
+
+/// Returns tags which are closed by an opening tag.
+fn implicitly_closes(tag: &str) -> &'static [&'static str] {
+    match tag {
+        "li" => &["li"],
+        "option" => &["option"],
+        "optgroup" => &["optgroup", "option"],
+        "p" => &["p"],
+        "dt" | "dd" => &["dt", "dd"],
+        "tr" => &["tr", "td", "th"],
+        "td" | "th" => &["td", "th"],
+        _ => &[],
+    }
+}
+
+/// Parses a start tag which goes after `<`.
+fn parse_tag(s: &str) -> (String, Vec<(String, String)>, bool, &str) {
+    let end = s
+        .find(|c: char| c.is_whitespace() || c == '/' || c == '>')
+        .unwrap_or(s.len());
+    let tag = s[..end].to_ascii_lowercase();
+    let mut rest = &s[end..];
+    let mut attributes = Vec::new();
+
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            return (tag, attributes, false, rest);
+        } else if let Some(tail) = rest.strip_prefix('>') {
+            return (tag, attributes, false, tail);
+        } else if let Some(tail) = rest.strip_prefix("/>") {
+            return (tag, attributes, true, tail);
+        } else if let Some(tail) = rest.strip_prefix('/') {
+            rest = tail;
+            continue;
+        }
+
+        let end = rest
+            .find(|c: char| c.is_whitespace() || c == '=' || c == '>' || c == '/')
+            .unwrap_or(rest.len());
+        let name = rest[..end].to_ascii_lowercase();
+        rest = rest[end..].trim_start();
+
+        let mut value = String::new();
+        if let Some(tail) = rest.strip_prefix('=') {
+            let tail = tail.trim_start();
+            let quote = tail.chars().next().filter(|&c| c == '"' || c == '\'');
+            let (raw, tail) = match quote {
+                Some(quote) => {
+                    let tail = &tail[1..];
+                    let end = tail.find(quote).unwrap_or(tail.len());
+                    (&tail[..end], tail.get(end + 1..).unwrap_or(""))
+                }
+                None => {
+                    let end = tail
+                        .find(|c: char| c.is_whitespace() || c == '>')
+                        .unwrap_or(tail.len());
+                    (&tail[..end], &tail[end..])
+                }
+            };
+            value = decode_entities(raw);
+            rest = tail;
+        }
+
+        if !name.is_empty() && !has_attribute(&attributes, &name) {
+            attributes.push((name, value));
+        }
+    }
+}
+
+/// Splits a content of a raw text element from the rest of a document.
+fn raw_text<'a>(s: &'a str, tag: &str) -> (&'a str, &'a str) {
+    let closing = format!("</{}", tag);
+    let end = s.to_ascii_lowercase().find(&closing);
+    match end {
+        Some(end) => {
+            let tail = &s[end..];
+            let tail = tail.find('>').map_or("", |i| &tail[i + 1..]);
+            (&s[..end], tail)
+        }
+        None => (s, ""),
+    }
+}
+
+pub(crate) fn collapse_whitespace(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn decode_entities(s: &str) -> String {
+    let mut decoded = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest[1..]
+            .find(';')
+            .filter(|&end| end <= 10)
+            .map(|end| &rest[1..end + 1]);
+        let c = entity.and_then(|entity| match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some('\u{a0}'),
+            _ => entity
+                .strip_prefix("#x")
+                .or_else(|| entity.strip_prefix("#X"))
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| entity.strip_prefix('#').and_then(|n| n.parse().ok()))
+                .and_then(std::char::from_u32),
+        });
+
+        match (entity, c) {
+            (Some(entity), Some(c)) => {
+                decoded.push(c);
+                rest = &rest[entity.len() + 2..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+
+    decoded
+}
+
+fn escape(s: &str, attribute: bool) -> String {
+    let s = s
+        .replace('&', "&amp;")
+        .replace('\u{a0}', "&nbsp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;");
+    if attribute {
+        s.replace('"', "&quot;")
+    } else {
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(html: &str) -> Document {
+        Document::parse(Url::parse("http://localhost/index.html").unwrap(), html)
+    }
+
+    fn by_id(doc: &Document, id: &str) -> NodeId {
+        doc.elements()
+            .find(|&e| doc.attr(e, "id") == Some(id))
+            .unwrap()
+    }
+
+    #[test]
+    fn parse_document() {
+        let doc = parse(
+            r#"<!DOCTYPE html>
+            <html>
+            <head><title>A &amp; B</title><script>if (a < b) {}</script></head>
+            <body>
+                <!-- comment -->
+                <p id="p">First<br>second <b>bold</b></p>
+                <ul><li id="one">1<li id="two">2</ul>
+                <div id="hidden" style="display: none">hidden</div>
+                <input id="input" value='x' disabled />
+            </body>
+            </html>"#,
+        );
+
+        assert_eq!(doc.title(), "A & B");
+        assert_eq!(doc.rendered_text(by_id(&doc, "p")), "First\nsecond bold");
+        assert_eq!(
+            doc.inner_html(by_id(&doc, "p")),
+            "First<br>second <b>bold</b>"
+        );
+        assert_eq!(
+            doc.parent(by_id(&doc, "two")),
+            doc.parent(by_id(&doc, "one"))
+        );
+        assert_eq!(doc.rendered_text(by_id(&doc, "hidden")), "");
+        assert!(!doc.is_displayed(by_id(&doc, "hidden")));
+        assert!(!doc.is_enabled(by_id(&doc, "input")));
+        assert_eq!(
+            doc.attribute(by_id(&doc, "input"), "disabled").as_deref(),
+            Some("true")
+        );
+        assert_eq!(doc.property(by_id(&doc, "input"), "value"), Json::from("x"));
+    }
+
+    #[test]
+    fn form_controls() {
+        let mut doc = parse(
+            r#"<form action="/search">
+                <input type="checkbox" id="check" name="check">
+                <input type="radio" id="r1" name="r" value="1" checked>
+                <input type="radio" id="r2" name="r" value="2">
+                <select id="select" name="s"><option value="a">A<option id="b">B</select>
+                <input id="text" name="q">
+            </form>"#,
+        );
+
+        let (check, r1, r2) = (by_id(&doc, "check"), by_id(&doc, "r1"), by_id(&doc, "r2"));
+        assert!(doc.click(check).is_none());
+        assert_eq!(doc.property(check, "checked"), Json::Bool(true));
+        assert_eq!(doc.property(check, "selected"), Json::Null);
+        doc.click(r2);
+        assert!(!doc.is_selected(r1));
+        assert!(doc.is_selected(r2));
+
+        let select = by_id(&doc, "select");
+        assert_eq!(doc.property(select, "value"), Json::from("a"));
+        doc.click(by_id(&doc, "b"));
+        assert_eq!(doc.property(select, "value"), Json::from("B"));
+        assert_eq!(
+            doc.attribute(by_id(&doc, "b"), "index").as_deref(),
+            Some("1")
+        );
+
+        let text = by_id(&doc, "text");
+        doc.send_keys(text, "rusz\u{E003}t");
+        let navigation = doc.send_keys(text, "\u{E007}").unwrap();
+        assert_eq!(
+            navigation.url.as_str(),
+            "http://localhost/search?check=on&r=2&s=B&q=rust"
+        );
+    }
+}
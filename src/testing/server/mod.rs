@@ -0,0 +1,874 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! A fake WebDriver server for hermetic integration tests.
+//!
+//! [`WebdriverServer`] speaks W3C WebDriver protocol over HTTP on localhost,
+//! so a real backend can be used without a browser, Docker or network access.
+//!
+//! Pages are static HTML documents which are registered in memory or served from a directory.
+//! Elements are looked up by CSS selectors, XPath, link text and tag name,
+//! and default actions of clicks and typing (links, checkboxes, radio buttons, options, forms)
+//! are modeled. Scripts on pages are never run; results of executed scripts
+//! can be set by [`WebdriverServer::add_script_result`]. There are no dialogs.
+//!
+//! ```no_run
+//! # #[cfg(feature = "thirtyfour_backend")]
+//! # async fn run(file: &siderunner::File) -> Result<(), Box<dyn std::error::Error>> {
+//! use siderunner::{testing::server::WebdriverServer, Runner};
+//! use thirtyfour::{DesiredCapabilities, WebDriver};
+//!
+//! let server = WebdriverServer::start()?;
+//! server.serve_dir("http://localhost:8000/", ".");
+//!
+//! let wb = WebDriver::new(&server.url(), DesiredCapabilities::chrome()).await?;
+//! let mut runner = Runner::new(&wb);
+//! runner.run(file).await.map_err(|err| format!("{:?}", err))?;
+//! wb.quit().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+mod html;
+mod selector;
+mod xpath;
+
+use html::{Document, Navigation, NodeId};
+use selector::FindError;
+use serde_json::{json, Value as Json};
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+use url::Url;
+
+const ELEMENT_KEY: &str = "element-6066-11e4-a52e-4f735466cecf";
+const WINDOW_SIZE: (u64, u64) = (1280, 720);
+/// A transparent 1x1 PNG image.
+const SCREENSHOT: &str =
+    "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mNkYPhfDwAChwGA60e6kgAAAABJRU5ErkJggg==";
+
+/// WebdriverServer is a WebDriver server which runs in a background thread.
+///
+/// The server listens on a random port of `127.0.0.1` and stops when it's dropped.
+pub struct WebdriverServer {
+    addr: SocketAddr,
+    state: Arc<Mutex<State>>,
+    running: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl WebdriverServer {
+    /// Starts a server.
+    pub fn start() -> io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(State::default()));
+        let running = Arc::new(AtomicBool::new(true));
+
+        let thread = {
+            let state = state.clone();
+            let running = running.clone();
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if !running.load(Ordering::SeqCst) {
+                        break;
+                    }
+
+                    if let Ok(stream) = stream {
+                        let state = state.clone();
+                        thread::spawn(move || {
+                            // A client which went away isn't a concern of the server.
+                            let _ = handle_connection(stream, &state);
+                        });
+                    }
+                }
+            })
+        };
+
+        Ok(Self {
+            addr,
+            state,
+            running,
+            thread: Some(thread),
+        })
+    }
+
+    /// Returns a URL which a client should connect to.
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Registers a page which is opened by a given URL.
+    pub fn add_page(&self, url: &str, html: &str) {
+        lock(&self.state)
+            .site
+            .pages
+            .insert(normalize_url(url), html.to_owned());
+    }
+
+    /// Serves files of a directory by URLs which start with a given base URL.
+    ///
+    /// Files are read on each navigation.
+    pub fn serve_dir<P: Into<PathBuf>>(&self, base_url: &str, dir: P) {
+        let mut base_url = normalize_url(base_url);
+        if !base_url.ends_with('/') {
+            base_url.push('/');
+        }
+
+        lock(&self.state).site.dirs.push((base_url, dir.into()));
+    }
+
+    /// Sets a value returned by scripts which contain a given pattern.
+    ///
+    /// Patterns are checked in the order they were added.
+    /// A script which doesn't match any pattern returns `null`.
+    pub fn add_script_result<S: Into<String>>(&self, pattern: S, value: Json) {
+        lock(&self.state).site.scripts.push((pattern.into(), value));
+    }
+}
+
+impl Drop for WebdriverServer {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+        // The listener is blocked in accept so it's woken up by a connection.
+        let _ = TcpStream::connect(self.addr);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn lock(state: &Mutex<State>) -> MutexGuard<'_, State> {
+    state.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+fn normalize_url(url: &str) -> String {
+    Url::parse(url).map_or_else(|_| url.to_owned(), String::from)
+}
+
+#[derive(Default)]
+struct State {
+    site: Site,
+    sessions: HashMap<String, Session>,
+    last_session: u64,
+}
+
+/// Content which is shared by all sessions.
+#[derive(Default)]
+struct Site {
+    pages: HashMap<String, String>,
+    dirs: Vec<(String, PathBuf)>,
+    scripts: Vec<(String, Json)>,
+}
+
+impl Site {
+    fn load(&self, url: Url) -> Document {
+        let html = self.html(&url).unwrap_or_default();
+        Document::parse(url, &html)
+    }
+
+    fn html(&self, url: &Url) -> Option<String> {
+        let mut url = url.clone();
+        url.set_fragment(None);
+        if let Some(html) = self.pages.get(url.as_str()) {
+            return Some(html.clone());
+        }
+
+        url.set_query(None);
+        if let Some(html) = self.pages.get(url.as_str()) {
+            return Some(html.clone());
+        }
+
+        self.dirs.iter().find_map(|(base, dir)| {
+            let path = url.as_str().strip_prefix(base.as_str())?;
+            std::fs::read_to_string(dir.join(percent_decode(path))).ok()
+        })
+    }
+
+    fn script_result(&self, script: &str) -> Json {
+        self.scripts
+            .iter()
+            .find(|(pattern, _)| script.contains(pattern.as_str()))
+            .map_or(Json::Null, |(_, value)| value.clone())
+    }
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .filter(|_| bytes[i] == b'%')
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match hex {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// An error response of WebDriver protocol.
+struct WebdriverError {
+    status: u16,
+    error: &'static str,
+    message: String,
+}
+
+impl WebdriverError {
+    fn new<S: Into<String>>(status: u16, error: &'static str, message: S) -> Self {
+        Self {
+            status,
+            error,
+            message: message.into(),
+        }
+    }
+
+    fn invalid_argument<S: Into<String>>(message: S) -> Self {
+        Self::new(400, "invalid argument", message)
+    }
+
+    fn no_such_element<S: Into<String>>(message: S) -> Self {
+        Self::new(404, "no such element", message)
+    }
+
+    fn no_such_window() -> Self {
+        Self::new(404, "no such window", "the current window is closed")
+    }
+
+    fn unknown_command() -> Self {
+        Self::new(404, "unknown command", "the command isn't supported")
+    }
+}
+
+impl From<FindError> for WebdriverError {
+    fn from(err: FindError) -> Self {
+        match err {
+            FindError::InvalidArgument(message) => Self::invalid_argument(message),
+            FindError::InvalidSelector(message) => Self::new(400, "invalid selector", message),
+        }
+    }
+}
+
+fn handle_connection(stream: TcpStream, state: &Mutex<State>) -> io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(10)))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut request_line = request_line.split_whitespace();
+    let method = request_line.next().unwrap_or("").to_owned();
+    let path = request_line.next().unwrap_or("").to_owned();
+
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    let body = serde_json::from_slice(&body).unwrap_or(Json::Null);
+
+    let (status, value) = match route(state, &method, &path, &body) {
+        Ok(value) => (200, value),
+        Err(err) => (
+            err.status,
+            json!({ "error": err.error, "message": err.message, "stacktrace": "" }),
+        ),
+    };
+
+    let body = json!({ "value": value }).to_string();
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        _ => "Internal Server Error",
+    };
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+fn route(
+    state: &Mutex<State>,
+    method: &str,
+    path: &str,
+    body: &Json,
+) -> Result<Json, WebdriverError> {
+    let path = path.split('?').next().unwrap_or("");
+    let segments = path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(percent_decode)
+        .collect::<Vec<_>>();
+    let mut segments = segments.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+    // Clients which used to talk to Selenium Grid keep its prefix.
+    if segments.starts_with(&["wd", "hub"]) {
+        segments.drain(..2);
+    }
+
+    let mut state = lock(state);
+    let state = &mut *state;
+    match (method, segments.as_slice()) {
+        ("GET", ["status"]) => Ok(json!({ "ready": true, "message": "siderunner fake webdriver" })),
+        ("POST", ["session"]) => {
+            state.last_session += 1;
+            let id = format!("session-{}", state.last_session);
+            state.sessions.insert(id.clone(), Session::new(&state.site));
+            Ok(json!({
+                "sessionId": id,
+                "capabilities": {
+                    "browserName": "siderunner",
+                    "browserVersion": env!("CARGO_PKG_VERSION"),
+                    "platformName": std::env::consts::OS,
+                    "acceptInsecureCerts": false,
+                    "pageLoadStrategy": "normal",
+                    "setWindowRect": true,
+                    "unhandledPromptBehavior": "ignore",
+                },
+            }))
+        }
+        ("DELETE", ["session", id]) => {
+            state.sessions.remove(*id);
+            Ok(Json::Null)
+        }
+        (_, ["session", id, command @ ..]) => {
+            let session = state
+                .sessions
+                .get_mut(*id)
+                .ok_or_else(|| WebdriverError::new(404, "invalid session id", "no such session"))?;
+            session.handle(&state.site, method, command, body)
+        }
+        _ => Err(WebdriverError::unknown_command()),
+    }
+}
+
+struct Window {
+    handle: String,
+    /// Documents of the window and its selected frames.
+    contexts: Vec<usize>,
+    size: (u64, u64),
+}
+
+struct Session {
+    documents: Vec<Document>,
+    /// Documents which are loaded into frames by (a parent document, a frame element).
+    frames: HashMap<(usize, NodeId), usize>,
+    windows: Vec<Window>,
+    current: Option<usize>,
+    last_window: u64,
+}
+
+impl Session {
+    fn new(site: &Site) -> Self {
+        let mut session = Self {
+            documents: Vec::new(),
+            frames: HashMap::new(),
+            windows: Vec::new(),
+            current: Some(0),
+            last_window: 0,
+        };
+        session.open_window(site, Url::parse("about:blank").unwrap());
+        session
+    }
+
+    fn handle(
+        &mut self,
+        site: &Site,
+        method: &str,
+        command: &[&str],
+        body: &Json,
+    ) -> Result<Json, WebdriverError> {
+        match (method, command) {
+            ("GET", ["timeouts"]) => {
+                Ok(json!({ "implicit": 0, "pageLoad": 300000, "script": 30000 }))
+            }
+            ("POST", ["timeouts"]) => Ok(Json::Null),
+            ("POST", ["url"]) => {
+                let url = string_param(body, "url")?;
+                let url = Url::parse(url)
+                    .map_err(|err| WebdriverError::invalid_argument(err.to_string()))?;
+                self.navigate(site, url)?;
+                Ok(Json::Null)
+            }
+            ("GET", ["url"]) => {
+                let top = self.window()?.contexts[0];
+                Ok(Json::String(self.documents[top].url.to_string()))
+            }
+            ("POST", ["refresh"]) => {
+                let url = self.document()?.url.clone();
+                self.navigate(site, url)?;
+                Ok(Json::Null)
+            }
+            ("GET", ["title"]) => {
+                let top = self.window()?.contexts[0];
+                Ok(Json::String(self.documents[top].title()))
+            }
+            ("GET", ["source"]) => Ok(Json::String(self.document()?.source())),
+            ("GET", ["window"]) => Ok(Json::String(self.window()?.handle.clone())),
+            ("DELETE", ["window"]) => {
+                let current = self
+                    .current
+                    .take()
+                    .ok_or_else(WebdriverError::no_such_window)?;
+                self.windows.remove(current);
+                Ok(self.window_handles())
+            }
+            ("POST", ["window"]) => {
+                let handle = string_param(body, "handle")?;
+                let index = self
+                    .windows
+                    .iter()
+                    .position(|window| window.handle == handle)
+                    .ok_or_else(WebdriverError::no_such_window)?;
+                self.current = Some(index);
+                Ok(Json::Null)
+            }
+            ("GET", ["window", "handles"]) => Ok(self.window_handles()),
+            ("GET", ["window", "rect"]) => Ok(self.window_rect()?),
+            ("POST", ["window", "rect"]) => {
+                let window = self.window_mut()?;
+                let param = |name| body.get(name).and_then(Json::as_u64);
+                if let (Some(width), Some(height)) = (param("width"), param("height")) {
+                    window.size = (width, height);
+                }
+                Ok(self.window_rect()?)
+            }
+            ("POST", ["window", "maximize"]) | ("POST", ["window", "fullscreen"]) => {
+                self.window_mut()?.size = WINDOW_SIZE;
+                Ok(self.window_rect()?)
+            }
+            ("POST", ["window", "minimize"]) => Ok(self.window_rect()?),
+            ("POST", ["frame"]) => {
+                self.switch_to_frame(site, body.get("id").unwrap_or(&Json::Null))?;
+                Ok(Json::Null)
+            }
+            ("POST", ["frame", "parent"]) => {
+                let window = self.window_mut()?;
+                if window.contexts.len() > 1 {
+                    window.contexts.pop();
+                }
+                Ok(Json::Null)
+            }
+            ("POST", ["element"]) => self.find(body, html::ROOT, true),
+            ("POST", ["elements"]) => self.find(body, html::ROOT, false),
+            ("GET", ["element", "active"]) => {
+                let doc = self.document()?;
+                let body = doc
+                    .elements()
+                    .find(|&id| doc.tag(id) == Some("body"))
+                    .ok_or_else(|| WebdriverError::no_such_element("there's no active element"))?;
+                Ok(self.element_reference(body)?)
+            }
+            ("POST", ["element", element, "element"]) => {
+                let node = self.element(element)?;
+                self.find(body, node, true)
+            }
+            ("POST", ["element", element, "elements"]) => {
+                let node = self.element(element)?;
+                self.find(body, node, false)
+            }
+            ("GET", ["element", element, query]) => {
+                let node = self.element(element)?;
+                let doc = self.document()?;
+                let value = match *query {
+                    "selected" => Json::Bool(doc.is_selected(node)),
+                    "displayed" => Json::Bool(doc.is_displayed(node)),
+                    "enabled" => Json::Bool(doc.is_enabled(node)),
+                    "text" => Json::String(doc.rendered_text(node)),
+                    "name" => Json::String(doc.tag(node).unwrap_or("").to_owned()),
+                    "rect" => json!({ "x": 0, "y": 0, "width": 100, "height": 20 }),
+                    "screenshot" => Json::String(SCREENSHOT.to_owned()),
+                    _ => return Err(WebdriverError::unknown_command()),
+                };
+                Ok(value)
+            }
+            ("GET", ["element", element, "attribute", name]) => {
+                let node = self.element(element)?;
+                Ok(self
+                    .document()?
+                    .attribute(node, name)
+                    .map_or(Json::Null, Json::String))
+            }
+            ("GET", ["element", element, "property", name]) => {
+                let node = self.element(element)?;
+                Ok(self.document()?.property(node, name))
+            }
+            ("GET", ["element", element, "css", _]) => {
+                self.element(element)?;
+                Ok(Json::String(String::new()))
+            }
+            ("POST", ["element", element, "click"]) => {
+                let node = self.interactable_element(element)?;
+                let navigation = self.document_mut()?.click(node);
+                self.follow(site, navigation)?;
+                Ok(Json::Null)
+            }
+            ("POST", ["element", element, "clear"]) => {
+                let node = self.interactable_element(element)?;
+                self.document_mut()?.clear(node);
+                Ok(Json::Null)
+            }
+            ("POST", ["element", element, "value"]) => {
+                let node = self.interactable_element(element)?;
+                let text = match body.get("text").and_then(Json::as_str) {
+                    Some(text) => text.to_owned(),
+                    None => body
+                        .get("value")
+                        .and_then(Json::as_array)
+                        .ok_or_else(|| WebdriverError::invalid_argument("text is missing"))?
+                        .iter()
+                        .filter_map(Json::as_str)
+                        .collect(),
+                };
+                let navigation = self.document_mut()?.send_keys(node, &text);
+                self.follow(site, navigation)?;
+                Ok(Json::Null)
+            }
+            ("GET", ["screenshot"]) => {
+                self.window()?;
+                Ok(Json::String(SCREENSHOT.to_owned()))
+            }
+            ("POST", ["execute", "sync"]) | ("POST", ["execute", "async"]) => {
+                self.window()?;
+                Ok(site.script_result(string_param(body, "script")?))
+            }
+            ("GET", ["cookie"]) => Ok(json!([])),
+            ("GET", ["cookie", _]) => Err(WebdriverError::new(
+                404,
+                "no such cookie",
+                "there are no cookies",
+            )),
+            ("POST", ["cookie"]) | ("DELETE", ["cookie"]) | ("DELETE", ["cookie", _]) => {
+                Ok(Json::Null)
+            }
+            // Pointer and key actions have no effect on a static page.
+            ("POST", ["actions"]) | ("DELETE", ["actions"]) => Ok(Json::Null),
+            (_, ["alert", ..]) => Err(WebdriverError::new(
+                404,
+                "no such alert",
+                "there's no open dialog",
+            )),
+            _ => Err(WebdriverError::unknown_command()),
+        }
+    }
+
+    fn window(&self) -> Result<&Window, WebdriverError> {
+        self.current
+            .and_then(|current| self.windows.get(current))
+            .ok_or_else(WebdriverError::no_such_window)
+    }
+
+    fn window_mut(&mut self) -> Result<&mut Window, WebdriverError> {
+        let current = self.current;
+        current
+            .and_then(move |current| self.windows.get_mut(current))
+            .ok_or_else(WebdriverError::no_such_window)
+    }
+
+    fn window_handles(&self) -> Json {
+        self.windows
+            .iter()
+            .map(|window| Json::String(window.handle.clone()))
+            .collect()
+    }
+
+    fn window_rect(&self) -> Result<Json, WebdriverError> {
+        let (width, height) = self.window()?.size;
+        Ok(json!({ "x": 0, "y": 0, "width": width, "height": height }))
+    }
+
+    /// Returns an index of a document of the current browsing context.
+    fn context(&self) -> Result<usize, WebdriverError> {
+        Ok(*self.window()?.contexts.last().unwrap())
+    }
+
+    fn document(&self) -> Result<&Document, WebdriverError> {
+        Ok(&self.documents[self.context()?])
+    }
+
+    fn document_mut(&mut self) -> Result<&mut Document, WebdriverError> {
+        let context = self.context()?;
+        Ok(&mut self.documents[context])
+    }
+
+    fn open_window(&mut self, site: &Site, url: Url) {
+        self.last_window += 1;
+        self.documents.push(site.load(url));
+        self.windows.push(Window {
+            handle: format!("window-{}", self.last_window),
+            contexts: vec![self.documents.len() - 1],
+            size: WINDOW_SIZE,
+        });
+    }
+
+    /// Navigates the current browsing context.
+    fn navigate(&mut self, site: &Site, url: Url) -> Result<(), WebdriverError> {
+        let old = self.context()?;
+        self.documents.push(site.load(url));
+        let new = self.documents.len() - 1;
+
+        for frame in self.frames.values_mut() {
+            if *frame == old {
+                *frame = new;
+            }
+        }
+
+        let window = self.window_mut()?;
+        if window.contexts.len() == 1 {
+            window.contexts[0] = new;
+        } else {
+            *window.contexts.last_mut().unwrap() = new;
+        }
+
+        Ok(())
+    }
+
+    fn follow(
+        &mut self,
+        site: &Site,
+        navigation: Option<Navigation>,
+    ) -> Result<(), WebdriverError> {
+        match navigation {
+            Some(Navigation {
+                url,
+                new_window: true,
+            }) => {
+                self.open_window(site, url);
+                Ok(())
+            }
+            Some(Navigation { url, .. }) => self.navigate(site, url),
+            None => Ok(()),
+        }
+    }
+
+    fn switch_to_frame(&mut self, site: &Site, id: &Json) -> Result<(), WebdriverError> {
+        let no_such_frame =
+            || WebdriverError::new(404, "no such frame", format!("no frame {}", id));
+        let frame = match id {
+            Json::Null => {
+                self.window_mut()?.contexts.truncate(1);
+                return Ok(());
+            }
+            Json::Number(index) => {
+                let index = index.as_u64().ok_or_else(no_such_frame)? as usize;
+                *self
+                    .document()?
+                    .frames()
+                    .get(index)
+                    .ok_or_else(no_such_frame)?
+            }
+            Json::Object(reference) => {
+                let element = reference
+                    .get(ELEMENT_KEY)
+                    .and_then(Json::as_str)
+                    .ok_or_else(|| WebdriverError::invalid_argument("wrong element reference"))?;
+                let node = self.element(element)?;
+                if !self.document()?.frames().contains(&node) {
+                    return Err(no_such_frame());
+                }
+                node
+            }
+            _ => return Err(WebdriverError::invalid_argument("wrong frame id")),
+        };
+
+        let parent = self.context()?;
+        let document = match self.frames.get(&(parent, frame)) {
+            Some(&document) => document,
+            None => {
+                let doc = &self.documents[parent];
+                let url = doc
+                    .attr(frame, "src")
+                    .and_then(|src| doc.url.join(src).ok())
+                    .unwrap_or_else(|| Url::parse("about:blank").unwrap());
+                self.documents.push(site.load(url));
+                let document = self.documents.len() - 1;
+                self.frames.insert((parent, frame), document);
+                document
+            }
+        };
+
+        self.window_mut()?.contexts.push(document);
+        Ok(())
+    }
+
+    fn find(&self, body: &Json, scope: NodeId, first: bool) -> Result<Json, WebdriverError> {
+        let using = string_param(body, "using")?;
+        let value = string_param(body, "value")?;
+        let found = selector::find(self.document()?, using, value, scope)?;
+        if first {
+            let id = found.first().ok_or_else(|| {
+                WebdriverError::no_such_element(format!("no element matches {} {:?}", using, value))
+            })?;
+            self.element_reference(*id)
+        } else {
+            found
+                .into_iter()
+                .map(|id| self.element_reference(id))
+                .collect()
+        }
+    }
+
+    fn element_reference(&self, node: NodeId) -> Result<Json, WebdriverError> {
+        Ok(json!({ ELEMENT_KEY: format!("{}-{}", self.context()?, node) }))
+    }
+
+    /// Resolves an element reference in the current browsing context.
+    fn element(&self, reference: &str) -> Result<NodeId, WebdriverError> {
+        let (document, node) = reference
+            .split_once('-')
+            .and_then(|(document, node)| Some((document.parse().ok()?, node.parse().ok()?)))
+            .filter(|&(document, node): &(usize, NodeId)| {
+                self.documents
+                    .get(document)
+                    .is_some_and(|doc| doc.contains(node) && doc.is_element(node))
+            })
+            .ok_or_else(|| {
+                WebdriverError::no_such_element(format!("unknown element {}", reference))
+            })?;
+
+        if document != self.context()? {
+            return Err(WebdriverError::new(
+                404,
+                "stale element reference",
+                format!("element {} isn't attached to the current page", reference),
+            ));
+        }
+
+        Ok(node)
+    }
+
+    fn interactable_element(&self, reference: &str) -> Result<NodeId, WebdriverError> {
+        let node = self.element(reference)?;
+        if self.document()?.is_displayed(node) {
+            Ok(node)
+        } else {
+            Err(WebdriverError::new(
+                400,
+                "element not interactable",
+                format!("element {} isn't displayed", reference),
+            ))
+        }
+    }
+}
+
+fn string_param<'a>(body: &'a Json, name: &str) -> Result<&'a str, WebdriverError> {
+    body.get(name)
+        .and_then(Json::as_str)
+        .ok_or_else(|| WebdriverError::invalid_argument(format!("{} is missing", name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(server: &WebdriverServer, method: &str, path: &str, body: Json) -> (u16, Json) {
+        let mut stream = TcpStream::connect(server.addr).unwrap();
+        let body = body.to_string();
+        write!(
+            stream,
+            "{} {} HTTP/1.1\r\nContent-Length: {}\r\n\r\n{}",
+            method,
+            path,
+            body.len(),
+            body
+        )
+        .unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let status = response[9..12].parse().unwrap();
+        let body = response.split("\r\n\r\n").nth(1).unwrap();
+        let body: Json = serde_json::from_str(body).unwrap();
+        (status, body["value"].clone())
+    }
+
+    #[test]
+    fn session_flow() {
+        let server = WebdriverServer::start().unwrap();
+        server.add_page(
+            "http://localhost/index.html",
+            r#"<title>Index</title><a id="next" href="next.html">Next</a>"#,
+        );
+        server.add_page("http://localhost/next.html", "<title>Next</title>");
+
+        let (status, session) = request(&server, "POST", "/session", json!({}));
+        assert_eq!(status, 200);
+        let session = format!("/session/{}", session["sessionId"].as_str().unwrap());
+
+        let url = json!({ "url": "http://localhost/index.html" });
+        request(&server, "POST", &format!("{}/url", session), url);
+        let (_, title) = request(&server, "GET", &format!("{}/title", session), Json::Null);
+        assert_eq!(title, "Index");
+
+        let find = json!({ "using": "css selector", "value": "#next" });
+        let (_, element) = request(&server, "POST", &format!("{}/element", session), find);
+        let element = element[ELEMENT_KEY].as_str().unwrap().to_owned();
+        let (_, text) = request(
+            &server,
+            "GET",
+            &format!("{}/element/{}/text", session, element),
+            Json::Null,
+        );
+        assert_eq!(text, "Next");
+
+        request(
+            &server,
+            "POST",
+            &format!("{}/element/{}/click", session, element),
+            json!({}),
+        );
+        let (_, url) = request(&server, "GET", &format!("{}/url", session), Json::Null);
+        assert_eq!(url, "http://localhost/next.html");
+
+        let (status, err) = request(
+            &server,
+            "GET",
+            &format!("{}/element/{}/text", session, element),
+            Json::Null,
+        );
+        assert_eq!(status, 404);
+        assert_eq!(err["error"], "stale element reference");
+
+        let find = json!({ "using": "xpath", "value": "//a[" });
+        let (status, err) = request(&server, "POST", &format!("{}/element", session), find);
+        assert_eq!(status, 400);
+        assert_eq!(err["error"], "invalid selector");
+    }
+}
@@ -0,0 +1,557 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Lookup of elements by W3C WebDriver location strategies.
+//!
+//! CSS selectors support type, id, class and attribute selectors, all combinators
+//! and a handful of structural pseudo classes which Selenium IDE tends to record.
+
+use super::{
+    html::{Document, NodeId},
+    xpath,
+};
+
+pub(crate) enum FindError {
+    InvalidArgument(String),
+    InvalidSelector(String),
+}
+
+/// Finds elements in a subtree of a node.
+pub(crate) fn find(
+    doc: &Document,
+    using: &str,
+    value: &str,
+    scope: NodeId,
+) -> Result<Vec<NodeId>, FindError> {
+    let candidates = doc
+        .descendants(scope)
+        .into_iter()
+        .filter(|&id| doc.is_element(id));
+    match using {
+        "css selector" => {
+            let selector = Selector::parse(value).map_err(FindError::InvalidSelector)?;
+            Ok(candidates.filter(|&id| selector.matches(doc, id)).collect())
+        }
+        "tag name" => {
+            let tag = value.to_ascii_lowercase();
+            Ok(candidates.filter(|&id| doc.tag(id) == Some(&tag)).collect())
+        }
+        "link text" => Ok(candidates
+            .filter(|&id| doc.tag(id) == Some("a") && doc.rendered_text(id) == value.trim())
+            .collect()),
+        "partial link text" => Ok(candidates
+            .filter(|&id| doc.tag(id) == Some("a") && doc.rendered_text(id).contains(value))
+            .collect()),
+        "xpath" => xpath::evaluate(doc, value, scope).map_err(FindError::InvalidSelector),
+        _ => Err(FindError::InvalidArgument(format!(
+            "unsupported location strategy {:?}",
+            using
+        ))),
+    }
+}
+
+/// A list of comma separated selectors.
+struct Selector(Vec<Vec<(Combinator, Compound)>>);
+
+#[derive(Clone, Copy)]
+enum Combinator {
+    Descendant,
+    Child,
+    NextSibling,
+    SubsequentSibling,
+}
+
+struct Compound {
+    tag: Option<String>,
+    conditions: Vec<Condition>,
+}
+
+enum Condition {
+    Id(String),
+    Class(String),
+    Attribute(String, Option<(AttributeOperator, String)>),
+    NthChild(i64, i64),
+    NthLastChild(i64, i64),
+    NthOfType(i64, i64),
+    Checked,
+    Disabled,
+    Enabled,
+    Not(Compound),
+}
+
+#[derive(Clone, Copy)]
+enum AttributeOperator {
+    Equals,
+    Includes,
+    DashMatch,
+    Prefix,
+    Suffix,
+    Substring,
+}
+
+impl Selector {
+    fn parse(s: &str) -> Result<Self, String> {
+        let mut parser = Parser {
+            chars: s.chars().collect(),
+            pos: 0,
+        };
+        let mut selectors = Vec::new();
+        loop {
+            parser.skip_whitespace();
+            selectors.push(parser.complex()?);
+            parser.skip_whitespace();
+            if parser.eat(',') {
+                continue;
+            } else if parser.peek().is_none() {
+                break;
+            } else {
+                return Err(parser.unexpected());
+            }
+        }
+
+        Ok(Self(selectors))
+    }
+
+    fn matches(&self, doc: &Document, id: NodeId) -> bool {
+        self.0
+            .iter()
+            .any(|parts| matches_complex(doc, id, parts, parts.len() - 1))
+    }
+}
+
+fn matches_complex(doc: &Document, id: NodeId, parts: &[(Combinator, Compound)], i: usize) -> bool {
+    if !parts[i].1.matches(doc, id) {
+        return false;
+    }
+    if i == 0 {
+        return true;
+    }
+
+    match parts[i].0 {
+        Combinator::Child => {
+            parent_element(doc, id).is_some_and(|parent| matches_complex(doc, parent, parts, i - 1))
+        }
+        Combinator::Descendant => doc
+            .ancestors(id)
+            .skip(1)
+            .filter(|&a| doc.is_element(a))
+            .any(|a| matches_complex(doc, a, parts, i - 1)),
+        Combinator::NextSibling => preceding_siblings(doc, id)
+            .first()
+            .is_some_and(|&sibling| matches_complex(doc, sibling, parts, i - 1)),
+        Combinator::SubsequentSibling => preceding_siblings(doc, id)
+            .into_iter()
+            .any(|sibling| matches_complex(doc, sibling, parts, i - 1)),
+    }
+}
+
+fn parent_element(doc: &Document, id: NodeId) -> Option<NodeId> {
+    doc.parent(id).filter(|&parent| doc.is_element(parent))
+}
+
+/// Returns element siblings of an element and a position of the element among them.
+fn siblings(doc: &Document, id: NodeId) -> (Vec<NodeId>, usize) {
+    let siblings = doc.child_elements(doc.parent(id).unwrap_or(id));
+    let position = siblings.iter().position(|&s| s == id).unwrap_or(0);
+    (siblings, position)
+}
+
+/// Returns element siblings which go before an element starting from the closest one.
+fn preceding_siblings(doc: &Document, id: NodeId) -> Vec<NodeId> {
+    let (siblings, position) = siblings(doc, id);
+    siblings[..position].iter().rev().copied().collect()
+}
+
+/// Checks whether a 1-based position fits `an+b` formula.
+fn nth(a: i64, b: i64, position: i64) -> bool {
+    if a == 0 {
+        position == b
+    } else {
+        (position - b) % a == 0 && (position - b) / a >= 0
+    }
+}
+
+impl Compound {
+    fn matches(&self, doc: &Document, id: NodeId) -> bool {
+        if let Some(tag) = &self.tag {
+            if doc.tag(id) != Some(tag) {
+                return false;
+            }
+        }
+
+        self.conditions.iter().all(|condition| match condition {
+            Condition::Id(value) => doc.attr(id, "id") == Some(value),
+            Condition::Class(class) => doc
+                .attr(id, "class")
+                .is_some_and(|classes| classes.split_whitespace().any(|c| c == class)),
+            Condition::Attribute(name, None) => doc.attr(id, name).is_some(),
+            Condition::Attribute(name, Some((operator, expected))) => {
+                let value = match doc.attr(id, name) {
+                    Some(value) => value,
+                    None => return false,
+                };
+                match operator {
+                    AttributeOperator::Equals => value == expected,
+                    AttributeOperator::Includes => value.split_whitespace().any(|v| v == expected),
+                    AttributeOperator::DashMatch => {
+                        value == expected || value.starts_with(&format!("{}-", expected))
+                    }
+                    AttributeOperator::Prefix => {
+                        !expected.is_empty() && value.starts_with(expected.as_str())
+                    }
+                    AttributeOperator::Suffix => {
+                        !expected.is_empty() && value.ends_with(expected.as_str())
+                    }
+                    AttributeOperator::Substring => {
+                        !expected.is_empty() && value.contains(expected.as_str())
+                    }
+                }
+            }
+            Condition::NthChild(a, b) => {
+                let (_, position) = siblings(doc, id);
+                nth(*a, *b, position as i64 + 1)
+            }
+            Condition::NthLastChild(a, b) => {
+                let (siblings, position) = siblings(doc, id);
+                nth(*a, *b, (siblings.len() - position) as i64)
+            }
+            Condition::NthOfType(a, b) => {
+                let tag = doc.tag(id);
+                let position = preceding_siblings(doc, id)
+                    .into_iter()
+                    .filter(|&sibling| doc.tag(sibling) == tag)
+                    .count();
+                nth(*a, *b, position as i64 + 1)
+            }
+            Condition::Checked => doc.is_selected(id),
+            Condition::Disabled => !doc.is_enabled(id),
+            Condition::Enabled => doc.is_enabled(id),
+            Condition::Not(compound) => !compound.matches(doc, id),
+        })
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), String> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    fn unexpected(&self) -> String {
+        match self.peek() {
+            Some(c) => format!("unexpected {:?} at {}", c, self.pos),
+            None => "unexpected end of selector".to_owned(),
+        }
+    }
+
+    fn skip_whitespace(&mut self) -> bool {
+        let start = self.pos;
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+
+        self.pos != start
+    }
+
+    fn complex(&mut self) -> Result<Vec<(Combinator, Compound)>, String> {
+        let mut parts = vec![(Combinator::Descendant, self.compound()?)];
+        loop {
+            let whitespace = self.skip_whitespace();
+            let combinator = match self.peek() {
+                Some('>') => Combinator::Child,
+                Some('+') => Combinator::NextSibling,
+                Some('~') => Combinator::SubsequentSibling,
+                Some(',') | None => break,
+                Some(_) if whitespace => {
+                    parts.push((Combinator::Descendant, self.compound()?));
+                    continue;
+                }
+                Some(_) => return Err(self.unexpected()),
+            };
+            self.pos += 1;
+            self.skip_whitespace();
+            parts.push((combinator, self.compound()?));
+        }
+
+        Ok(parts)
+    }
+
+    fn compound(&mut self) -> Result<Compound, String> {
+        let start = self.pos;
+        let mut compound = Compound {
+            tag: None,
+            conditions: Vec::new(),
+        };
+
+        if self.eat('*') {
+        } else if self.peek().is_some_and(is_ident_char) {
+            compound.tag = Some(self.ident()?.to_ascii_lowercase());
+        }
+
+        loop {
+            let condition = match self.peek() {
+                Some('#') => {
+                    self.pos += 1;
+                    Condition::Id(self.ident()?)
+                }
+                Some('.') => {
+                    self.pos += 1;
+                    Condition::Class(self.ident()?)
+                }
+                Some('[') => self.attribute()?,
+                Some(':') => self.pseudo_class()?,
+                _ => break,
+            };
+            compound.conditions.push(condition);
+        }
+
+        if self.pos == start {
+            return Err(self.unexpected());
+        }
+
+        Ok(compound)
+    }
+
+    fn ident(&mut self) -> Result<String, String> {
+        let mut ident = String::new();
+        while let Some(c) = self.peek() {
+            if c == '\\' {
+                self.pos += 1;
+                ident.extend(self.peek());
+            } else if is_ident_char(c) {
+                ident.push(c);
+            } else {
+                break;
+            }
+            self.pos += 1;
+        }
+
+        if ident.is_empty() {
+            Err(self.unexpected())
+        } else {
+            Ok(ident)
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        let quote = match self.peek() {
+            Some(quote @ '"') | Some(quote @ '\'') => quote,
+            _ => return self.ident(),
+        };
+        self.pos += 1;
+
+        let mut s = String::new();
+        loop {
+            match self.peek() {
+                Some('\\') => {
+                    self.pos += 1;
+                    s.extend(self.peek());
+                }
+                Some(c) if c == quote => break,
+                Some(c) => s.push(c),
+                None => return Err(self.unexpected()),
+            }
+            self.pos += 1;
+        }
+        self.pos += 1;
+
+        Ok(s)
+    }
+
+    fn attribute(&mut self) -> Result<Condition, String> {
+        self.expect('[')?;
+        self.skip_whitespace();
+        let name = self.ident()?.to_ascii_lowercase();
+        self.skip_whitespace();
+
+        let operator = match self.peek() {
+            Some(']') => {
+                self.pos += 1;
+                return Ok(Condition::Attribute(name, None));
+            }
+            Some('=') => AttributeOperator::Equals,
+            Some('~') => AttributeOperator::Includes,
+            Some('|') => AttributeOperator::DashMatch,
+            Some('^') => AttributeOperator::Prefix,
+            Some('$') => AttributeOperator::Suffix,
+            Some('*') => AttributeOperator::Substring,
+            _ => return Err(self.unexpected()),
+        };
+        self.pos += 1;
+        if !matches!(operator, AttributeOperator::Equals) {
+            self.expect('=')?;
+        }
+
+        self.skip_whitespace();
+        let value = self.string()?;
+        self.skip_whitespace();
+        self.expect(']')?;
+
+        Ok(Condition::Attribute(name, Some((operator, value))))
+    }
+
+    fn pseudo_class(&mut self) -> Result<Condition, String> {
+        self.expect(':')?;
+        let name = self.ident()?.to_ascii_lowercase();
+        let condition = match name.as_str() {
+            "first-child" => Condition::NthChild(0, 1),
+            "last-child" => Condition::NthLastChild(0, 1),
+            "first-of-type" => Condition::NthOfType(0, 1),
+            "checked" | "selected" => Condition::Checked,
+            "disabled" => Condition::Disabled,
+            "enabled" => Condition::Enabled,
+            "nth-child" | "nth-last-child" | "nth-of-type" => {
+                let (a, b) = self.nth_argument()?;
+                match name.as_str() {
+                    "nth-child" => Condition::NthChild(a, b),
+                    "nth-last-child" => Condition::NthLastChild(a, b),
+                    _ => Condition::NthOfType(a, b),
+                }
+            }
+            "not" => {
+                self.expect('(')?;
+                self.skip_whitespace();
+                let compound = self.compound()?;
+                self.skip_whitespace();
+                self.expect(')')?;
+                Condition::Not(compound)
+            }
+            _ => return Err(format!("unsupported pseudo class {:?}", name)),
+        };
+
+        Ok(condition)
+    }
+
+    fn nth_argument(&mut self) -> Result<(i64, i64), String> {
+        self.expect('(')?;
+        let mut argument = String::new();
+        while let Some(c) = self.peek().filter(|&c| c != ')') {
+            if !c.is_whitespace() {
+                argument.push(c.to_ascii_lowercase());
+            }
+            self.pos += 1;
+        }
+        self.expect(')')?;
+
+        let parse = |s: &str| match s {
+            "" | "+" => Some(1),
+            "-" => Some(-1),
+            s => s.trim_start_matches('+').parse().ok(),
+        };
+        let nth = match argument.as_str() {
+            "odd" => Some((2, 1)),
+            "even" => Some((2, 0)),
+            s => match s.find('n') {
+                Some(n) => parse(&s[..n]).and_then(|a| {
+                    let b = &s[n + 1..];
+                    let b = if b.is_empty() {
+                        Some(0)
+                    } else {
+                        b.trim_start_matches('+').parse().ok()
+                    };
+                    b.map(|b| (a, b))
+                }),
+                None => s.parse().ok().map(|b| (0, b)),
+            },
+        };
+
+        nth.ok_or_else(|| format!("wrong argument {:?}", argument))
+    }
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '-' || c == '_' || !c.is_ascii()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use url::Url;
+
+    const HTML: &str = r#"
+        <div id="app" class="main wide">
+            <ul>
+                <li class="item">One</li>
+                <li class="item active" data-id="2">Two</li>
+                <li class="item">Three</li>
+            </ul>
+            <a href="/next" id="link">Next page</a>
+            <input type="checkbox" name="check" checked>
+        </div>
+    "#;
+
+    fn ids(doc: &Document, using: &str, value: &str) -> Vec<String> {
+        let found = match find(doc, using, value, super::super::html::ROOT) {
+            Ok(found) => found,
+            Err(FindError::InvalidSelector(err)) | Err(FindError::InvalidArgument(err)) => {
+                panic!("{}", err)
+            }
+        };
+        found
+            .into_iter()
+            .map(|id| {
+                doc.attr(id, "id")
+                    .or_else(|| doc.attr(id, "data-id"))
+                    .map(|id| id.to_owned())
+                    .unwrap_or_else(|| doc.rendered_text(id))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn css() {
+        let doc = Document::parse(Url::parse("http://localhost").unwrap(), HTML);
+
+        assert_eq!(
+            ids(&doc, "css selector", "#app > ul li:nth-child(2)"),
+            ["2"]
+        );
+        assert_eq!(ids(&doc, "css selector", "[id=\"link\"]"), ["link"]);
+        assert_eq!(ids(&doc, "css selector", "div.main.wide"), ["app"]);
+        assert_eq!(ids(&doc, "css selector", "li.active + li"), ["Three"]);
+        assert_eq!(
+            ids(
+                &doc,
+                "css selector",
+                "li:not(.active):last-child, a[href^='/n']"
+            ),
+            ["Three", "link"]
+        );
+        assert_eq!(ids(&doc, "css selector", "input:checked").len(), 1);
+        assert!(ids(&doc, "css selector", "span").is_empty());
+        assert!(Selector::parse("div >").is_err());
+        assert!(Selector::parse("a[href").is_err());
+    }
+
+    #[test]
+    fn link_text() {
+        let doc = Document::parse(Url::parse("http://localhost").unwrap(), HTML);
+
+        assert_eq!(ids(&doc, "link text", "Next page"), ["link"]);
+        assert_eq!(ids(&doc, "partial link text", "Next"), ["link"]);
+        assert!(ids(&doc, "link text", "Next").is_empty());
+        assert_eq!(ids(&doc, "tag name", "LI").len(), 3);
+    }
+}
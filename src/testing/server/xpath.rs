@@ -0,0 +1,904 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! A subset of XPath 1.0.
+//!
+//! Location paths with all common axes, predicates, comparisons, unions
+//! and string functions are supported. Arithmetic isn't.
+
+use super::html::{collapse_whitespace, Document, NodeId, ROOT};
+
+/// Evaluates an expression and returns elements it selects.
+pub(crate) fn evaluate(doc: &Document, expr: &str, scope: NodeId) -> Result<Vec<NodeId>, String> {
+    let tokens = tokenize(expr)?;
+    let mut parser = Parser { tokens, pos: 0 };
+    let expr = parser.expr()?;
+    if parser.pos != parser.tokens.len() {
+        return Err(format!("unexpected token {:?}", parser.tokens[parser.pos]));
+    }
+
+    let ctx = Context {
+        item: Item::Node(scope),
+        position: 1,
+        size: 1,
+    };
+    let value = Evaluator { doc }.eval(&expr, &ctx)?;
+    match value {
+        Value::Nodes(items) => items
+            .into_iter()
+            .map(|item| match item {
+                Item::Node(id) if doc.is_element(id) => Ok(id),
+                _ => Err("the expression selects not only elements".to_owned()),
+            })
+            .collect(),
+        _ => Err("the expression doesn't select elements".to_owned()),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Slash,
+    DoubleSlash,
+    LeftBracket,
+    RightBracket,
+    LeftParen,
+    RightParen,
+    At,
+    Comma,
+    Pipe,
+    Dot,
+    DoubleDot,
+    Star,
+    DoubleColon,
+    Operator(&'static str),
+    Literal(String),
+    Number(f64),
+    Name(String),
+}
+
+fn tokenize(s: &str) -> Result<Vec<Token>, String> {
+    let chars = s.chars().collect::<Vec<_>>();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        let (token, len) = match c {
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '/' if next == Some('/') => (Token::DoubleSlash, 2),
+            '/' => (Token::Slash, 1),
+            '[' => (Token::LeftBracket, 1),
+            ']' => (Token::RightBracket, 1),
+            '(' => (Token::LeftParen, 1),
+            ')' => (Token::RightParen, 1),
+            '@' => (Token::At, 1),
+            ',' => (Token::Comma, 1),
+            '|' => (Token::Pipe, 1),
+            '*' => (Token::Star, 1),
+            ':' if next == Some(':') => (Token::DoubleColon, 2),
+            '=' => (Token::Operator("="), 1),
+            '!' if next == Some('=') => (Token::Operator("!="), 2),
+            '<' if next == Some('=') => (Token::Operator("<="), 2),
+            '>' if next == Some('=') => (Token::Operator(">="), 2),
+            '<' => (Token::Operator("<"), 1),
+            '>' => (Token::Operator(">"), 1),
+            '.' if next == Some('.') => (Token::DoubleDot, 2),
+            '.' if !next.is_some_and(|c| c.is_ascii_digit()) => (Token::Dot, 1),
+            '"' | '\'' => {
+                let end = chars[i + 1..]
+                    .iter()
+                    .position(|&q| q == c)
+                    .ok_or_else(|| "unterminated string literal".to_owned())?;
+                let literal = chars[i + 1..i + 1 + end].iter().collect();
+                (Token::Literal(literal), end + 2)
+            }
+            c if c.is_ascii_digit() || c == '.' => {
+                let len = chars[i..]
+                    .iter()
+                    .take_while(|c| c.is_ascii_digit() || **c == '.')
+                    .count();
+                let number = chars[i..i + len].iter().collect::<String>();
+                let number = number
+                    .parse()
+                    .map_err(|_| format!("wrong number {:?}", number))?;
+                (Token::Number(number), len)
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let len = chars[i..]
+                    .iter()
+                    .take_while(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.'))
+                    .count();
+                (Token::Name(chars[i..i + len].iter().collect()), len)
+            }
+            c => return Err(format!("unexpected {:?} at {}", c, i)),
+        };
+
+        tokens.push(token);
+        i += len;
+    }
+
+    Ok(tokens)
+}
+
+enum Expr {
+    Or(Box<Expr>, Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Compare(Box<Expr>, &'static str, Box<Expr>),
+    Union(Box<Expr>, Box<Expr>),
+    /// A location path which starts from a root, a context node or from a result of an expression.
+    Path(PathStart, Vec<Step>),
+    Filter(Box<Expr>, Vec<Expr>),
+    Literal(String),
+    Number(f64),
+    Call(String, Vec<Expr>),
+}
+
+enum PathStart {
+    Root,
+    Context,
+    Expr(Box<Expr>),
+}
+
+struct Step {
+    axis: Axis,
+    test: NodeTest,
+    predicates: Vec<Expr>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Axis {
+    Ancestor,
+    AncestorOrSelf,
+    Attribute,
+    Child,
+    Descendant,
+    DescendantOrSelf,
+    Following,
+    FollowingSibling,
+    Parent,
+    Preceding,
+    PrecedingSibling,
+    Self_,
+}
+
+enum NodeTest {
+    Name(String),
+    Any,
+    Text,
+    Node,
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&Token> {
+        self.tokens.get(self.pos + offset)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &Token) -> Result<(), String> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(format!("expected {:?} but got {:?}", token, self.peek()))
+        }
+    }
+
+    fn eat_name(&mut self, name: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Name(n)) if n == name) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expr(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.and_expr()?;
+        while self.eat_name("or") {
+            lhs = Expr::Or(Box::new(lhs), Box::new(self.and_expr()?));
+        }
+
+        Ok(lhs)
+    }
+
+    fn and_expr(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.compare_expr()?;
+        while self.eat_name("and") {
+            lhs = Expr::And(Box::new(lhs), Box::new(self.compare_expr()?));
+        }
+
+        Ok(lhs)
+    }
+
+    fn compare_expr(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.union_expr()?;
+        while let Some(Token::Operator(op)) = self.peek() {
+            let op = *op;
+            self.pos += 1;
+            lhs = Expr::Compare(Box::new(lhs), op, Box::new(self.union_expr()?));
+        }
+
+        Ok(lhs)
+    }
+
+    fn union_expr(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.path_expr()?;
+        while self.eat(&Token::Pipe) {
+            lhs = Expr::Union(Box::new(lhs), Box::new(self.path_expr()?));
+        }
+
+        Ok(lhs)
+    }
+
+    fn path_expr(&mut self) -> Result<Expr, String> {
+        match self.peek() {
+            Some(Token::Slash) => {
+                self.pos += 1;
+                let steps = if self.is_step_start() {
+                    self.relative_path()?
+                } else {
+                    Vec::new()
+                };
+                Ok(Expr::Path(PathStart::Root, steps))
+            }
+            Some(Token::DoubleSlash) => {
+                self.pos += 1;
+                let mut steps = vec![descendant_or_self()];
+                steps.extend(self.relative_path()?);
+                Ok(Expr::Path(PathStart::Root, steps))
+            }
+            Some(Token::Literal(_)) | Some(Token::Number(_)) | Some(Token::LeftParen) => {
+                self.filter_expr()
+            }
+            Some(Token::Name(name))
+                if self.peek_at(1) == Some(&Token::LeftParen)
+                    && !matches!(name.as_str(), "text" | "node" | "comment") =>
+            {
+                self.filter_expr()
+            }
+            _ => Ok(Expr::Path(PathStart::Context, self.relative_path()?)),
+        }
+    }
+
+    fn filter_expr(&mut self) -> Result<Expr, String> {
+        let mut expr = self.primary()?;
+        let predicates = self.predicates()?;
+        if !predicates.is_empty() {
+            expr = Expr::Filter(Box::new(expr), predicates);
+        }
+
+        let mut steps = Vec::new();
+        loop {
+            if self.eat(&Token::Slash) {
+                steps.push(self.step()?);
+            } else if self.eat(&Token::DoubleSlash) {
+                steps.push(descendant_or_self());
+                steps.push(self.step()?);
+            } else {
+                break;
+            }
+        }
+
+        if steps.is_empty() {
+            Ok(expr)
+        } else {
+            Ok(Expr::Path(PathStart::Expr(Box::new(expr)), steps))
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expr, String> {
+        match self.next() {
+            Some(Token::Literal(s)) => Ok(Expr::Literal(s)),
+            Some(Token::Number(n)) => Ok(Expr::Number(n)),
+            Some(Token::LeftParen) => {
+                let expr = self.expr()?;
+                self.expect(&Token::RightParen)?;
+                Ok(expr)
+            }
+            Some(Token::Name(name)) => {
+                self.expect(&Token::LeftParen)?;
+                let mut args = Vec::new();
+                if !self.eat(&Token::RightParen) {
+                    loop {
+                        args.push(self.expr()?);
+                        if self.eat(&Token::RightParen) {
+                            break;
+                        }
+                        self.expect(&Token::Comma)?;
+                    }
+                }
+                Ok(Expr::Call(name, args))
+            }
+            token => Err(format!("unexpected token {:?}", token)),
+        }
+    }
+
+    fn is_step_start(&self) -> bool {
+        matches!(
+            self.peek(),
+            Some(Token::Dot)
+                | Some(Token::DoubleDot)
+                | Some(Token::At)
+                | Some(Token::Star)
+                | Some(Token::Name(_))
+        )
+    }
+
+    fn relative_path(&mut self) -> Result<Vec<Step>, String> {
+        let mut steps = vec![self.step()?];
+        loop {
+            if self.eat(&Token::Slash) {
+                steps.push(self.step()?);
+            } else if self.eat(&Token::DoubleSlash) {
+                steps.push(descendant_or_self());
+                steps.push(self.step()?);
+            } else {
+                break;
+            }
+        }
+
+        Ok(steps)
+    }
+
+    fn step(&mut self) -> Result<Step, String> {
+        if self.eat(&Token::Dot) {
+            return Ok(Step {
+                axis: Axis::Self_,
+                test: NodeTest::Node,
+                predicates: Vec::new(),
+            });
+        }
+        if self.eat(&Token::DoubleDot) {
+            return Ok(Step {
+                axis: Axis::Parent,
+                test: NodeTest::Node,
+                predicates: Vec::new(),
+            });
+        }
+
+        let axis = if self.eat(&Token::At) {
+            Axis::Attribute
+        } else if self.peek_at(1) == Some(&Token::DoubleColon) {
+            let axis = match self.next() {
+                Some(Token::Name(name)) => match name.as_str() {
+                    "ancestor" => Axis::Ancestor,
+                    "ancestor-or-self" => Axis::AncestorOrSelf,
+                    "attribute" => Axis::Attribute,
+                    "child" => Axis::Child,
+                    "descendant" => Axis::Descendant,
+                    "descendant-or-self" => Axis::DescendantOrSelf,
+                    "following" => Axis::Following,
+                    "following-sibling" => Axis::FollowingSibling,
+                    "parent" => Axis::Parent,
+                    "preceding" => Axis::Preceding,
+                    "preceding-sibling" => Axis::PrecedingSibling,
+                    "self" => Axis::Self_,
+                    name => return Err(format!("unsupported axis {:?}", name)),
+                },
+                token => return Err(format!("unexpected token {:?}", token)),
+            };
+            self.pos += 1;
+            axis
+        } else {
+            Axis::Child
+        };
+
+        let test = match self.next() {
+            Some(Token::Star) => NodeTest::Any,
+            Some(Token::Name(name)) if self.peek() == Some(&Token::LeftParen) => {
+                self.pos += 1;
+                self.expect(&Token::RightParen)?;
+                match name.as_str() {
+                    "text" => NodeTest::Text,
+                    "node" => NodeTest::Node,
+                    name => return Err(format!("unsupported node test {:?}", name)),
+                }
+            }
+            Some(Token::Name(name)) => NodeTest::Name(name.to_ascii_lowercase()),
+            token => return Err(format!("unexpected token {:?}", token)),
+        };
+
+        Ok(Step {
+            axis,
+            test,
+            predicates: self.predicates()?,
+        })
+    }
+
+    fn predicates(&mut self) -> Result<Vec<Expr>, String> {
+        let mut predicates = Vec::new();
+        while self.eat(&Token::LeftBracket) {
+            predicates.push(self.expr()?);
+            self.expect(&Token::RightBracket)?;
+        }
+
+        Ok(predicates)
+    }
+}
+
+fn descendant_or_self() -> Step {
+    Step {
+        axis: Axis::DescendantOrSelf,
+        test: NodeTest::Node,
+        predicates: Vec::new(),
+    }
+}
+
+/// A node or an attribute of an element by its index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Item {
+    Node(NodeId),
+    Attribute(NodeId, usize),
+}
+
+impl Item {
+    fn order(&self) -> (NodeId, usize) {
+        match *self {
+            Item::Node(id) => (id, 0),
+            Item::Attribute(id, index) => (id, index + 1),
+        }
+    }
+}
+
+enum Value {
+    Nodes(Vec<Item>),
+    String(String),
+    Number(f64),
+    Bool(bool),
+}
+
+struct Context {
+    item: Item,
+    position: usize,
+    size: usize,
+}
+
+struct Evaluator<'a> {
+    doc: &'a Document,
+}
+
+impl<'a> Evaluator<'a> {
+    fn eval(&self, expr: &Expr, ctx: &Context) -> Result<Value, String> {
+        let value = match expr {
+            Expr::Or(lhs, rhs) => Value::Bool(
+                self.boolean(self.eval(lhs, ctx)?) || self.boolean(self.eval(rhs, ctx)?),
+            ),
+            Expr::And(lhs, rhs) => Value::Bool(
+                self.boolean(self.eval(lhs, ctx)?) && self.boolean(self.eval(rhs, ctx)?),
+            ),
+            Expr::Compare(lhs, op, rhs) => {
+                Value::Bool(self.compare(self.eval(lhs, ctx)?, op, self.eval(rhs, ctx)?))
+            }
+            Expr::Union(lhs, rhs) => {
+                let mut items = self.nodes(self.eval(lhs, ctx)?)?;
+                items.extend(self.nodes(self.eval(rhs, ctx)?)?);
+                Value::Nodes(sort(items))
+            }
+            Expr::Path(start, steps) => {
+                let mut items = match start {
+                    PathStart::Root => vec![Item::Node(ROOT)],
+                    PathStart::Context => vec![ctx.item],
+                    PathStart::Expr(expr) => self.nodes(self.eval(expr, ctx)?)?,
+                };
+                for step in steps {
+                    items = self.step(&items, step)?;
+                }
+                Value::Nodes(items)
+            }
+            Expr::Filter(expr, predicates) => {
+                let items = self.nodes(self.eval(expr, ctx)?)?;
+                Value::Nodes(self.filter(items, predicates)?)
+            }
+            Expr::Literal(s) => Value::String(s.clone()),
+            Expr::Number(n) => Value::Number(*n),
+            Expr::Call(name, args) => self.call(name, args, ctx)?,
+        };
+
+        Ok(value)
+    }
+
+    fn call(&self, name: &str, args: &[Expr], ctx: &Context) -> Result<Value, String> {
+        let args = args
+            .iter()
+            .map(|arg| self.eval(arg, ctx))
+            .collect::<Result<Vec<_>, _>>()?;
+        let string_arg = |i: usize| match args.get(i) {
+            Some(value) => self.string(value),
+            None => self.string_value(ctx.item),
+        };
+        let arity = |n: usize| {
+            if args.len() == n {
+                Ok(())
+            } else {
+                Err(format!("function {} expects {} arguments", name, n))
+            }
+        };
+
+        let value = match name {
+            "last" => Value::Number(ctx.size as f64),
+            "position" => Value::Number(ctx.position as f64),
+            "count" => {
+                arity(1)?;
+                let count = match &args[0] {
+                    Value::Nodes(items) => items.len(),
+                    _ => return Err("count() expects a node set".to_owned()),
+                };
+                Value::Number(count as f64)
+            }
+            "string" => Value::String(string_arg(0)),
+            "normalize-space" => Value::String(collapse_whitespace(&string_arg(0))),
+            "string-length" => Value::Number(string_arg(0).chars().count() as f64),
+            "concat" => Value::String(args.iter().map(|arg| self.string(arg)).collect()),
+            "contains" => {
+                arity(2)?;
+                Value::Bool(string_arg(0).contains(&string_arg(1)))
+            }
+            "starts-with" => {
+                arity(2)?;
+                Value::Bool(string_arg(0).starts_with(&string_arg(1)))
+            }
+            "ends-with" => {
+                arity(2)?;
+                Value::Bool(string_arg(0).ends_with(&string_arg(1)))
+            }
+            "translate" => {
+                arity(3)?;
+                let from = string_arg(1).chars().collect::<Vec<_>>();
+                let to = string_arg(2).chars().collect::<Vec<_>>();
+                let translated = string_arg(0)
+                    .chars()
+                    .filter_map(|c| match from.iter().position(|&f| f == c) {
+                        Some(i) => to.get(i).copied(),
+                        None => Some(c),
+                    })
+                    .collect();
+                Value::String(translated)
+            }
+            "not" => {
+                arity(1)?;
+                Value::Bool(!self.boolean(args.into_iter().next().unwrap()))
+            }
+            "boolean" => {
+                arity(1)?;
+                Value::Bool(self.boolean(args.into_iter().next().unwrap()))
+            }
+            "true" => Value::Bool(true),
+            "false" => Value::Bool(false),
+            "number" => Value::Number(parse_number(&string_arg(0))),
+            "name" | "local-name" => {
+                let item = match args.first() {
+                    Some(Value::Nodes(items)) => items.first().copied(),
+                    Some(_) => return Err(format!("{}() expects a node set", name)),
+                    None => Some(ctx.item),
+                };
+                let name = match item {
+                    Some(Item::Node(id)) => self.doc.tag(id).unwrap_or("").to_owned(),
+                    Some(Item::Attribute(id, i)) => self.doc.attrs(id)[i].0.clone(),
+                    None => String::new(),
+                };
+                Value::String(name)
+            }
+            name => return Err(format!("unsupported function {}()", name)),
+        };
+
+        Ok(value)
+    }
+
+    fn step(&self, items: &[Item], step: &Step) -> Result<Vec<Item>, String> {
+        let mut result = Vec::new();
+        for &item in items {
+            let candidates = self
+                .axis(item, step.axis)
+                .into_iter()
+                .filter(|&candidate| self.test(candidate, &step.test, step.axis))
+                .collect();
+            result.extend(self.filter(candidates, &step.predicates)?);
+        }
+
+        Ok(sort(result))
+    }
+
+    fn filter(&self, mut items: Vec<Item>, predicates: &[Expr]) -> Result<Vec<Item>, String> {
+        for predicate in predicates {
+            let size = items.len();
+            let mut filtered = Vec::new();
+            for (i, &item) in items.iter().enumerate() {
+                let ctx = Context {
+                    item,
+                    position: i + 1,
+                    size,
+                };
+                let keep = match self.eval(predicate, &ctx)? {
+                    Value::Number(n) => n == (i + 1) as f64,
+                    value => self.boolean(value),
+                };
+                if keep {
+                    filtered.push(item);
+                }
+            }
+            items = filtered;
+        }
+
+        Ok(items)
+    }
+
+    /// Returns nodes of an axis in the axis order.
+    fn axis(&self, item: Item, axis: Axis) -> Vec<Item> {
+        let doc = self.doc;
+        let id = match item {
+            Item::Node(id) => id,
+            Item::Attribute(id, _) => {
+                return match axis {
+                    Axis::Parent => vec![Item::Node(id)],
+                    Axis::Self_ => vec![item],
+                    Axis::Ancestor | Axis::AncestorOrSelf => {
+                        let mut items = if axis == Axis::AncestorOrSelf {
+                            vec![item]
+                        } else {
+                            Vec::new()
+                        };
+                        items.extend(doc.ancestors(id).map(Item::Node));
+                        items
+                    }
+                    _ => Vec::new(),
+                };
+            }
+        };
+
+        let nodes = |ids: Vec<NodeId>| ids.into_iter().map(Item::Node).collect::<Vec<_>>();
+        let siblings = || {
+            let siblings = doc
+                .parent(id)
+                .map_or(&[][..], |parent| doc.children(parent));
+            let position = siblings.iter().position(|&s| s == id).unwrap_or(0);
+            (siblings, position)
+        };
+
+        match axis {
+            Axis::Self_ => vec![item],
+            Axis::Child => nodes(doc.children(id).to_vec()),
+            Axis::Descendant => nodes(doc.descendants(id)),
+            Axis::DescendantOrSelf => {
+                let mut items = vec![item];
+                items.extend(nodes(doc.descendants(id)));
+                items
+            }
+            Axis::Parent => nodes(doc.parent(id).into_iter().collect()),
+            Axis::Ancestor => nodes(doc.ancestors(id).skip(1).collect()),
+            Axis::AncestorOrSelf => nodes(doc.ancestors(id).collect()),
+            Axis::FollowingSibling => {
+                let (siblings, position) = siblings();
+                nodes(siblings.get(position + 1..).unwrap_or(&[]).to_vec())
+            }
+            Axis::PrecedingSibling => {
+                let (siblings, position) = siblings();
+                nodes(siblings[..position].iter().rev().copied().collect())
+            }
+            Axis::Following => {
+                let descendants = doc.descendants(id);
+                let last = descendants.last().copied().unwrap_or(id);
+                nodes(
+                    doc.descendants(ROOT)
+                        .into_iter()
+                        .filter(|&n| n > last)
+                        .collect(),
+                )
+            }
+            Axis::Preceding => {
+                let ancestors = doc.ancestors(id).collect::<Vec<_>>();
+                nodes(
+                    doc.descendants(ROOT)
+                        .into_iter()
+                        .filter(|&n| n < id && !ancestors.contains(&n))
+                        .rev()
+                        .collect(),
+                )
+            }
+            Axis::Attribute => (0..doc.attrs(id).len())
+                .map(|i| Item::Attribute(id, i))
+                .collect(),
+        }
+    }
+
+    fn test(&self, item: Item, test: &NodeTest, axis: Axis) -> bool {
+        match (item, test) {
+            (Item::Attribute(id, i), NodeTest::Name(name)) => &self.doc.attrs(id)[i].0 == name,
+            (Item::Attribute(..), NodeTest::Any) => true,
+            (Item::Attribute(..), NodeTest::Node) => true,
+            (Item::Attribute(..), NodeTest::Text) => false,
+            (Item::Node(_), _) if axis == Axis::Attribute => false,
+            (Item::Node(id), NodeTest::Name(name)) => self.doc.tag(id) == Some(name),
+            (Item::Node(id), NodeTest::Any) => self.doc.is_element(id),
+            (Item::Node(id), NodeTest::Text) => self.doc.is_text(id),
+            (Item::Node(_), NodeTest::Node) => true,
+        }
+    }
+
+    fn nodes(&self, value: Value) -> Result<Vec<Item>, String> {
+        match value {
+            Value::Nodes(items) => Ok(items),
+            _ => Err("expected a node set".to_owned()),
+        }
+    }
+
+    fn string_value(&self, item: Item) -> String {
+        match item {
+            Item::Node(id) => self.doc.text_content(id),
+            Item::Attribute(id, i) => self.doc.attrs(id)[i].1.clone(),
+        }
+    }
+
+    fn string(&self, value: &Value) -> String {
+        match value {
+            Value::Nodes(items) => items
+                .first()
+                .map(|&item| self.string_value(item))
+                .unwrap_or_default(),
+            Value::String(s) => s.clone(),
+            Value::Number(n) if n.fract() == 0.0 => format!("{}", *n as i64),
+            Value::Number(n) => n.to_string(),
+            Value::Bool(b) => b.to_string(),
+        }
+    }
+
+    fn number(&self, value: &Value) -> f64 {
+        match value {
+            Value::Number(n) => *n,
+            Value::Bool(b) => *b as u8 as f64,
+            value => parse_number(&self.string(value)),
+        }
+    }
+
+    fn boolean(&self, value: Value) -> bool {
+        match value {
+            Value::Nodes(items) => !items.is_empty(),
+            Value::String(s) => !s.is_empty(),
+            Value::Number(n) => n != 0.0 && !n.is_nan(),
+            Value::Bool(b) => b,
+        }
+    }
+
+    fn compare(&self, lhs: Value, op: &str, rhs: Value) -> bool {
+        match (lhs, rhs) {
+            (Value::Nodes(lhs), Value::Nodes(rhs)) => lhs.iter().any(|&l| {
+                let l = Value::String(self.string_value(l));
+                rhs.iter()
+                    .any(|&r| self.compare_atomic(&l, op, &Value::String(self.string_value(r))))
+            }),
+            (Value::Nodes(items), Value::Bool(b)) => {
+                self.compare_atomic(&Value::Bool(!items.is_empty()), op, &Value::Bool(b))
+            }
+            (Value::Bool(b), Value::Nodes(items)) => {
+                self.compare_atomic(&Value::Bool(b), op, &Value::Bool(!items.is_empty()))
+            }
+            (Value::Nodes(items), value) => items.iter().any(|&item| {
+                self.compare_atomic(&Value::String(self.string_value(item)), op, &value)
+            }),
+            (value, Value::Nodes(items)) => items.iter().any(|&item| {
+                self.compare_atomic(&value, op, &Value::String(self.string_value(item)))
+            }),
+            (lhs, rhs) => self.compare_atomic(&lhs, op, &rhs),
+        }
+    }
+
+    fn compare_atomic(&self, lhs: &Value, op: &str, rhs: &Value) -> bool {
+        match op {
+            "=" | "!=" => {
+                let equal = match (lhs, rhs) {
+                    (Value::Bool(_), _) | (_, Value::Bool(_)) => {
+                        self.boolean(self.clone_value(lhs)) == self.boolean(self.clone_value(rhs))
+                    }
+                    (Value::Number(_), _) | (_, Value::Number(_)) => {
+                        self.number(lhs) == self.number(rhs)
+                    }
+                    _ => self.string(lhs) == self.string(rhs),
+                };
+                equal == (op == "=")
+            }
+            "<" => self.number(lhs) < self.number(rhs),
+            "<=" => self.number(lhs) <= self.number(rhs),
+            ">" => self.number(lhs) > self.number(rhs),
+            ">=" => self.number(lhs) >= self.number(rhs),
+            _ => false,
+        }
+    }
+
+    fn clone_value(&self, value: &Value) -> Value {
+        match value {
+            Value::Nodes(items) => Value::Nodes(items.clone()),
+            Value::String(s) => Value::String(s.clone()),
+            Value::Number(n) => Value::Number(*n),
+            Value::Bool(b) => Value::Bool(*b),
+        }
+    }
+}
+
+fn parse_number(s: &str) -> f64 {
+    s.trim().parse().unwrap_or(f64::NAN)
+}
+
+/// Sorts items in a document order and removes duplicates.
+fn sort(mut items: Vec<Item>) -> Vec<Item> {
+    items.sort_by_key(Item::order);
+    items.dedup();
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use url::Url;
+
+    fn ids(doc: &Document, expr: &str) -> Vec<String> {
+        evaluate(doc, expr, ROOT)
+            .unwrap()
+            .into_iter()
+            .map(|id| doc.attr(id, "id").unwrap_or("").to_owned())
+            .collect()
+    }
+
+    #[test]
+    fn xpath() {
+        let doc = Document::parse(
+            Url::parse("http://localhost").unwrap(),
+            r#"<div id="app">
+                <label id="label">Name</label><input id="name" name="name">
+                <table id="table">
+                    <tr id="r1"><td id="c11">1</td><td id="c12">2</td></tr>
+                    <tr id="r2"><td id="c21">3</td><td id="c22">Save it</td></tr>
+                </table>
+                <button id="save" class="btn primary">  Save  </button>
+            </div>"#,
+        );
+
+        assert_eq!(ids(&doc, "//input[@name='name']"), ["name"]);
+        assert_eq!(ids(&doc, "//*[@id=\"app\"]/button"), ["save"]);
+        assert_eq!(ids(&doc, "//tr[2]/td[1]"), ["c21"]);
+        assert_eq!(ids(&doc, "//td[1]"), ["c11", "c21"]);
+        assert_eq!(ids(&doc, "(//td)[last()]"), ["c22"]);
+        assert_eq!(ids(&doc, "//button[normalize-space(.)='Save']"), ["save"]);
+        assert_eq!(ids(&doc, "//*[contains(text(), 'Save')]"), ["c22", "save"]);
+        assert_eq!(
+            ids(&doc, "//label[.='Name']/following-sibling::input"),
+            ["name"]
+        );
+        assert_eq!(ids(&doc, "//td[@id='c22']/ancestor::table"), ["table"]);
+        assert_eq!(
+            ids(
+                &doc,
+                "//button[contains(@class, 'primary') and not(@disabled)]"
+            ),
+            ["save"]
+        );
+        assert_eq!(ids(&doc, "//label | //button"), ["label", "save"]);
+        assert_eq!(ids(&doc, "//tr[count(td) = 2][position() > 1]"), ["r2"]);
+        assert!(evaluate(&doc, "//td/text()", ROOT).is_err());
+        assert!(evaluate(&doc, "//td[", ROOT).is_err());
+    }
+}
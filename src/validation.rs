@@ -69,11 +69,11 @@ fn validate(cmd: &Cmd, state: &mut Vec<State>) -> Result<(), RunnerErrorKind> {
 
 fn validate_end(state: &mut Vec<State>) -> Result<(), RunnerErrorKind> {
     match state.last() {
-        Some(st) if matches!(st, State::While | State::If | State::ForEach | State::Times) => {
+        Some(State::While | State::If | State::ForEach | State::Times) => {
             state.pop();
             Ok(())
         }
-        Some(st) if matches!(st, State::ElseIf | State::Else) => {
+        Some(State::ElseIf | State::Else) => {
             state.pop();
             validate_end(state)
         }
@@ -85,11 +85,11 @@ fn validate_end(state: &mut Vec<State>) -> Result<(), RunnerErrorKind> {
 
 fn validate_else(state: &mut Vec<State>) -> Result<(), RunnerErrorKind> {
     match state.last() {
-        Some(st) if matches!(st, State::If | State::ElseIf) => {
+        Some(State::If | State::ElseIf) => {
             state.push(State::Else);
             Ok(())
         }
-        Some(st) if matches!(st, State::Else) => Err(RunnerErrorKind::BranchValidationError(
+        Some(State::Else) => Err(RunnerErrorKind::BranchValidationError(
             "too many else operations".to_owned(),
         )),
         _ => Err(RunnerErrorKind::BranchValidationError(
@@ -100,11 +100,11 @@ fn validate_else(state: &mut Vec<State>) -> Result<(), RunnerErrorKind> {
 
 fn validate_else_if(state: &mut Vec<State>) -> Result<(), RunnerErrorKind> {
     match state.last() {
-        Some(st) if matches!(st, State::If | State::ElseIf) => {
+        Some(State::If | State::ElseIf) => {
             state.push(State::ElseIf);
             Ok(())
         }
-        Some(st) if matches!(st, State::Else) => Err(RunnerErrorKind::BranchValidationError(
+        Some(State::Else) => Err(RunnerErrorKind::BranchValidationError(
             "usage of elseif after else".to_owned(),
         )),
         _ => Err(RunnerErrorKind::BranchValidationError(
@@ -115,7 +115,7 @@ fn validate_else_if(state: &mut Vec<State>) -> Result<(), RunnerErrorKind> {
 
 fn validate_do(state: &mut Vec<State>) -> Result<(), RunnerErrorKind> {
     match state.last() {
-        Some(st) if matches!(st, State::Do) => {
+        Some(State::Do) => {
             state.pop();
             Ok(())
         }
@@ -243,13 +243,13 @@ mod tests {
     #[test]
     fn test_validation_times() {
         assert!(validate_conditions(&commands(vec![
-            Cmd::Times(String::new()),
+            Cmd::Times(1.into()),
             Cmd::Echo(String::new()),
             Cmd::End,
         ]))
         .is_ok());
 
-        assert!(validate_conditions(&commands(vec![Cmd::Times(String::new())])).is_err());
+        assert!(validate_conditions(&commands(vec![Cmd::Times(1.into())])).is_err());
     }
 
     fn commands(cmds: Vec<Cmd>) -> Vec<Command> {
@@ -257,6 +257,6 @@ mod tests {
     }
 
     fn blank_cmd(cmd: Cmd) -> Command {
-        Command::new("".to_owned(), "".to_owned(), cmd)
+        Command::new("", "", cmd)
     }
 }
@@ -4,36 +4,79 @@
 
 #![cfg(feature = "fantoccini_backend")]
 
-use super::{Element as WebElement, Locator, Webdriver};
-use crate::error::RunnerErrorKind;
+use super::{Element as WebElement, IntoWebdriver, Locator, Webdriver};
+use crate::{
+    error::RunnerErrorKind,
+    js_lib::{DRAG_AND_DROP_TO_OBJECT, SUBMIT_FORM, WATCH_DROP},
+};
 use fantoccini as fan;
+use hyper::{client::HttpConnector, Method};
+use hyper_tls::HttpsConnector;
 use serde_json::Value as Json;
 use std::time::Duration;
+use webdriver::error::{ErrorStatus, WebDriverError};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+const IS_VISIBLE: &str = "function isVisible(e) { \
+    if (!e) return false; \
+    var style = window.getComputedStyle(e); \
+    return style.display !== 'none' && style.visibility !== 'hidden' && e.getClientRects().length > 0; \
+}";
+
+const IS_EDITABLE: &str = "function isEditable(e) { \
+    return isVisible(e) && !e.disabled && !e.readOnly; \
+}";
 
 /// Fantoccini Webdriver interface
-pub struct Client(pub fan::Client);
+///
+/// fantoccini doesn't expose WebDriver alert and actions endpoints,
+/// so dialogs and mouse commands are handled by requests sent to the WebDriver server directly.
+/// It requires a URL of the server, hence a client must be created by [`Client::new`].
+pub struct Client {
+    client: fan::Client,
+    wire: Wire,
+}
+
+impl Client {
+    /// Creates a client from a fantoccini client which is connected to a `webdriver` URL.
+    pub fn new(client: fan::Client, webdriver: &str) -> Result<Self, url::ParseError> {
+        let wire = Wire::new(webdriver)?;
+        Ok(Self { client, wire })
+    }
+}
+
+impl IntoWebdriver for Client {
+    type Webdriver = Client;
+
+    fn into_webdriver(self) -> Self::Webdriver {
+        self
+    }
+}
 
 #[async_trait::async_trait]
 impl Webdriver for Client {
     type Element = Element;
 
     async fn goto(&mut self, url: &str) -> Result<(), RunnerErrorKind> {
-        self.0.goto(url).await?;
+        self.client.goto(url).await?;
         Ok(())
     }
 
     async fn find(&mut self, locator: Locator) -> Result<Self::Element, RunnerErrorKind> {
-        let e = self.0.find((&locator).into()).await?;
-        Ok(Element(e))
+        let locator = supported_locator(locator);
+        let e = self.client.find(locator.as_fan()).await?;
+        Ok(self.element(e))
     }
 
     async fn find_all(&mut self, locator: Locator) -> Result<Vec<Self::Element>, RunnerErrorKind> {
+        let locator = supported_locator(locator);
         let elements = self
-            .0
-            .find_all((&locator).into())
+            .client
+            .find_all(locator.as_fan())
             .await?
             .into_iter()
-            .map(|e| Element(e))
+            .map(|e| self.element(e))
             .collect();
         Ok(elements)
     }
@@ -43,7 +86,15 @@ impl Webdriver for Client {
         locator: Locator,
         timeout: Duration,
     ) -> Result<(), RunnerErrorKind> {
-        self.wait_for_present(locator, timeout).await
+        let condition = format!("{} return isVisible(arguments[0]);", IS_VISIBLE);
+        self.wait_for_condition(locator, timeout, &condition)
+            .await
+            .map_err(|err| match err {
+                RunnerErrorKind::Timeout(..) => {
+                    RunnerErrorKind::Timeout("Wait for element visible timeout".to_string())
+                }
+                err => err,
+            })
     }
 
     async fn wait_for_not_visible(
@@ -51,7 +102,15 @@ impl Webdriver for Client {
         locator: Locator,
         timeout: Duration,
     ) -> Result<(), RunnerErrorKind> {
-        todo!()
+        let condition = format!("{} return !isVisible(arguments[0]);", IS_VISIBLE);
+        self.wait_for_condition(locator, timeout, &condition)
+            .await
+            .map_err(|err| match err {
+                RunnerErrorKind::Timeout(..) => {
+                    RunnerErrorKind::Timeout("Wait for element not visible timeout".to_string())
+                }
+                err => err,
+            })
     }
 
     async fn wait_for_not_present(
@@ -59,11 +118,12 @@ impl Webdriver for Client {
         locator: Locator,
         timeout: Duration,
     ) -> Result<(), RunnerErrorKind> {
-        let locator = (&locator).into();
+        let locator = supported_locator(locator);
+        let locator = locator.as_fan();
 
         let now = std::time::Instant::now();
         loop {
-            match self.0.find(locator).await {
+            match self.client.find(locator).await {
                 Ok(..) => {}
                 Err(fantoccini::error::CmdError::NoSuchElement(..)) => break Ok(()),
                 Err(err) => Err(err)?,
@@ -82,11 +142,12 @@ impl Webdriver for Client {
         locator: Locator,
         timeout: Duration,
     ) -> Result<(), RunnerErrorKind> {
-        let locator = (&locator).into();
+        let locator = supported_locator(locator);
+        let locator = locator.as_fan();
 
         let now = std::time::Instant::now();
         loop {
-            match self.0.find(locator).await {
+            match self.client.find(locator).await {
                 Ok(..) => break Ok(()),
                 Err(fantoccini::error::CmdError::NoSuchElement(..)) => (),
                 Err(err) => Err(err)?,
@@ -105,36 +166,18 @@ impl Webdriver for Client {
         locator: Locator,
         timeout: Duration,
     ) -> Result<(), RunnerErrorKind> {
-        let locator = (&locator).into();
-        let now = std::time::Instant::now();
-        loop {
-            match self.0.find(locator).await {
-                Ok(mut element) => {
-                    let is_displayed = match element.attr("style").await? {
-                        Some(style) if !style.contains("display: none;") => true,
-                        None => true,
-                        _ => false,
-                    };
-
-                    let is_enabled = match element.attr("disabled").await? {
-                        Some(..) => false,
-                        _ => true,
-                    };
-
-                    if is_displayed && is_enabled {
-                        break Ok(());
-                    }
+        let condition = format!(
+            "{} {} return !!arguments[0] && isEditable(arguments[0]);",
+            IS_VISIBLE, IS_EDITABLE
+        );
+        self.wait_for_condition(locator, timeout, &condition)
+            .await
+            .map_err(|err| match err {
+                RunnerErrorKind::Timeout(..) => {
+                    RunnerErrorKind::Timeout("Wait for element editable timeout".to_string())
                 }
-                Err(fantoccini::error::CmdError::NoSuchElement(..)) => {}
-                Err(err) => Err(err)?,
-            }
-
-            if now.elapsed() > timeout {
-                break Err(RunnerErrorKind::Timeout(
-                    "Wait for element editable timeout".to_string(),
-                ));
-            }
-        }
+                err => err,
+            })
     }
 
     async fn wait_for_not_editable(
@@ -142,21 +185,32 @@ impl Webdriver for Client {
         locator: Locator,
         timeout: Duration,
     ) -> Result<(), RunnerErrorKind> {
-        todo!()
+        let condition = format!(
+            "{} {} return !!arguments[0] && !isEditable(arguments[0]);",
+            IS_VISIBLE, IS_EDITABLE
+        );
+        self.wait_for_condition(locator, timeout, &condition)
+            .await
+            .map_err(|err| match err {
+                RunnerErrorKind::Timeout(..) => {
+                    RunnerErrorKind::Timeout("Wait for element not editable timeout".to_string())
+                }
+                err => err,
+            })
     }
 
     async fn current_url(&mut self) -> Result<url::Url, RunnerErrorKind> {
-        let url = self.0.current_url().await?;
+        let url = self.client.current_url().await?;
         Ok(url)
     }
 
     async fn set_window_size(&mut self, width: u32, height: u32) -> Result<(), RunnerErrorKind> {
-        self.0.set_window_size(width, height).await?;
+        self.client.set_window_size(width, height).await?;
         Ok(())
     }
 
     async fn execute(&mut self, script: &str, args: Vec<Json>) -> Result<Json, RunnerErrorKind> {
-        let json = self.0.execute(script, args).await?;
+        let json = self.client.execute(script, args).await?;
         Ok(json)
     }
 
@@ -165,41 +219,173 @@ impl Webdriver for Client {
         script: &str,
         args: Vec<Json>,
     ) -> Result<Json, RunnerErrorKind> {
-        let json = self.0.execute_async(script, args).await?;
+        let json = self.client.execute_async(script, args).await?;
         Ok(json)
     }
 
     async fn close(&mut self) -> Result<(), RunnerErrorKind> {
-        self.0.close().await?;
+        self.client.close().await?;
+        Ok(())
+    }
+
+    async fn delete_cookies(&mut self) -> Result<(), RunnerErrorKind> {
+        self.client.delete_all_cookies().await?;
         Ok(())
     }
 
     async fn alert_text(&mut self) -> Result<String, RunnerErrorKind> {
-        todo!()
+        match self.issue(Method::GET, "alert/text", None).await? {
+            Json::String(text) => Ok(text),
+            _ => Err(RunnerErrorKind::MismatchedType(
+                "expected to get an alert text as a string".to_owned(),
+            )),
+        }
     }
 
     async fn alert_accept(&mut self) -> Result<(), RunnerErrorKind> {
-        todo!()
+        self.issue(Method::POST, "alert/accept", Some(serde_json::json!({})))
+            .await?;
+        Ok(())
     }
 
     async fn alert_dissmis(&mut self) -> Result<(), RunnerErrorKind> {
-        todo!()
+        self.issue(Method::POST, "alert/dismiss", Some(serde_json::json!({})))
+            .await?;
+        Ok(())
+    }
+
+    async fn alert_send_text(&mut self, text: &str) -> Result<(), RunnerErrorKind> {
+        let body = serde_json::json!({ "text": text });
+        self.issue(Method::POST, "alert/text", Some(body)).await?;
+        Ok(())
     }
 
     async fn double_click(&mut self, locator: Locator) -> Result<(), RunnerErrorKind> {
-        todo!()
+        self.double_click_at(locator, (0, 0)).await
     }
 
     async fn mouse_down(&mut self, locator: Locator) -> Result<(), RunnerErrorKind> {
-        todo!()
+        self.mouse_down_at(locator, (0, 0)).await
     }
 
     async fn mouse_up(&mut self, locator: Locator) -> Result<(), RunnerErrorKind> {
-        todo!()
+        self.mouse_up_at(locator, (0, 0)).await
+    }
+
+    async fn mouse_over(&mut self, locator: Locator) -> Result<(), RunnerErrorKind> {
+        self.pointer_actions_at(locator, (0, 0), &[]).await
+    }
+
+    async fn mouse_out(&mut self, locator: Locator) -> Result<(), RunnerErrorKind> {
+        let locator = supported_locator(locator);
+        let element = self.client.find(locator.as_fan()).await?;
+        let script = "var rect = arguments[0].getBoundingClientRect(); \
+            return [rect.left + window.pageXOffset, rect.top + window.pageYOffset, \
+            rect.width, rect.height, window.innerWidth, window.innerHeight];";
+        let geometry = self
+            .client
+            .execute(script, vec![serde_json::json!(element)])
+            .await?;
+        let geometry = match geometry {
+            Json::Array(values) if values.len() == 6 => values
+                .iter()
+                .map(|value| value.as_f64().unwrap_or_default())
+                .collect::<Vec<_>>(),
+            _ => {
+                return Err(RunnerErrorKind::MismatchedType(
+                    "expected to get an element rect and a viewport size".to_owned(),
+                ))
+            }
+        };
+
+        let offset = super::mouse_out_offset(
+            (geometry[0], geometry[1], geometry[2], geometry[3]),
+            (geometry[4], geometry[5]),
+        )?;
+        self.perform_pointer_actions(vec![pointer_move(&element, offset)])
+            .await
+    }
+
+    async fn mouse_move_at(
+        &mut self,
+        locator: Locator,
+        coord: (i32, i32),
+    ) -> Result<(), RunnerErrorKind> {
+        self.pointer_actions_at(locator, coord, &[]).await
+    }
+
+    async fn mouse_down_at(
+        &mut self,
+        locator: Locator,
+        coord: (i32, i32),
+    ) -> Result<(), RunnerErrorKind> {
+        self.pointer_actions_at(locator, coord, &["pointerDown"])
+            .await
+    }
+
+    async fn mouse_up_at(
+        &mut self,
+        locator: Locator,
+        coord: (i32, i32),
+    ) -> Result<(), RunnerErrorKind> {
+        self.pointer_actions_at(locator, coord, &["pointerUp"])
+            .await
+    }
+
+    async fn drag_and_drop(
+        &mut self,
+        source: Locator,
+        destination: Locator,
+    ) -> Result<(), RunnerErrorKind> {
+        let source = supported_locator(source);
+        let destination = supported_locator(destination);
+        let source_element = self.client.find(source.as_fan()).await?;
+        let destination_element = self.client.find(destination.as_fan()).await?;
+        let script = format!("{} watchDrop(arguments[0]);", WATCH_DROP);
+        self.client
+            .execute(&script, vec![serde_json::json!(destination_element)])
+            .await?;
+
+        self.perform_pointer_actions(vec![
+            pointer_move(&source_element, (0, 0)),
+            pointer_button("pointerDown"),
+            pointer_move(&destination_element, (0, 0)),
+            pointer_button("pointerUp"),
+        ])
+        .await?;
+
+        // Pointer actions don't fire HTML5 drag events, so the drop is replayed by a script.
+        let script = format!("{} return isDropped();", WATCH_DROP);
+        if self.client.execute(&script, Vec::new()).await? != Json::Bool(true) {
+            let script = format!(
+                "{} dragAndDropToObject(arguments[0], arguments[1]);",
+                DRAG_AND_DROP_TO_OBJECT
+            );
+            self.client
+                .execute(
+                    &script,
+                    vec![
+                        serde_json::json!(source_element),
+                        serde_json::json!(destination_element),
+                    ],
+                )
+                .await?;
+        }
+
+        Ok(())
     }
 
     async fn title(&mut self) -> Result<String, RunnerErrorKind> {
-        todo!()
+        let title = self
+            .client
+            .execute("return document.title;", Vec::new())
+            .await?;
+        match title {
+            Json::String(title) => Ok(title),
+            _ => Err(RunnerErrorKind::MismatchedType(
+                "expected to get a title as a string".to_owned(),
+            )),
+        }
     }
 
     async fn click_at(
@@ -207,7 +393,8 @@ impl Webdriver for Client {
         locator: Locator,
         coord: (i32, i32),
     ) -> Result<(), RunnerErrorKind> {
-        todo!()
+        self.pointer_actions_at(locator, coord, &["pointerDown", "pointerUp"])
+            .await
     }
 
     async fn double_click_at(
@@ -215,33 +402,197 @@ impl Webdriver for Client {
         locator: Locator,
         coord: (i32, i32),
     ) -> Result<(), RunnerErrorKind> {
-        todo!()
+        self.pointer_actions_at(
+            locator,
+            coord,
+            &["pointerDown", "pointerUp", "pointerDown", "pointerUp"],
+        )
+        .await
+    }
+
+    async fn switch_to_default_content(&mut self) -> Result<(), RunnerErrorKind> {
+        self.client.clone().enter_frame(None).await?;
+        Ok(())
+    }
+
+    async fn switch_to_parent_frame(&mut self) -> Result<(), RunnerErrorKind> {
+        self.client.clone().enter_parent_frame().await?;
+        Ok(())
+    }
+
+    async fn switch_to_frame(&mut self, index: u16) -> Result<(), RunnerErrorKind> {
+        self.client.clone().enter_frame(Some(index)).await?;
+        Ok(())
+    }
+
+    async fn switch_to_frame_element(&mut self, locator: Locator) -> Result<(), RunnerErrorKind> {
+        let locator = supported_locator(locator);
+        let e = self.client.find(locator.as_fan()).await?;
+        e.enter_frame().await?;
+        Ok(())
+    }
+
+    async fn window_handles(&mut self) -> Result<Vec<String>, RunnerErrorKind> {
+        let handles = self
+            .client
+            .windows()
+            .await?
+            .into_iter()
+            .map(|window| window.0)
+            .collect();
+        Ok(handles)
+    }
+
+    async fn current_window_handle(&mut self) -> Result<String, RunnerErrorKind> {
+        let window = self.client.window().await?;
+        Ok(window.0)
+    }
+
+    async fn switch_to_window(&mut self, handle: &str) -> Result<(), RunnerErrorKind> {
+        let window = webdriver::common::WebWindow(handle.to_owned());
+        self.client.switch_to_window(window).await?;
+        Ok(())
+    }
+
+    async fn screenshot(&mut self) -> Result<Vec<u8>, RunnerErrorKind> {
+        let png = self.client.screenshot().await?;
+        Ok(png)
+    }
+
+    async fn page_source(&mut self) -> Result<String, RunnerErrorKind> {
+        let source = self.client.source().await?;
+        Ok(source)
+    }
+}
+
+impl Client {
+    fn element(&self, element: fan::elements::Element) -> Element {
+        Element {
+            element,
+            wire: self.wire.clone(),
+        }
+    }
+
+    /// Polls an element untill a JS `condition` becomes true or a timeout is reached.
+    ///
+    /// The condition gets the element as `arguments[0]` or `null` if it's not present.
+    async fn wait_for_condition(
+        &mut self,
+        locator: Locator,
+        timeout: Duration,
+        condition: &str,
+    ) -> Result<(), RunnerErrorKind> {
+        let locator = supported_locator(locator);
+        let now = std::time::Instant::now();
+        loop {
+            let element = match self.client.find(locator.as_fan()).await {
+                Ok(element) => serde_json::json!(element),
+                Err(fan::error::CmdError::NoSuchElement(..)) => Json::Null,
+                Err(err) => Err(err)?,
+            };
+
+            if self.client.execute(condition, vec![element]).await? == Json::Bool(true) {
+                break Ok(());
+            }
+
+            if now.elapsed() > timeout {
+                break Err(RunnerErrorKind::Timeout(
+                    "Wait for element condition timeout".to_string(),
+                ));
+            }
+
+            futures_timer::Delay::new(POLL_INTERVAL).await;
+        }
+    }
+
+    /// Sends a command of the current session which fantoccini doesn't support
+    /// straight to the WebDriver server.
+    async fn issue(
+        &mut self,
+        method: Method,
+        command: &str,
+        body: Option<Json>,
+    ) -> Result<Json, RunnerErrorKind> {
+        self.wire
+            .issue(&mut self.client, method, command, body)
+            .await
+    }
+
+    /// Moves the mouse to an element and then presses and releases its left button
+    /// in the order of `buttons`, e.g. `pointerDown`.
+    ///
+    /// Coordinates are an offset from the element's center.
+    async fn pointer_actions_at(
+        &mut self,
+        locator: Locator,
+        coord: (i32, i32),
+        buttons: &[&str],
+    ) -> Result<(), RunnerErrorKind> {
+        let locator = supported_locator(locator);
+        let element = self.client.find(locator.as_fan()).await?;
+        let mut actions = vec![pointer_move(&element, coord)];
+        actions.extend(buttons.iter().map(|button| pointer_button(button)));
+        self.perform_pointer_actions(actions).await
+    }
+
+    /// Performs mouse actions by the WebDriver actions endpoint
+    /// as fantoccini doesn't support it.
+    async fn perform_pointer_actions(&mut self, actions: Vec<Json>) -> Result<(), RunnerErrorKind> {
+        let body = serde_json::json!({
+            "actions": [{
+                "type": "pointer",
+                "id": "mouse",
+                "parameters": { "pointerType": "mouse" },
+                "actions": actions,
+            }]
+        });
+        self.issue(Method::POST, "actions", Some(body)).await?;
+        Ok(())
     }
 }
 
-pub struct Element(fan::elements::Element);
+/// A pointer action which moves the mouse to an offset from an element's center.
+fn pointer_move(element: &fan::elements::Element, (x, y): (i32, i32)) -> Json {
+    serde_json::json!({
+        "type": "pointerMove",
+        "duration": 0,
+        "origin": element,
+        "x": x,
+        "y": y,
+    })
+}
+
+/// A pointer action which presses or releases the left mouse button.
+fn pointer_button(action: &str) -> Json {
+    serde_json::json!({ "type": action, "button": 0 })
+}
+
+pub struct Element {
+    element: fan::elements::Element,
+    wire: Wire,
+}
 
 #[async_trait::async_trait]
 impl WebElement for Element {
     type Driver = Client;
 
     async fn attr(&mut self, attribute: &str) -> Result<Option<String>, RunnerErrorKind> {
-        let attr = self.0.attr(attribute).await?;
+        let attr = self.element.attr(attribute).await?;
         Ok(attr)
     }
 
     async fn prop(&mut self, prop: &str) -> Result<Option<String>, RunnerErrorKind> {
-        let prop = self.0.prop(prop).await?;
+        let prop = self.element.prop(prop).await?;
         Ok(prop)
     }
 
     async fn text(&mut self) -> Result<String, RunnerErrorKind> {
-        let text = self.0.text().await?;
+        let text = self.element.text().await?;
         Ok(text)
     }
 
     async fn html(&mut self, inner: bool) -> Result<String, RunnerErrorKind> {
-        let html = self.0.html(inner).await?;
+        let html = self.element.html(inner).await?;
         Ok(html)
     }
 
@@ -249,53 +600,247 @@ impl WebElement for Element {
     where
         Self: Sized,
     {
-        let e = self.0.find((&search).into()).await?;
-        Ok(Element(e))
+        let search = supported_locator(search);
+        let e = self.element.find(search.as_fan()).await?;
+        Ok(Element {
+            element: e,
+            wire: self.wire.clone(),
+        })
     }
 
     async fn click(mut self) -> Result<Self::Driver, RunnerErrorKind> {
-        let c = self.0.click().await?;
-        Ok(Client(c))
+        let c = self.element.click().await?;
+        Ok(Client {
+            client: c,
+            wire: self.wire,
+        })
     }
 
     async fn select_by_index(mut self, index: usize) -> Result<Self::Driver, RunnerErrorKind> {
-        let c = self.0.select_by_index(index).await?;
-        Ok(Client(c))
+        let c = self.element.select_by_index(index).await?;
+        Ok(Client {
+            client: c,
+            wire: self.wire,
+        })
     }
 
     async fn select_by_value(mut self, value: &str) -> Result<Self::Driver, RunnerErrorKind> {
-        let c = self.0.select_by_value(value).await?;
-        Ok(Client(c))
+        let c = self.element.select_by_value(value).await?;
+        Ok(Client {
+            client: c,
+            wire: self.wire,
+        })
     }
 
     async fn send_keys(mut self, value: &str) -> Result<(), RunnerErrorKind> {
-        todo!()
+        self.element.send_keys(value).await?;
+        Ok(())
+    }
+
+    async fn submit(mut self) -> Result<(), RunnerErrorKind> {
+        // fantoccini can submit only forms so we submit an element's form from JS.
+        let script = format!("{} submitForm(arguments[0]);", SUBMIT_FORM);
+        let args = vec![serde_json::json!(self.element)];
+        self.element.client().execute(&script, args).await?;
+        Ok(())
     }
 
     async fn select_by_label(mut self, value: &str) -> Result<Self::Driver, RunnerErrorKind> {
-        todo!()
+        let c = self.element.select_by_label(value).await?;
+        Ok(Client {
+            client: c,
+            wire: self.wire,
+        })
     }
 
     async fn is_selected(&mut self) -> Result<bool, RunnerErrorKind> {
-        todo!()
+        self.check("return !!(arguments[0].checked || arguments[0].selected);")
+            .await
     }
 
     async fn is_present(&mut self) -> Result<bool, RunnerErrorKind> {
-        todo!()
+        match self.check("return arguments[0].isConnected;").await {
+            Err(RunnerErrorKind::WebdriverError(err)) if is_stale_element_error(err.as_ref()) => {
+                Ok(false)
+            }
+            result => result,
+        }
     }
 
     async fn is_enabled(&mut self) -> Result<bool, RunnerErrorKind> {
-        todo!()
+        self.check("return !arguments[0].disabled;").await
+    }
+}
+
+impl Element {
+    /// Runs a JS predicate against the element which is passed as `arguments[0]`.
+    ///
+    /// fantoccini returns only string properties so boolean ones are checked this way.
+    async fn check(&mut self, script: &str) -> Result<bool, RunnerErrorKind> {
+        let args = vec![serde_json::json!(self.element)];
+        let result = self.element.clone().client().execute(script, args).await?;
+        Ok(result == Json::Bool(true))
     }
 }
 
-impl<'a> Into<fan::Locator<'a>> for &'a Locator {
-    fn into(self) -> fan::Locator<'a> {
+fn is_stale_element_error(err: &(dyn std::error::Error + 'static)) -> bool {
+    matches!(
+        err.downcast_ref::<fan::error::CmdError>(),
+        Some(fan::error::CmdError::Standard(err)) if err.error == ErrorStatus::StaleElementReference
+    )
+}
+
+/// A connection to a WebDriver server for endpoints which fantoccini doesn't expose.
+#[derive(Clone)]
+struct Wire {
+    url: url::Url,
+    http: hyper::Client<HttpsConnector<HttpConnector>>,
+}
+
+impl Wire {
+    fn new(webdriver: &str) -> Result<Self, url::ParseError> {
+        let url = webdriver.parse()?;
+        let http = hyper::Client::builder().build(HttpsConnector::new());
+        Ok(Self { url, http })
+    }
+
+    /// Sends a command of the client's session and returns a value of the response.
+    async fn issue(
+        &self,
+        client: &mut fan::Client,
+        method: Method,
+        command: &str,
+        body: Option<Json>,
+    ) -> Result<Json, RunnerErrorKind> {
+        let session = client.session_id().await?.ok_or_else(|| {
+            RunnerErrorKind::from(fan::error::CmdError::Standard(WebDriverError::new(
+                ErrorStatus::InvalidSessionId,
+                "the fantoccini client has no session",
+            )))
+        })?;
+        // The URL is joined the same way fantoccini does it,
+        // so the request reaches the same endpoint as its own commands.
+        let url = self.url.join(&format!("session/{}/{}", session, command))?;
+
+        let body = body.map_or_else(hyper::Body::empty, |body| body.to_string().into());
+        let request = hyper::Request::builder()
+            .method(method)
+            .uri(url.as_str())
+            .header(
+                hyper::header::CONTENT_TYPE,
+                "application/json; charset=utf-8",
+            )
+            .body(body)
+            .map_err(|err| RunnerErrorKind::WebdriverError(Box::new(err)))?;
+        let response = self
+            .http
+            .request(request)
+            .await
+            .map_err(|err| RunnerErrorKind::WebdriverError(Box::new(err)))?;
+
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body())
+            .await
+            .map_err(|err| RunnerErrorKind::WebdriverError(Box::new(err)))?;
+        let mut body: Json = serde_json::from_slice(&body)
+            .map_err(|err| RunnerErrorKind::WebdriverError(Box::new(err)))?;
+        let value = body["value"].take();
+        if status.is_success() {
+            return Ok(value);
+        }
+
+        let error = value["error"].as_str().unwrap_or_default().to_owned();
+        let message = value["message"].as_str().unwrap_or_default().to_owned();
+        Err(fan::error::CmdError::Standard(WebDriverError::new(error.into(), message)).into())
+    }
+}
+
+/// A locator of a strategy which fantoccini supports.
+#[derive(Debug, PartialEq)]
+enum SupportedLocator {
+    Css(String),
+    Id(String),
+    LinkText(String),
+    XPath(String),
+}
+
+impl SupportedLocator {
+    fn as_fan(&self) -> fan::Locator<'_> {
         match self {
-            Locator::LinkText(s) => fan::Locator::LinkText(&s),
-            Locator::Css(s) => fan::Locator::Css(&s),
-            Locator::Id(s) => fan::Locator::Id(&s),
-            Locator::XPath(s) => fan::Locator::XPath(&s),
+            Self::Css(s) => fan::Locator::Css(s),
+            Self::Id(s) => fan::Locator::Id(s),
+            Self::LinkText(s) => fan::Locator::LinkText(s),
+            Self::XPath(s) => fan::Locator::XPath(s),
         }
     }
 }
+
+/// Converts a locator to one which fantoccini supports.
+///
+/// fantoccini has no `name` and `partial link text` strategies
+/// so they're replaced by CSS and XPath equivalents.
+fn supported_locator(locator: Locator) -> SupportedLocator {
+    match locator {
+        Locator::Css(s) => SupportedLocator::Css(s),
+        Locator::Id(s) => SupportedLocator::Id(s),
+        Locator::LinkText(s) => SupportedLocator::LinkText(s),
+        Locator::XPath(s) => SupportedLocator::XPath(s),
+        Locator::Name(name) => SupportedLocator::Css(format!(
+            "[name=\"{}\"]",
+            name.replace('\\', "\\\\").replace('"', "\\\"")
+        )),
+        Locator::PartialLinkText(text) => {
+            SupportedLocator::XPath(format!(".//a[contains(., {})]", xpath_literal(&text)))
+        }
+    }
+}
+
+/// Quotes a string for XPath which has no escape sequences.
+fn xpath_literal(s: &str) -> String {
+    if !s.contains('"') {
+        format!("\"{}\"", s)
+    } else if !s.contains('\'') {
+        format!("'{}'", s)
+    } else {
+        let parts = s
+            .split('"')
+            .map(|part| format!("\"{}\"", part))
+            .collect::<Vec<_>>();
+        format!("concat({})", parts.join(", '\"', "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn supported_locators() {
+        assert_eq!(
+            supported_locator(Locator::Name("user".to_owned())),
+            SupportedLocator::Css(r#"[name="user"]"#.to_owned())
+        );
+        assert_eq!(
+            supported_locator(Locator::Name(r#"a"b\c"#.to_owned())),
+            SupportedLocator::Css(r#"[name="a\"b\\c"]"#.to_owned())
+        );
+        assert_eq!(
+            supported_locator(Locator::PartialLinkText("more".to_owned())),
+            SupportedLocator::XPath(r#".//a[contains(., "more")]"#.to_owned())
+        );
+        assert_eq!(
+            supported_locator(Locator::LinkText("more".to_owned())),
+            SupportedLocator::LinkText("more".to_owned())
+        );
+    }
+
+    #[test]
+    fn xpath_literals() {
+        assert_eq!(xpath_literal("it's"), r#""it's""#);
+        assert_eq!(xpath_literal(r#"say "hi""#), r#"'say "hi"'"#);
+        assert_eq!(
+            xpath_literal(r#"it's "hi""#),
+            r#"concat("it's ", '"', "hi", '"', "")"#
+        );
+    }
+}
@@ -2,110 +2,294 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+//! An interface which a backend must implement to be used by [`Runner`].
+//!
+//! `siderunner` ships `thirtyfour` and `fantoccini` backends,
+//! but any other driver may be plugged in by implementing [`Webdriver`] and [`Element`]
+//! and creating a runner by [`Runner::with_backend`].
+//!
+//! [`Runner`]: crate::Runner
+//! [`Runner::with_backend`]: crate::Runner::with_backend
+
 use serde_json::Value as Json;
 use std::time::Duration;
 
 use crate::error::RunnerErrorKind;
 
-pub(crate) mod fantoccini;
-pub(crate) mod thirtyfour;
+pub mod fantoccini;
+pub mod thirtyfour;
 
-/// Webdriver an interface over a ebdriver functionality.
+/// Webdriver an interface over a webdriver functionality.
 ///
-/// Mainly created for test purpouses and to be able to support 2 backends.
+/// Methods which take a [`Locator`] are expected to find an element on their own
+/// and return an error if there's no such element.
+/// An error of an underlying driver is expected to be wrapped into [`RunnerErrorKind::WebdriverError`].
 #[async_trait::async_trait]
 pub trait Webdriver: Send {
+    /// An element type the webdriver produces.
     type Element: Element<Driver = Self>;
 
+    /// Opens a page by URL.
     async fn goto(&mut self, url: &str) -> Result<(), RunnerErrorKind>;
+    /// Finds a first element by locator.
     async fn find(&mut self, locator: Locator) -> Result<Self::Element, RunnerErrorKind>;
+    /// Finds all elements by locator.
     async fn find_all(&mut self, locator: Locator) -> Result<Vec<Self::Element>, RunnerErrorKind>;
+    /// Returns URL of the current page.
     async fn current_url(&mut self) -> Result<url::Url, RunnerErrorKind>;
+    /// Waits untill an element is displayed.
     async fn wait_for_visible(
         &mut self,
         locator: Locator,
         timeout: Duration,
     ) -> Result<(), RunnerErrorKind>;
+    /// Waits untill an element is not displayed.
     async fn wait_for_not_visible(
         &mut self,
         locator: Locator,
         timeout: Duration,
     ) -> Result<(), RunnerErrorKind>;
+    /// Waits untill an element is present in DOM.
     async fn wait_for_present(
         &mut self,
         locator: Locator,
         timeout: Duration,
     ) -> Result<(), RunnerErrorKind>;
+    /// Waits untill an element is removed from DOM.
     async fn wait_for_not_present(
         &mut self,
         locator: Locator,
         timeout: Duration,
     ) -> Result<(), RunnerErrorKind>;
+    /// Waits untill an element is enabled and not read only.
     async fn wait_for_editable(
         &mut self,
         locator: Locator,
         timeout: Duration,
     ) -> Result<(), RunnerErrorKind>;
+    /// Waits untill an element is disabled or read only.
     async fn wait_for_not_editable(
         &mut self,
         locator: Locator,
         timeout: Duration,
     ) -> Result<(), RunnerErrorKind>;
+    /// Sets a size of the current window.
     async fn set_window_size(&mut self, width: u32, height: u32) -> Result<(), RunnerErrorKind>;
+    /// Executes a synchronous JS script with arguments.
     async fn execute(&mut self, script: &str, mut args: Vec<Json>)
         -> Result<Json, RunnerErrorKind>;
+    /// Executes an asynchronous JS script with arguments.
     async fn execute_async(
         &mut self,
         script: &str,
         mut args: Vec<Json>,
     ) -> Result<Json, RunnerErrorKind>;
+    /// Closes the current window.
     async fn close(&mut self) -> Result<(), RunnerErrorKind>;
+    /// Deletes all cookies.
+    async fn delete_cookies(&mut self) -> Result<(), RunnerErrorKind>;
+    /// Returns a text of an opened dialog.
     async fn alert_text(&mut self) -> Result<String, RunnerErrorKind>;
+    /// Accepts an opened dialog.
     async fn alert_accept(&mut self) -> Result<(), RunnerErrorKind>;
+    /// Dismisses an opened dialog.
     async fn alert_dissmis(&mut self) -> Result<(), RunnerErrorKind>;
+    /// Types a text into an opened prompt.
+    async fn alert_send_text(&mut self, text: &str) -> Result<(), RunnerErrorKind>;
+    /// Double clicks in the center of an element.
     async fn double_click(&mut self, locator: Locator) -> Result<(), RunnerErrorKind>;
+    /// Presses a left mouse button in the center of an element.
     async fn mouse_down(&mut self, locator: Locator) -> Result<(), RunnerErrorKind>;
+    /// Releases a left mouse button in the center of an element.
     async fn mouse_up(&mut self, locator: Locator) -> Result<(), RunnerErrorKind>;
+    /// Moves a pointer over an element.
+    async fn mouse_over(&mut self, locator: Locator) -> Result<(), RunnerErrorKind>;
+    /// Moves a pointer out of an element.
+    async fn mouse_out(&mut self, locator: Locator) -> Result<(), RunnerErrorKind>;
+    /// Moves a pointer to an offset from the center of an element.
+    async fn mouse_move_at(
+        &mut self,
+        locator: Locator,
+        coord: (i32, i32),
+    ) -> Result<(), RunnerErrorKind>;
+    /// Presses a left mouse button at an offset from the center of an element.
+    async fn mouse_down_at(
+        &mut self,
+        locator: Locator,
+        coord: (i32, i32),
+    ) -> Result<(), RunnerErrorKind>;
+    /// Releases a left mouse button at an offset from the center of an element.
+    async fn mouse_up_at(
+        &mut self,
+        locator: Locator,
+        coord: (i32, i32),
+    ) -> Result<(), RunnerErrorKind>;
+    /// Drags an element and drops it onto another one.
+    ///
+    /// Native actions are performed first.
+    /// Browsers don't fire HTML5 drag events for them, so if the destination doesn't get
+    /// a `drop` event the drag is repeated by dispatching the events from a script.
+    async fn drag_and_drop(
+        &mut self,
+        source: Locator,
+        destination: Locator,
+    ) -> Result<(), RunnerErrorKind>;
+    /// Returns a title of the current page.
     async fn title(&mut self) -> Result<String, RunnerErrorKind>;
+    /// Clicks at an offset from the center of an element.
     async fn click_at(
         &mut self,
         locator: Locator,
         coord: (i32, i32),
     ) -> Result<(), RunnerErrorKind>;
+    /// Double clicks at an offset from the center of an element.
     async fn double_click_at(
         &mut self,
         locator: Locator,
         coord: (i32, i32),
     ) -> Result<(), RunnerErrorKind>;
+    /// Switches to the top level browsing context.
+    async fn switch_to_default_content(&mut self) -> Result<(), RunnerErrorKind>;
+    /// Switches to the parent of the current frame.
+    async fn switch_to_parent_frame(&mut self) -> Result<(), RunnerErrorKind>;
+    /// Switches to a frame by its index.
+    async fn switch_to_frame(&mut self, index: u16) -> Result<(), RunnerErrorKind>;
+    /// Switches to a frame by its element.
+    async fn switch_to_frame_element(&mut self, locator: Locator) -> Result<(), RunnerErrorKind>;
+    /// Returns handles of all opened windows.
+    async fn window_handles(&mut self) -> Result<Vec<String>, RunnerErrorKind>;
+    /// Returns a handle of the current window.
+    async fn current_window_handle(&mut self) -> Result<String, RunnerErrorKind>;
+    /// Switches to a window by its handle.
+    async fn switch_to_window(&mut self, handle: &str) -> Result<(), RunnerErrorKind>;
+    /// Takes a screenshot of the current window in PNG format.
+    async fn screenshot(&mut self) -> Result<Vec<u8>, RunnerErrorKind>;
+    /// Returns a source of the current page.
+    async fn page_source(&mut self) -> Result<String, RunnerErrorKind>;
+}
+
+/// IntoWebdriver converts a client of a backend into a [`Webdriver`].
+///
+/// It's implemented for clients of the supported backends
+/// so a [`Runner`] can be created from either of them.
+///
+/// [`Runner`]: crate::Runner
+pub trait IntoWebdriver {
+    type Webdriver: Webdriver;
+
+    fn into_webdriver(self) -> Self::Webdriver;
 }
 
 /// Element represents functionality which may be taken agains a WebElement by means of Webdriver.
 #[async_trait::async_trait]
 pub trait Element: Send {
+    /// A webdriver type which is given back by consuming methods.
+    ///
+    /// Some drivers (e.g. `fantoccini`) consume themselves on such actions.
     type Driver;
 
+    /// Returns an attribute value or `None` if there's no such attribute.
     async fn attr(&mut self, attribute: &str) -> Result<Option<String>, RunnerErrorKind>;
+    /// Returns a property value or `None` if there's no such property.
     async fn prop(&mut self, prop: &str) -> Result<Option<String>, RunnerErrorKind>;
+    /// Returns a visible text.
     async fn text(&mut self) -> Result<String, RunnerErrorKind>;
+    /// Returns an inner or outer HTML.
     async fn html(&mut self, inner: bool) -> Result<String, RunnerErrorKind>;
+    /// Finds a first descendant element by locator.
     async fn find(&mut self, search: Locator) -> Result<Self, RunnerErrorKind>
     where
         Self: Sized;
+    /// Clicks on the element.
     async fn click(mut self) -> Result<Self::Driver, RunnerErrorKind>;
+    /// Selects an option of a `select` element by index.
     async fn select_by_index(mut self, index: usize) -> Result<Self::Driver, RunnerErrorKind>;
+    /// Selects an option of a `select` element by value.
     async fn select_by_value(mut self, value: &str) -> Result<Self::Driver, RunnerErrorKind>;
+    /// Selects an option of a `select` element by visible text.
     async fn select_by_label(mut self, value: &str) -> Result<Self::Driver, RunnerErrorKind>;
+    /// Types keys into the element.
+    ///
+    /// Special keys are passed as WebDriver key codes, e.g. `'\u{E007}'` for Enter.
     async fn send_keys(mut self, value: &str) -> Result<(), RunnerErrorKind>;
+    /// Submits a form the element belongs to.
+    ///
+    /// The form is validated and gets a `submit` event as if it was submitted by a user,
+    /// unless a browser doesn't support `requestSubmit`.
+    async fn submit(mut self) -> Result<(), RunnerErrorKind>;
+    /// Checks whether the element is selected or checked.
     async fn is_selected(&mut self) -> Result<bool, RunnerErrorKind>;
+    /// Checks whether the element is still attached to DOM.
     async fn is_present(&mut self) -> Result<bool, RunnerErrorKind>;
+    /// Checks whether the element is enabled.
     async fn is_enabled(&mut self) -> Result<bool, RunnerErrorKind>;
 }
 
 /// Locator represents a way how to find a particular web element.
+///
+/// It's produced from a target of a command with variables already substituted.
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
 pub enum Locator {
+    /// A CSS selector.
     Css(String),
+    /// A value of `id` attribute.
     Id(String),
+    /// A value of `name` attribute.
+    Name(String),
+    /// An exact text of a link.
     LinkText(String),
+    /// A part of a text of a link.
+    PartialLinkText(String),
+    /// An XPath expression.
     XPath(String),
 }
+
+/// Calculates an offset from an element's center to a point right outside of the element
+/// but still inside of the viewport.
+///
+/// The logic is borrowed from Selenium IDE's implementation of `mouseOut`.
+pub(crate) fn mouse_out_offset(
+    (x, y, width, height): (f64, f64, f64, f64),
+    (viewport_width, viewport_height): (f64, f64),
+) -> Result<(i32, i32), RunnerErrorKind> {
+    if y > 0.0 {
+        Ok((0, -((height / 2.0).ceil() as i32 + 1)))
+    } else if viewport_width - x - width > 0.0 {
+        Ok(((width / 2.0).ceil() as i32 + 1, 0))
+    } else if viewport_height - y - height > 0.0 {
+        Ok((0, (height / 2.0).ceil() as i32 + 1))
+    } else if x > 0.0 {
+        Ok((-((width / 2.0).ceil() as i32 + 1), 0))
+    } else {
+        Err(RunnerErrorKind::ActionFailed(
+            "Unable to perform mouse out as the element takes up the entire viewport".to_owned(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mouse_out_offset_test() {
+        let viewport = (1000.0, 800.0);
+        assert_eq!(
+            mouse_out_offset((10.0, 10.0, 100.0, 50.0), viewport).unwrap(),
+            (0, -26)
+        );
+        assert_eq!(
+            mouse_out_offset((0.0, 0.0, 100.0, 50.0), viewport).unwrap(),
+            (51, 0)
+        );
+        assert_eq!(
+            mouse_out_offset((0.0, 0.0, 1000.0, 50.0), viewport).unwrap(),
+            (0, 26)
+        );
+        assert_eq!(
+            mouse_out_offset((10.0, 0.0, 990.0, 800.0), viewport).unwrap(),
+            (-496, 0)
+        );
+        assert!(mouse_out_offset((0.0, 0.0, 1000.0, 800.0), viewport).is_err());
+    }
+}
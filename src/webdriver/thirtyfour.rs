@@ -4,8 +4,11 @@
 
 #![cfg(feature = "thirtyfour_backend")]
 
-use super::{Element, Locator, Webdriver};
-use crate::error::RunnerErrorKind;
+use super::{Element, IntoWebdriver, Locator, Webdriver};
+use crate::{
+    error::RunnerErrorKind,
+    js_lib::{DRAG_AND_DROP_TO_OBJECT, SUBMIT_FORM, WATCH_DROP},
+};
 use serde_json::Value as Json;
 use std::time::Duration;
 use thirtyfour::{
@@ -17,6 +20,14 @@ use url::Url;
 /// Thirtyfour Webdriver interface
 pub struct Client<'a>(pub &'a thirtyfour::WebDriver);
 
+impl<'a> IntoWebdriver for &'a thirtyfour::WebDriver {
+    type Webdriver = Client<'a>;
+
+    fn into_webdriver(self) -> Self::Webdriver {
+        Client(self)
+    }
+}
+
 #[async_trait::async_trait]
 impl<'a> Webdriver for Client<'a> {
     type Element = WebElement<'a>;
@@ -178,6 +189,11 @@ impl<'a> Webdriver for Client<'a> {
         Ok(())
     }
 
+    async fn delete_cookies(&mut self) -> Result<(), RunnerErrorKind> {
+        self.0.delete_all_cookies().await?;
+        Ok(())
+    }
+
     async fn alert_text(&mut self) -> Result<String, RunnerErrorKind> {
         let text = self.0.switch_to().alert().text().await?;
         Ok(text)
@@ -193,6 +209,11 @@ impl<'a> Webdriver for Client<'a> {
         Ok(())
     }
 
+    async fn alert_send_text(&mut self, text: &str) -> Result<(), RunnerErrorKind> {
+        self.0.switch_to().alert().send_keys(text).await?;
+        Ok(())
+    }
+
     async fn double_click(&mut self, locator: Locator) -> Result<(), RunnerErrorKind> {
         let by: By = (&locator).into();
         let el = self.0.find_element(by).await?;
@@ -232,6 +253,149 @@ impl<'a> Webdriver for Client<'a> {
         Ok(())
     }
 
+    async fn mouse_over(&mut self, locator: Locator) -> Result<(), RunnerErrorKind> {
+        let by: By = (&locator).into();
+        let el = self.0.find_element(by).await?;
+        self.0
+            .action_chain()
+            .move_to_element_center(&el)
+            .perform()
+            .await?;
+
+        Ok(())
+    }
+
+    async fn mouse_out(&mut self, locator: Locator) -> Result<(), RunnerErrorKind> {
+        let by: By = (&locator).into();
+        let el = self.0.find_element(by).await?;
+        let rect = el.rect().await?;
+        let viewport = self
+            .0
+            .execute_script("return [window.innerWidth, window.innerHeight];")
+            .await?;
+        let viewport = match viewport.value() {
+            Json::Array(size) if size.len() == 2 => (
+                size[0].as_f64().unwrap_or_default(),
+                size[1].as_f64().unwrap_or_default(),
+            ),
+            _ => {
+                return Err(RunnerErrorKind::MismatchedType(
+                    "expected to get a viewport size".to_owned(),
+                ))
+            }
+        };
+
+        let (x, y) = super::mouse_out_offset(
+            (
+                rect.x as f64,
+                rect.y as f64,
+                rect.width as f64,
+                rect.height as f64,
+            ),
+            viewport,
+        )?;
+
+        self.0
+            .action_chain()
+            .move_to_element_center(&el)
+            .move_by_offset(x, y)
+            .perform()
+            .await?;
+
+        Ok(())
+    }
+
+    async fn mouse_move_at(
+        &mut self,
+        locator: Locator,
+        coord: (i32, i32),
+    ) -> Result<(), RunnerErrorKind> {
+        let by: By = (&locator).into();
+        let el = self.0.find_element(by).await?;
+        self.0
+            .action_chain()
+            .move_to_element_center(&el)
+            .move_by_offset(coord.0, coord.1)
+            .perform()
+            .await?;
+
+        Ok(())
+    }
+
+    async fn mouse_down_at(
+        &mut self,
+        locator: Locator,
+        coord: (i32, i32),
+    ) -> Result<(), RunnerErrorKind> {
+        let by: By = (&locator).into();
+        let el = self.0.find_element(by).await?;
+        self.0
+            .action_chain()
+            .move_to_element_center(&el)
+            .move_by_offset(coord.0, coord.1)
+            .click_and_hold()
+            .perform()
+            .await?;
+
+        Ok(())
+    }
+
+    async fn mouse_up_at(
+        &mut self,
+        locator: Locator,
+        coord: (i32, i32),
+    ) -> Result<(), RunnerErrorKind> {
+        let by: By = (&locator).into();
+        let el = self.0.find_element(by).await?;
+        self.0
+            .action_chain()
+            .move_to_element_center(&el)
+            .move_by_offset(coord.0, coord.1)
+            .release()
+            .perform()
+            .await?;
+
+        Ok(())
+    }
+
+    async fn drag_and_drop(
+        &mut self,
+        source: Locator,
+        destination: Locator,
+    ) -> Result<(), RunnerErrorKind> {
+        let by: By = (&source).into();
+        let source = self.0.find_element(by).await?;
+        let by: By = (&destination).into();
+        let destination = self.0.find_element(by).await?;
+
+        let mut args = ScriptArgs::new();
+        args.push(&destination)?;
+        let script = format!("{} watchDrop(arguments[0]);", WATCH_DROP);
+        self.0.execute_script_with_args(&script, &args).await?;
+
+        self.0
+            .action_chain()
+            .drag_and_drop_element(&source, &destination)
+            .perform()
+            .await?;
+
+        // HTML5 drag and drop doesn't react on native actions, the shim replays it then.
+        let script = format!("{} return isDropped();", WATCH_DROP);
+        let dropped = self.0.execute_script(&script).await?;
+        if dropped.value() != &Json::Bool(true) {
+            let mut args = ScriptArgs::new();
+            args.push(&source)?;
+            args.push(&destination)?;
+            let script = format!(
+                "{} dragAndDropToObject(arguments[0], arguments[1]);",
+                DRAG_AND_DROP_TO_OBJECT
+            );
+            self.0.execute_script_with_args(&script, &args).await?;
+        }
+
+        Ok(())
+    }
+
     async fn title(&mut self) -> Result<String, RunnerErrorKind> {
         let title = self.0.title().await?;
         Ok(title)
@@ -272,6 +436,59 @@ impl<'a> Webdriver for Client<'a> {
 
         Ok(())
     }
+
+    async fn switch_to_default_content(&mut self) -> Result<(), RunnerErrorKind> {
+        self.0.switch_to().default_content().await?;
+        Ok(())
+    }
+
+    async fn switch_to_parent_frame(&mut self) -> Result<(), RunnerErrorKind> {
+        self.0.switch_to().parent_frame().await?;
+        Ok(())
+    }
+
+    async fn switch_to_frame(&mut self, index: u16) -> Result<(), RunnerErrorKind> {
+        self.0.switch_to().frame_number(index).await?;
+        Ok(())
+    }
+
+    async fn switch_to_frame_element(&mut self, locator: Locator) -> Result<(), RunnerErrorKind> {
+        let by: By = (&locator).into();
+        let el = self.0.find_element(by).await?;
+        self.0.switch_to().frame_element(&el).await?;
+        Ok(())
+    }
+
+    async fn window_handles(&mut self) -> Result<Vec<String>, RunnerErrorKind> {
+        let handles = self
+            .0
+            .window_handles()
+            .await?
+            .into_iter()
+            .map(|handle| handle.to_string())
+            .collect();
+        Ok(handles)
+    }
+
+    async fn current_window_handle(&mut self) -> Result<String, RunnerErrorKind> {
+        let handle = self.0.current_window_handle().await?;
+        Ok(handle.to_string())
+    }
+
+    async fn switch_to_window(&mut self, handle: &str) -> Result<(), RunnerErrorKind> {
+        self.0.switch_to().window(&handle.into()).await?;
+        Ok(())
+    }
+
+    async fn screenshot(&mut self) -> Result<Vec<u8>, RunnerErrorKind> {
+        let png = self.0.screenshot_as_png().await?;
+        Ok(png)
+    }
+
+    async fn page_source(&mut self) -> Result<String, RunnerErrorKind> {
+        let source = self.0.page_source().await?;
+        Ok(source)
+    }
 }
 
 pub struct WebElement<'a>(thirtyfour::WebElement<'a>, &'a thirtyfour::WebDriver);
@@ -343,6 +560,16 @@ impl<'a> Element for WebElement<'a> {
         Ok(())
     }
 
+    async fn submit(mut self) -> Result<(), RunnerErrorKind> {
+        // WebDriver spec has no submit endpoint so we submit an element's form from JS,
+        // the same way Selenium does.
+        let script = format!("{} submitForm(arguments[0]);", SUBMIT_FORM);
+        let mut args = ScriptArgs::new();
+        args.push(&self.0)?;
+        self.1.execute_script_with_args(&script, &args).await?;
+        Ok(())
+    }
+
     async fn select_by_label(mut self, value: &str) -> Result<Self::Driver, RunnerErrorKind> {
         SelectElement::new(&self.0)
             .await?
@@ -372,8 +599,10 @@ impl<'a> From<&'a Locator> for By<'a> {
     fn from(locator: &'a Locator) -> By<'a> {
         match locator {
             Locator::LinkText(s) => By::LinkText(s),
+            Locator::PartialLinkText(s) => By::PartialLinkText(s),
             Locator::Css(s) => By::Css(s),
             Locator::Id(s) => By::Id(s),
+            Locator::Name(s) => By::Name(s),
             Locator::XPath(s) => By::XPath(s),
         }
     }
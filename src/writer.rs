@@ -0,0 +1,490 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::parser::{format, Cmd, Command, File, Param, Suite, Target, Test};
+
+/// Write a [`File`] in [.side format]
+///
+/// The output can be parsed back by [`parse`] and opened in Selenium IDE.
+///
+/// [`File`]: struct.File.html
+/// [`parse`]: fn.parse.html
+/// [.side format]: https://github.com/SeleniumHQ/selenium-ide/issues/77
+pub fn write<W: std::io::Write>(file: &File, w: W) -> Result<(), serde_json::Error> {
+    let side = format_file(file);
+    serde_json::to_writer_pretty(w, &side)
+}
+
+fn format_file(file: &File) -> format::SideFile {
+    format::SideFile {
+        id: file.id.clone(),
+        version: file.version.clone(),
+        name: file.name.clone(),
+        url: file.url.clone(),
+        tests: file.tests.iter().map(format_test).collect(),
+        suites: file.suites.iter().map(format_suite).collect(),
+        urls: file.urls.clone(),
+        plugins: file.plugins.clone(),
+    }
+}
+
+fn format_test(test: &Test) -> format::Test {
+    format::Test {
+        id: test.id.clone(),
+        name: test.name.clone(),
+        commands: test.commands.iter().map(format_command).collect(),
+    }
+}
+
+fn format_suite(suite: &Suite) -> format::Suite {
+    format::Suite {
+        id: suite.id.clone(),
+        name: suite.name.clone(),
+        persist_session: suite.persist_session,
+        parallel: suite.parallel,
+        timeout: suite.timeout.as_secs(),
+        tests: suite.tests.clone(),
+    }
+}
+
+fn format_command(command: &Command) -> format::Command {
+    let (cmd, target, value) = format_cmd(&command.cmd);
+    let targets = command.targets.iter().map(format_target).collect();
+
+    format::Command {
+        id: command.id.clone(),
+        comment: command.comment.clone(),
+        cmd,
+        target,
+        targets,
+        value,
+        opens_window: command.opens_window.is_some(),
+        window_handle_name: command
+            .opens_window
+            .as_ref()
+            .map_or_else(String::new, |window| window.handle_name.clone()),
+        window_timeout: command
+            .opens_window
+            .as_ref()
+            .and_then(|window| window.timeout)
+            .map(|timeout| timeout.as_millis() as u64),
+    }
+}
+
+fn format_target(target: &Target) -> (String, String) {
+    let tag = match &target.location {
+        Param::Completed(location) => {
+            let tp = location.type_name();
+            match &target.tag {
+                Some(tag) if tag != tp => format!("{}:{}", tp, tag),
+                _ => tp.to_owned(),
+            }
+        }
+        // A type of the location isn't known until variables are substituted.
+        Param::Incomplete(..) => target.tag.clone().unwrap_or_default(),
+    };
+
+    (target.location.to_string(), tag)
+}
+
+/// Format a command in a way it's stored in a side file.
+///
+/// It returns a name of the command, its target and value.
+pub(crate) fn format_cmd(cmd: &Cmd) -> (String, String, String) {
+    let (name, target, value) = match cmd {
+        Cmd::Open(url) => ("open", url.clone(), String::new()),
+        Cmd::Echo(text) => ("echo", text.clone(), String::new()),
+        Cmd::Click(target) => ("click", target.location.to_string(), String::new()),
+        Cmd::Pause(timeout) => ("pause", timeout.format(), String::new()),
+        Cmd::SetWindowSize(size) => ("setWindowSize", size.format(), String::new()),
+        Cmd::Select { target, locator } => {
+            ("select", target.location.to_string(), locator.to_string())
+        }
+        Cmd::AddSelection(target, value) => {
+            ("addSelection", target.location.to_string(), value.clone())
+        }
+        Cmd::RemoveSelection(target, value) => (
+            "removeSelection",
+            target.location.to_string(),
+            value.clone(),
+        ),
+        Cmd::WaitForElementVisible { target, timeout } => (
+            "waitForElementVisible",
+            target.location.to_string(),
+            timeout.format(),
+        ),
+        Cmd::WaitForElementNotVisible { target, timeout } => (
+            "waitForElementNotVisible",
+            target.location.to_string(),
+            timeout.format(),
+        ),
+        Cmd::WaitForElementEditable { target, timeout } => (
+            "waitForElementEditable",
+            target.location.to_string(),
+            timeout.format(),
+        ),
+        Cmd::WaitForElementNotEditable { target, timeout } => (
+            "waitForElementNotEditable",
+            target.location.to_string(),
+            timeout.format(),
+        ),
+        Cmd::WaitForElementPresent { target, timeout } => (
+            "waitForElementPresent",
+            target.location.to_string(),
+            timeout.format(),
+        ),
+        Cmd::WaitForElementNotPresent { target, timeout } => (
+            "waitForElementNotPresent",
+            target.location.to_string(),
+            timeout.format(),
+        ),
+        Cmd::StoreText { var, target } => ("storeText", target.location.to_string(), var.clone()),
+        Cmd::Store { var, value } => ("store", value.clone(), var.clone()),
+        Cmd::Execute { script, var } => (
+            "executeScript",
+            script.clone(),
+            var.clone().unwrap_or_default(),
+        ),
+        Cmd::ExecuteAsync { script, var } => (
+            "executeScriptAsync",
+            script.clone(),
+            var.clone().unwrap_or_default(),
+        ),
+        Cmd::While(cond) => ("while", cond.clone(), String::new()),
+        Cmd::If(cond) => ("if", cond.clone(), String::new()),
+        Cmd::ElseIf(cond) => ("elseIf", cond.clone(), String::new()),
+        Cmd::Else => ("else", String::new(), String::new()),
+        Cmd::Do => ("do", String::new(), String::new()),
+        Cmd::RepeatIf(cond) => ("repeatIf", cond.clone(), String::new()),
+        Cmd::ForEach { iterator, var } => ("forEach", iterator.clone(), var.clone()),
+        Cmd::End => ("end", String::new(), String::new()),
+        Cmd::StoreXpathCount { var, xpath } => (
+            "storeXpathCount",
+            format!("xpath={}", xpath),
+            var.clone().unwrap_or_default(),
+        ),
+        Cmd::Close => ("close", String::new(), String::new()),
+        Cmd::Custom { cmd, target, value } => return (cmd.clone(), target.clone(), value.clone()),
+        Cmd::Assert { var, value } => ("assert", var.clone(), value.clone()),
+        Cmd::RunScript { script } => ("runScript", script.clone(), String::new()),
+        Cmd::AnswerOnNextPrompt(answer) => ("answerOnNextPrompt", answer.clone(), String::new()),
+        Cmd::AssertAlert(text) => ("assertAlert", text.clone(), String::new()),
+        Cmd::AssertPrompt(text) => ("assertPrompt", text.clone(), String::new()),
+        Cmd::AssertChecked(target) => ("assertChecked", target.location.to_string(), String::new()),
+        Cmd::AssertNotChecked(target) => (
+            "assertNotChecked",
+            target.location.to_string(),
+            String::new(),
+        ),
+        Cmd::AssertSelectedValue(target, value) => (
+            "assertSelectedValue",
+            target.location.to_string(),
+            value.clone(),
+        ),
+        Cmd::AssertNotSelectedValue(target, value) => (
+            "assertNotSelectedValue",
+            target.location.to_string(),
+            value.clone(),
+        ),
+        Cmd::AssertText(target, text) => ("assertText", target.location.to_string(), text.clone()),
+        Cmd::AssertNotText(target, text) => {
+            ("assertNotText", target.location.to_string(), text.clone())
+        }
+        Cmd::DoubleClick(target) => ("doubleClick", target.location.to_string(), String::new()),
+        Cmd::EditContent(target, value) => {
+            ("editContent", target.location.to_string(), value.clone())
+        }
+        Cmd::SendKeys(target, keys) => ("sendKeys", target.location.to_string(), keys.clone()),
+        Cmd::Type(target, text) => ("type", target.location.to_string(), text.clone()),
+        Cmd::Check(target) => ("check", target.location.to_string(), String::new()),
+        Cmd::UnCheck(target) => ("uncheck", target.location.to_string(), String::new()),
+        Cmd::MouseUp(target) => ("mouseUp", target.location.to_string(), String::new()),
+        Cmd::MouseDown(target) => ("mouseDown", target.location.to_string(), String::new()),
+        Cmd::ChooseCancelOnNextConfirmation => (
+            "chooseCancelOnNextConfirmation",
+            String::new(),
+            String::new(),
+        ),
+        Cmd::ChooseOkOnNextConfirmation => {
+            ("chooseOkOnNextConfirmation", String::new(), String::new())
+        }
+        Cmd::ChooseCancelOnNextPrompt => ("chooseCancelOnNextPrompt", String::new(), String::new()),
+        Cmd::AssertTitle(title) => ("assertTitle", title.clone(), String::new()),
+        Cmd::StoreTitle(var) => ("storeTitle", String::new(), var.clone()),
+        Cmd::AssertValue(target, value) => {
+            ("assertValue", target.location.to_string(), value.clone())
+        }
+        Cmd::AssertConfirmation(text) => ("assertConfirmation", text.clone(), String::new()),
+        Cmd::AssertSelectedLabel(target, label) => (
+            "assertSelectedLabel",
+            target.location.to_string(),
+            label.clone(),
+        ),
+        Cmd::Times(times) => ("times", times.format(), String::new()),
+        Cmd::RunTest(test) => ("run", test.clone(), String::new()),
+        Cmd::StoreJson(json, var) => ("storeJson", json.clone(), var.clone()),
+        Cmd::StoreValue(target, var) => ("storeValue", target.location.to_string(), var.clone()),
+        Cmd::StoreAttribute(target, attribute, var) => (
+            "storeAttribute",
+            format!("{}@{}", target.location, attribute),
+            var.clone(),
+        ),
+        Cmd::AssertElementPresent(target) => (
+            "assertElementPresent",
+            target.location.to_string(),
+            String::new(),
+        ),
+        Cmd::AssertElementNotPresent(target) => (
+            "assertElementNotPresent",
+            target.location.to_string(),
+            String::new(),
+        ),
+        Cmd::AssertEditable(target) => {
+            ("assertEditable", target.location.to_string(), String::new())
+        }
+        Cmd::AssertNotEditable(target) => (
+            "assertNotEditable",
+            target.location.to_string(),
+            String::new(),
+        ),
+        Cmd::ClickAt(target, coords) => ("clickAt", target.location.to_string(), coords.format()),
+        Cmd::DoubleClickAt(target, coords) => (
+            "doubleClickAt",
+            target.location.to_string(),
+            coords.format(),
+        ),
+        Cmd::MouseOver(target) => ("mouseOver", target.location.to_string(), String::new()),
+        Cmd::MouseOut(target) => ("mouseOut", target.location.to_string(), String::new()),
+        Cmd::MouseMoveAt(target, coords) => {
+            ("mouseMoveAt", target.location.to_string(), coords.format())
+        }
+        Cmd::MouseDownAt(target, coords) => {
+            ("mouseDownAt", target.location.to_string(), coords.format())
+        }
+        Cmd::MouseUpAt(target, coords) => {
+            ("mouseUpAt", target.location.to_string(), coords.format())
+        }
+        Cmd::DragAndDropToObject(source, destination) => (
+            "dragAndDropToObject",
+            source.location.to_string(),
+            destination.location.to_string(),
+        ),
+        Cmd::Submit(target) => ("submit", target.location.to_string(), String::new()),
+        Cmd::WebdriverAnswerOnVisiblePrompt(answer) => (
+            "webdriverAnswerOnVisiblePrompt",
+            answer.clone(),
+            String::new(),
+        ),
+        Cmd::WebdriverChooseOkOnVisibleConfirmation => (
+            "webdriverChooseOkOnVisibleConfirmation",
+            String::new(),
+            String::new(),
+        ),
+        Cmd::WebdriverChooseCancelOnVisibleConfirmation => (
+            "webdriverChooseCancelOnVisibleConfirmation",
+            String::new(),
+            String::new(),
+        ),
+        Cmd::WebdriverChooseCancelOnVisiblePrompt => (
+            "webdriverChooseCancelOnVisiblePrompt",
+            String::new(),
+            String::new(),
+        ),
+        Cmd::SelectFrame(locator) => ("selectFrame", locator.to_string(), String::new()),
+        Cmd::SelectWindow(locator) => ("selectWindow", locator.to_string(), String::new()),
+        Cmd::StoreWindowHandle(var) => ("storeWindowHandle", var.clone(), String::new()),
+        Cmd::Verify { var, value } => ("verify", var.clone(), value.clone()),
+        Cmd::VerifyChecked(target) => ("verifyChecked", target.location.to_string(), String::new()),
+        Cmd::VerifyNotChecked(target) => (
+            "verifyNotChecked",
+            target.location.to_string(),
+            String::new(),
+        ),
+        Cmd::VerifyEditable(target) => {
+            ("verifyEditable", target.location.to_string(), String::new())
+        }
+        Cmd::VerifyNotEditable(target) => (
+            "verifyNotEditable",
+            target.location.to_string(),
+            String::new(),
+        ),
+        Cmd::VerifyElementPresent(target) => (
+            "verifyElementPresent",
+            target.location.to_string(),
+            String::new(),
+        ),
+        Cmd::VerifyElementNotPresent(target) => (
+            "verifyElementNotPresent",
+            target.location.to_string(),
+            String::new(),
+        ),
+        Cmd::VerifySelectedLabel(target, label) => (
+            "verifySelectedLabel",
+            target.location.to_string(),
+            label.clone(),
+        ),
+        Cmd::VerifySelectedValue(target, value) => (
+            "verifySelectedValue",
+            target.location.to_string(),
+            value.clone(),
+        ),
+        Cmd::VerifyNotSelectedValue(target, value) => (
+            "verifyNotSelectedValue",
+            target.location.to_string(),
+            value.clone(),
+        ),
+        Cmd::VerifyText(target, text) => ("verifyText", target.location.to_string(), text.clone()),
+        Cmd::VerifyNotText(target, text) => {
+            ("verifyNotText", target.location.to_string(), text.clone())
+        }
+        Cmd::VerifyTitle(title) => ("verifyTitle", title.clone(), String::new()),
+        Cmd::VerifyValue(target, value) => {
+            ("verifyValue", target.location.to_string(), value.clone())
+        }
+    };
+
+    (name.to_owned(), target, value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn _write_round_trip() {
+        let mut files = Vec::new();
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        side_files(&root.join("tests").join("resources"), &mut files);
+        files.push(root.join("examples").join("wiki.side"));
+
+        assert!(!files.is_empty());
+
+        for path in files {
+            let input = std::fs::read(&path).unwrap();
+            let file = parse(input.as_slice()).unwrap_or_else(|err| panic!("{:?} {:?}", path, err));
+
+            let mut output = Vec::new();
+            write(&file, &mut output).unwrap();
+
+            let input: serde_json::Value = serde_json::from_slice(&input).unwrap();
+            let output: serde_json::Value = serde_json::from_slice(&output).unwrap();
+            assert_eq!(input, output, "{:?}", path);
+        }
+    }
+
+    #[test]
+    fn _write_urls_and_plugins() {
+        let input = serde_json::json!({
+            "id": "1",
+            "version": "2.0",
+            "name": "project",
+            "url": "http://localhost:8000",
+            "tests": [],
+            "suites": [],
+            "urls": ["http://localhost:8000", "https://example.com"],
+            "plugins": [{ "id": "plugin", "name": "A plugin", "version": "1.0.0" }]
+        });
+
+        let file = parse(input.to_string().as_bytes()).unwrap();
+        let mut output = Vec::new();
+        write(&file, &mut output).unwrap();
+
+        let output: serde_json::Value = serde_json::from_slice(&output).unwrap();
+        assert_eq!(input, output);
+    }
+
+    #[test]
+    fn _write_window_timeout_only_if_set() {
+        let input = serde_json::json!({
+            "id": "1",
+            "version": "2.0",
+            "name": "project",
+            "url": "http://localhost:8000",
+            "tests": [{
+                "id": "2",
+                "name": "test",
+                "commands": [
+                    {
+                        "id": "3",
+                        "comment": "",
+                        "command": "click",
+                        "target": "css=a",
+                        "targets": [],
+                        "value": "",
+                        "opensWindow": true,
+                        "windowHandleName": "win1"
+                    },
+                    {
+                        "id": "4",
+                        "comment": "",
+                        "command": "click",
+                        "target": "css=a",
+                        "targets": [],
+                        "value": "",
+                        "opensWindow": true,
+                        "windowHandleName": "win2",
+                        "windowTimeout": 3000
+                    }
+                ]
+            }],
+            "suites": [],
+            "urls": [],
+            "plugins": []
+        });
+
+        let file = parse(input.to_string().as_bytes()).unwrap();
+        let mut output = Vec::new();
+        write(&file, &mut output).unwrap();
+
+        let output: serde_json::Value = serde_json::from_slice(&output).unwrap();
+        assert_eq!(input, output);
+    }
+
+    #[test]
+    fn _write_targets() {
+        let cmd = Cmd::StoreText {
+            var: "text".to_owned(),
+            target: Target::new(crate::parser::Location::Css("#id".to_owned())),
+        };
+        let mut command = Command::new("1", "a comment", cmd);
+        command.targets = vec![
+            Target {
+                location: crate::parser::Location::Id("id".to_owned()).into(),
+                tag: Some("id".to_owned()),
+            },
+            Target {
+                location: crate::parser::Location::XPath("//a".to_owned()).into(),
+                tag: Some("position".to_owned()),
+            },
+        ];
+
+        let command = format_command(&command);
+
+        assert_eq!(command.id, "1");
+        assert_eq!(command.comment, "a comment");
+        assert_eq!(command.cmd, "storeText");
+        assert_eq!(command.target, "css=#id");
+        assert_eq!(command.value, "text");
+        assert_eq!(
+            command.targets,
+            vec![
+                ("id=id".to_owned(), "id".to_owned()),
+                ("xpath=//a".to_owned(), "xpath:position".to_owned()),
+            ]
+        );
+    }
+
+    fn side_files(dir: &Path, files: &mut Vec<PathBuf>) {
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                side_files(&path, files);
+            } else if path.to_string_lossy().ends_with(".side.json") {
+                files.push(path);
+            }
+        }
+    }
+}
@@ -2,7 +2,8 @@
 
 integration_tests () {
    echo "Run integrational tests"
-   cargo test --verbose --test '*' -- --test-threads=1
+   SIDERUNNER_WEBDRIVER=http://localhost:4444/wd/hub \
+      cargo test --verbose --features testing --test all --test browser "$@" -- --test-threads=1 --include-ignored
 }
 
 SERVER_PORT=8000
@@ -13,7 +14,7 @@ RUNNING_SERVER=$(netstat -tulpn | grep $SERVER_PORT)
 
 if [[ ! -z $RUNNING_SERVER ]] && [[ ! -z $RUNNING_SELENIUM ]]; then
    echo "Environment is already set up"
-   integration_tests
+   integration_tests "$@"
 else
    echo "Environment is not set"
    echo "Setting up environment"
@@ -24,7 +25,7 @@ else
    echo "Wait for containers are ready"
    sleep 10
    
-   integration_tests
+   integration_tests "$@"
 
    echo "Clean up environment"
 
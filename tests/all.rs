@@ -1,45 +1,33 @@
-use siderunner::{parse, Runner};
-use std::fs::File;
-use thirtyfour::{Capabilities, DesiredCapabilities, WebDriver};
-use tokio::test;
+//! Runs resources which the fake WebDriver server can model, i.e. static pages, against it,
+//! or against a WebDriver set by `SIDERUNNER_WEBDRIVER`, e.g. the Selenium container of `docker-compose.yml`.
+//!
+//! Resources which depend on page scripts or dialogs live in `tests/browser.rs`.
+//! The fantoccini backend checks elements by scripts, so without thirtyfour these resources
+//! are ignored unless `SIDERUNNER_WEBDRIVER` is used with `-- --include-ignored`.
 
-async fn testing(path: &str) {
-    let mut file = File::open(path).expect("Failed to read a file");
-    let side_file = parse(&mut file).expect("Failed to parse a file");
+mod common;
 
-    let mut cops = DesiredCapabilities::chrome();
-    cops.set_headless()
-        .expect("Failed to set a headless setting");
-    cops.set_unexpected_alert_behaviour(thirtyfour::AlertBehaviour::Ignore)
-        .expect("Failed to set an option setting");
-    let wb = WebDriver::new("http://localhost:4444/wd/hub", cops)
-        .await
-        .expect("Failed to create a webdriver");
+use siderunner::testing::server::WebdriverServer;
+use tokio::test;
 
-    let mut runner = Runner::new(&wb);
-    match runner.run(&side_file).await {
-        Ok(()) => {
-            wb.quit().await.expect("Failed to stop a webdriver");
+async fn testing(path: &str) {
+    let server;
+    let webdriver_url = match std::env::var("SIDERUNNER_WEBDRIVER") {
+        Ok(url) => url,
+        Err(..) => {
+            server = WebdriverServer::start().expect("Failed to start a server");
+            server.serve_dir("http://localhost:8000/", env!("CARGO_MANIFEST_DIR"));
+            server.url()
         }
-        Err(err) => {
-            wb.quit().await.expect("Failed to stop a webdriver");
+    };
 
-            let test = side_file
-                .tests
-                .iter()
-                .find(|test| test.name.as_str() == err.test.as_ref().unwrap())
-                .unwrap();
-            let failed_command = &test.commands[err.index];
-            if failed_command.comment != "FAIL" {
-                panic!("Failed to run a file {:?} test: {:?}", path, err);
-            }
-        }
-    }
+    common::check(path, &webdriver_url).await;
 }
 
 macro_rules! test_file {
     ( $test_file:expr, $test_name:ident ) => {
         #[test]
+        #[cfg_attr(not(feature = "thirtyfour_backend"), ignore = "needs a browser")]
         async fn $test_name() {
             testing($test_file).await;
         }
@@ -55,51 +43,10 @@ test_file!(
     "tests/resources/commands/assert/test.side.json",
     command_assert
 );
-test_file!(
-    "tests/resources/commands/click/test.side.json",
-    command_click
-);
-test_file!(
-    "tests/resources/commands/execute/test.side.json",
-    command_execute
-);
-test_file!(
-    "tests/resources/commands/execute async/test.side.json",
-    command_execute_async
-);
-test_file!(
-    "tests/resources/commands/run script/test.side.json",
-    command_run_script
-);
-test_file!(
-    "tests/resources/commands/for each/test.side.json",
-    command_for_each
-);
-test_file!(
-    "tests/resources/commands/add selection/test.side.json",
-    command_add_selection
-);
-test_file!(
-    "tests/resources/commands/answer on next prompt/test.side.json",
-    command_answer_on_next_prompt
-);
-#[cfg(not(feature = "fantoccini_backend"))]
-test_file!(
-    "tests/resources/commands/assert alert/test.side.json",
-    command_assert_alert
-);
-test_file!(
-    "tests/resources/commands/assert checked/test.side.json",
-    command_assert_checked
-);
 test_file!(
     "tests/resources/commands/assert not checked/test.side.json",
     command_assert_not_checked
 );
-test_file!(
-    "tests/resources/commands/assert prompt/test.side.json",
-    command_assert_prompt
-);
 test_file!(
     "tests/resources/commands/assert selected value/test.side.json",
     command_assert_selected_value
@@ -116,44 +63,10 @@ test_file!(
     "tests/resources/commands/assert not text/test.side.json",
     command_assert_not_text
 );
-#[cfg(not(feature = "fantoccini_backend"))]
-test_file!(
-    "tests/resources/commands/double click/test.side.json",
-    command_double_click
-);
-#[cfg(not(feature = "fantoccini_backend"))]
-test_file!(
-    "tests/resources/commands/edit content/test.side.json",
-    command_edit_content
-);
-#[cfg(not(feature = "fantoccini_backend"))]
-test_file!(
-    "tests/resources/commands/send keys/test.side.json",
-    command_send_keys
-);
-#[cfg(not(feature = "fantoccini_backend"))]
-test_file!("tests/resources/commands/type/test.side.json", command_type);
 test_file!(
     "tests/resources/commands/check/test.side.json",
     command_check
 );
-#[cfg(not(feature = "fantoccini_backend"))]
-test_file!(
-    "tests/resources/commands/mouse down-up/test.side.json",
-    command_mouse_down_up
-);
-test_file!(
-    "tests/resources/commands/choose cancel on next confirmation/test.side.json",
-    command_choose_cancel_on_next_confirmation
-);
-test_file!(
-    "tests/resources/commands/choose ok on next confirmation/test.side.json",
-    command_choose_ok_on_next_confirmation
-);
-test_file!(
-    "tests/resources/commands/choose cancel on next prompt/test.side.json",
-    command_choose_cancel_on_next_prompt
-);
 test_file!(
     "tests/resources/commands/store title/test.side.json",
     command_store_title
@@ -166,18 +79,10 @@ test_file!(
     "tests/resources/commands/assert value/test.side.json",
     command_assert_value
 );
-test_file!(
-    "tests/resources/commands/assert confirmation/test.side.json",
-    command_assert_confirmation
-);
 test_file!(
     "tests/resources/commands/assert selected label/test.side.json",
     command_assert_selected_label
 );
-test_file!(
-    "tests/resources/commands/store json/test.side.json",
-    command_store_json
-);
 test_file!(
     "tests/resources/commands/store value/test.side.json",
     command_store_value
@@ -186,10 +91,6 @@ test_file!(
     "tests/resources/commands/store attribute/test.side.json",
     command_store_attribute
 );
-test_file!(
-    "tests/resources/commands/remove selection/test.side.json",
-    command_remove_selection
-);
 test_file!(
     "tests/resources/commands/assert element present/test.side.json",
     command_assert_element_present
@@ -207,10 +108,85 @@ test_file!(
     command_assert_element_not_editable
 );
 test_file!(
-    "tests/resources/commands/click at/test.side.json",
-    command_click_at
+    "tests/resources/commands/verify/test.side.json",
+    command_verify
+);
+test_file!(
+    "tests/resources/commands/select frame/test.side.json",
+    command_select_frame
 );
 test_file!(
-    "tests/resources/commands/double click at/test.side.json",
-    command_double_click_at
+    "tests/resources/commands/select window/test.side.json",
+    command_select_window
 );
+test_file!("tests/resources/locators/test.side.json", locators);
+
+/// fantoccini has no alert endpoints so its backend sends them to the server on its own.
+#[cfg(feature = "fantoccini_backend")]
+#[test]
+async fn fantoccini_dialogs() {
+    use siderunner::{webdriver::Webdriver, RunnerErrorKind};
+
+    let server = WebdriverServer::start().expect("Failed to start a server");
+    let mut client = fantoccini::ClientBuilder::native()
+        .connect(&server.url())
+        .await
+        .expect("Failed to create a webdriver");
+    let mut backend = siderunner::webdriver::fantoccini::Client::new(client.clone(), &server.url())
+        .expect("Failed to parse a webdriver url");
+
+    let results = vec![
+        backend.alert_text().await.map(|_| ()),
+        backend.alert_accept().await,
+        backend.alert_dissmis().await,
+        backend.alert_send_text("answer").await,
+    ];
+    for result in results {
+        match result {
+            Err(RunnerErrorKind::WebdriverError(err)) => assert!(
+                matches!(
+                    err.downcast_ref::<fantoccini::error::CmdError>(),
+                    Some(fantoccini::error::CmdError::Standard(err))
+                        if err.error == webdriver::error::ErrorStatus::NoSuchAlert
+                ),
+                "{}",
+                err
+            ),
+            result => panic!("expected a no such alert error but got {:?}", result),
+        }
+    }
+
+    client.close().await.expect("Failed to stop a webdriver");
+}
+
+/// fantoccini has no actions endpoint so its backend sends mouse actions to the server on its own.
+#[cfg(feature = "fantoccini_backend")]
+#[test]
+async fn fantoccini_mouse_actions() {
+    use siderunner::webdriver::{Locator, Webdriver};
+
+    let server = WebdriverServer::start().expect("Failed to start a server");
+    server.add_page(
+        "http://localhost/index.html",
+        r#"<div id="target">Target</div><div id="other">Other</div>"#,
+    );
+    let mut client = fantoccini::ClientBuilder::native()
+        .connect(&server.url())
+        .await
+        .expect("Failed to create a webdriver");
+    let mut backend = siderunner::webdriver::fantoccini::Client::new(client.clone(), &server.url())
+        .expect("Failed to parse a webdriver url");
+
+    let target = || Locator::Id("target".to_owned());
+    backend.goto("http://localhost/index.html").await.unwrap();
+    backend.mouse_over(target()).await.unwrap();
+    backend.mouse_down_at(target(), (1, 2)).await.unwrap();
+    backend.mouse_up_at(target(), (1, 2)).await.unwrap();
+    backend.double_click(target()).await.unwrap();
+    backend
+        .drag_and_drop(target(), Locator::Id("other".to_owned()))
+        .await
+        .unwrap();
+
+    client.close().await.expect("Failed to stop a webdriver");
+}
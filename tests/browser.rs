@@ -0,0 +1,135 @@
+//! Runs resources which depend on page scripts, dialogs or user input and so need a real browser.
+//! The fake WebDriver server can't run them, so the target isn't run by a plain `cargo test`.
+//!
+//! It runs against a WebDriver set by `SIDERUNNER_WEBDRIVER`, e.g. the Selenium container of `docker-compose.yml`,
+//! which must also serve the repository on `http://localhost:8000`.
+//!
+//! ```bash
+//! SIDERUNNER_WEBDRIVER=http://localhost:4444/wd/hub cargo test --test browser -- --test-threads=1
+//! ```
+
+mod common;
+
+use tokio::test;
+
+async fn testing(path: &str) {
+    let webdriver_url = std::env::var("SIDERUNNER_WEBDRIVER")
+        .expect("SIDERUNNER_WEBDRIVER must be set to a WebDriver url");
+
+    common::check(path, &webdriver_url).await;
+}
+
+macro_rules! test_file {
+    ( $test_file:expr, $test_name:ident ) => {
+        #[test]
+        async fn $test_name() {
+            testing($test_file).await;
+        }
+    };
+}
+
+test_file!(
+    "tests/resources/commands/click/test.side.json",
+    command_click
+);
+test_file!(
+    "tests/resources/commands/execute/test.side.json",
+    command_execute
+);
+test_file!(
+    "tests/resources/commands/execute async/test.side.json",
+    command_execute_async
+);
+test_file!(
+    "tests/resources/commands/run script/test.side.json",
+    command_run_script
+);
+test_file!(
+    "tests/resources/commands/for each/test.side.json",
+    command_for_each
+);
+test_file!(
+    "tests/resources/commands/add selection/test.side.json",
+    command_add_selection
+);
+test_file!(
+    "tests/resources/commands/answer on next prompt/test.side.json",
+    command_answer_on_next_prompt
+);
+test_file!(
+    "tests/resources/commands/assert alert/test.side.json",
+    command_assert_alert
+);
+test_file!(
+    "tests/resources/commands/assert checked/test.side.json",
+    command_assert_checked
+);
+test_file!(
+    "tests/resources/commands/assert prompt/test.side.json",
+    command_assert_prompt
+);
+test_file!(
+    "tests/resources/commands/double click/test.side.json",
+    command_double_click
+);
+test_file!(
+    "tests/resources/commands/edit content/test.side.json",
+    command_edit_content
+);
+test_file!(
+    "tests/resources/commands/send keys/test.side.json",
+    command_send_keys
+);
+test_file!("tests/resources/commands/type/test.side.json", command_type);
+test_file!(
+    "tests/resources/commands/mouse down-up/test.side.json",
+    command_mouse_down_up
+);
+test_file!(
+    "tests/resources/commands/mouse over-out/test.side.json",
+    command_mouse_over_out
+);
+test_file!(
+    "tests/resources/commands/drag and drop to object/test.side.json",
+    command_drag_and_drop_to_object
+);
+test_file!(
+    "tests/resources/commands/submit/test.side.json",
+    command_submit
+);
+test_file!(
+    "tests/resources/commands/webdriver visible dialogs/test.side.json",
+    command_webdriver_visible_dialogs
+);
+test_file!(
+    "tests/resources/commands/choose cancel on next confirmation/test.side.json",
+    command_choose_cancel_on_next_confirmation
+);
+test_file!(
+    "tests/resources/commands/choose ok on next confirmation/test.side.json",
+    command_choose_ok_on_next_confirmation
+);
+test_file!(
+    "tests/resources/commands/choose cancel on next prompt/test.side.json",
+    command_choose_cancel_on_next_prompt
+);
+test_file!(
+    "tests/resources/commands/assert confirmation/test.side.json",
+    command_assert_confirmation
+);
+test_file!(
+    "tests/resources/commands/store json/test.side.json",
+    command_store_json
+);
+test_file!(
+    "tests/resources/commands/remove selection/test.side.json",
+    command_remove_selection
+);
+test_file!(
+    "tests/resources/commands/click at/test.side.json",
+    command_click_at
+);
+test_file!(
+    "tests/resources/commands/double click at/test.side.json",
+    command_double_click_at
+);
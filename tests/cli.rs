@@ -0,0 +1,229 @@
+//! Runs the `siderunner` binary against a fake WebDriver server.
+
+use siderunner::testing::server::WebdriverServer;
+use std::process::{Command, Output};
+
+fn siderunner(server: &WebdriverServer, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_siderunner"))
+        .arg("--webdriver")
+        .arg(server.url())
+        .args(args)
+        .output()
+        .expect("Failed to run a binary")
+}
+
+fn server() -> WebdriverServer {
+    let server = WebdriverServer::start().expect("Failed to start a server");
+    server.serve_dir("http://localhost:8000/", env!("CARGO_MANIFEST_DIR"));
+    server
+}
+
+#[test]
+fn run_file() {
+    let server = server();
+    let output = siderunner(&server, &["tests/resources/basic/test.side.json"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success(), "{}", stdout);
+    assert!(stdout.contains("issue324 ... ok"), "{}", stdout);
+    assert!(stdout.contains("1 passed; 0 failed"), "{}", stdout);
+}
+
+#[test]
+fn run_missing_test() {
+    let server = server();
+    let output = siderunner(
+        &server,
+        &["--test", "missing", "tests/resources/basic/test.side.json"],
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert_eq!(output.status.code(), Some(1), "{}", stdout);
+    assert!(stdout.contains("missing ... FAILED"), "{}", stdout);
+}
+
+#[test]
+fn set_variable() {
+    let server = server();
+    let dir = std::env::temp_dir().join(format!("siderunner-var-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("test.side");
+    std::fs::write(
+        &path,
+        r#"{
+            "id": "1", "version": "2.0", "name": "var", "url": "http://localhost:8000",
+            "tests": [{
+                "id": "t1",
+                "name": "greet",
+                "commands": [
+                    { "id": "c1", "comment": "", "command": "echo", "target": "hello ${user}", "targets": [], "value": "" },
+                    { "id": "c2", "comment": "", "command": "assert", "target": "count", "targets": [], "value": "3" }
+                ]
+            }],
+            "suites": [],
+            "urls": [],
+            "plugins": []
+        }"#,
+    )
+    .unwrap();
+
+    let output = siderunner(
+        &server,
+        &[
+            "--var",
+            "user=admin",
+            "--var",
+            "count=3",
+            path.to_str().unwrap(),
+        ],
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert!(output.status.success(), "{}", stdout);
+    assert!(stdout.contains("hello admin"), "{}", stdout);
+    assert!(stdout.contains("greet ... ok"), "{}", stdout);
+}
+
+#[test]
+fn run_suite() {
+    let server = server();
+    let report = std::env::temp_dir().join(format!("siderunner-suite-{}.xml", std::process::id()));
+    let output = siderunner(
+        &server,
+        &[
+            "--suite",
+            "Default Suite",
+            "--junit",
+            report.to_str().unwrap(),
+            "tests/resources/basic/test.side.json",
+        ],
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let xml = std::fs::read_to_string(&report).expect("Failed to read a report");
+    std::fs::remove_file(&report).unwrap();
+
+    assert!(output.status.success(), "{}", stdout);
+    // Tests of a suite are reported one by one.
+    assert!(stdout.contains("issue324 ... ok"), "{}", stdout);
+    assert!(
+        xml.contains(r#"<testcase name="issue324" classname="test""#),
+        "{}",
+        xml
+    );
+}
+
+#[test]
+fn run_unknown_file() {
+    let server = server();
+    let report =
+        std::env::temp_dir().join(format!("siderunner-unknown-{}.xml", std::process::id()));
+    let output = siderunner(
+        &server,
+        &[
+            "--junit",
+            report.to_str().unwrap(),
+            "tests/resources/missing.side.json",
+            "tests/resources/basic/test.side.json",
+        ],
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let xml = std::fs::read_to_string(&report).expect("Failed to read a report");
+    std::fs::remove_file(&report).unwrap();
+
+    assert_eq!(output.status.code(), Some(2));
+    // The rest of files are run and reported anyway.
+    assert!(stdout.contains("issue324 ... ok"), "{}", stdout);
+    assert!(
+        xml.contains(r#"<testsuites tests="2" failures="1""#),
+        "{}",
+        xml
+    );
+    assert!(
+        xml.contains(r#"<testsuite name="tests/resources/missing.side.json""#),
+        "{}",
+        xml
+    );
+}
+
+#[test]
+fn write_junit_report() {
+    let server = server();
+    let report = std::env::temp_dir().join(format!("siderunner-junit-{}.xml", std::process::id()));
+    let output = siderunner(
+        &server,
+        &[
+            "--junit",
+            report.to_str().unwrap(),
+            "tests/resources/basic/test.side.json",
+        ],
+    );
+    let xml = std::fs::read_to_string(&report).expect("Failed to read a report");
+    std::fs::remove_file(&report).unwrap();
+
+    assert!(output.status.success());
+    assert!(
+        xml.contains(r#"<testsuites tests="1" failures="0""#),
+        "{}",
+        xml
+    );
+    assert!(
+        xml.contains(r#"<testcase name="issue324" classname="test""#),
+        "{}",
+        xml
+    );
+}
+
+#[test]
+fn write_healed_file() {
+    let server = WebdriverServer::start().expect("Failed to start a server");
+    server.add_page(
+        "http://staging.test/index.html",
+        r#"<button id="submit">Submit</button>"#,
+    );
+
+    let dir = std::env::temp_dir().join(format!("siderunner-heal-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("test.side");
+    std::fs::write(
+        &path,
+        r#"{
+            "id": "1", "version": "2.0", "name": "heal", "url": "http://production.test",
+            "tests": [{
+                "id": "t1",
+                "name": "login",
+                "commands": [
+                    { "id": "c1", "comment": "", "command": "open", "target": "/index.html", "targets": [], "value": "" },
+                    {
+                        "id": "c2", "comment": "", "command": "click", "target": "id=old",
+                        "targets": [["id=old", "id"], ["id=submit", "id"]], "value": ""
+                    }
+                ]
+            }],
+            "suites": [],
+            "urls": ["http://production.test"],
+            "plugins": []
+        }"#,
+    )
+    .unwrap();
+
+    let output = siderunner(
+        &server,
+        &[
+            "--heal",
+            "--base-url",
+            "http://staging.test",
+            path.to_str().unwrap(),
+        ],
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let healed = std::fs::read_to_string(dir.join("test.healed.side"));
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert!(output.status.success(), "{}", stdout);
+    assert!(stdout.contains("id=old -> id=submit"), "{}", stdout);
+    let healed: serde_json::Value = serde_json::from_str(&healed.unwrap()).unwrap();
+    // A base URL of the run isn't written into the corrected file.
+    assert_eq!(healed["url"], "http://production.test");
+    assert_eq!(healed["tests"][0]["commands"][1]["target"], "id=submit");
+}
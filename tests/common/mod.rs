@@ -0,0 +1,96 @@
+//! Runs `.side` files and checks that only commands commented as `FAIL` fail.
+
+use siderunner::{parse, RunnerError};
+use std::fs::File;
+
+pub async fn check(path: &str, webdriver_url: &str) {
+    let mut file = File::open(path).expect("Failed to read a file");
+    let side_file = parse(&mut file).expect("Failed to parse a file");
+
+    if let Err(err) = run(&side_file, webdriver_url).await {
+        let test = side_file
+            .tests
+            .iter()
+            .find(|test| test.name.as_str() == err.test.as_ref().unwrap())
+            .unwrap();
+        let failed_command = &test.commands[err.index];
+        if failed_command.comment != "FAIL" {
+            panic!("Failed to run a file {:?} test: {:?}", path, err);
+        }
+    }
+}
+
+/// Runs a file against a backend set by `SIDERUNNER_BACKEND` environment variable
+/// in case both backends are turned on.
+#[cfg(all(feature = "thirtyfour_backend", feature = "fantoccini_backend"))]
+async fn run(side_file: &siderunner::File, webdriver_url: &str) -> Result<(), RunnerError> {
+    match std::env::var("SIDERUNNER_BACKEND").as_deref() {
+        Ok("fantoccini") => run_fantoccini(side_file, webdriver_url).await,
+        _ => run_thirtyfour(side_file, webdriver_url).await,
+    }
+}
+
+#[cfg(all(feature = "thirtyfour_backend", not(feature = "fantoccini_backend")))]
+async fn run(side_file: &siderunner::File, webdriver_url: &str) -> Result<(), RunnerError> {
+    run_thirtyfour(side_file, webdriver_url).await
+}
+
+#[cfg(all(feature = "fantoccini_backend", not(feature = "thirtyfour_backend")))]
+async fn run(side_file: &siderunner::File, webdriver_url: &str) -> Result<(), RunnerError> {
+    run_fantoccini(side_file, webdriver_url).await
+}
+
+#[cfg(feature = "thirtyfour_backend")]
+async fn run_thirtyfour(
+    side_file: &siderunner::File,
+    webdriver_url: &str,
+) -> Result<(), RunnerError> {
+    use siderunner::Runner;
+    use thirtyfour::{Capabilities, DesiredCapabilities, WebDriver};
+
+    let mut cops = DesiredCapabilities::chrome();
+    cops.set_headless()
+        .expect("Failed to set a headless setting");
+    cops.set_unexpected_alert_behaviour(thirtyfour::AlertBehaviour::Ignore)
+        .expect("Failed to set an option setting");
+    let wb = WebDriver::new(webdriver_url, cops)
+        .await
+        .expect("Failed to create a webdriver");
+
+    let mut runner = Runner::new(&wb);
+    let result = runner.run(side_file).await;
+    wb.quit().await.expect("Failed to stop a webdriver");
+
+    result
+}
+
+#[cfg(feature = "fantoccini_backend")]
+async fn run_fantoccini(
+    side_file: &siderunner::File,
+    webdriver_url: &str,
+) -> Result<(), RunnerError> {
+    use siderunner::Runner;
+
+    let mut caps = serde_json::Map::new();
+    caps.insert(
+        "goog:chromeOptions".to_string(),
+        serde_json::json!({ "args": ["--headless"] }),
+    );
+    caps.insert(
+        "unhandledPromptBehavior".to_string(),
+        serde_json::json!("ignore"),
+    );
+    let mut client = fantoccini::ClientBuilder::native()
+        .capabilities(caps)
+        .connect(webdriver_url)
+        .await
+        .expect("Failed to create a webdriver");
+
+    let backend = siderunner::webdriver::fantoccini::Client::new(client.clone(), webdriver_url)
+        .expect("Failed to parse a webdriver url");
+    let mut runner = Runner::new(backend);
+    let result = runner.run(side_file).await;
+    client.close().await.expect("Failed to stop a webdriver");
+
+    result
+}